@@ -0,0 +1,79 @@
+use sidekick::app::App;
+use sidekick::entities::rectangle::Rectangle;
+use sidekick::input::Key;
+use sidekick::types::color::Color;
+use sidekick::types::vector2::Vector2;
+
+fn main() {
+    // Create a sidekick app
+    let mut app = App::new();
+
+    // Follow the player smoothly, only moving once the player leaves a small area around the screen center
+    app.game_view.follow_settings.dead_zone = Vector2 { x: 60., y: 40. };
+    app.game_view.follow_settings.damping = 0.3;
+    app.game_view.follow_settings.look_ahead = 0.4;
+    // Never show anything outside the level
+    app.game_view.bounds = Some((
+        Vector2 {
+            x: -1000.,
+            y: -600.,
+        },
+        Vector2 { x: 1000., y: 600. },
+    ));
+
+    // A player moved with WASD or arrow keys
+    let mut player = Rectangle::new();
+    player.fill_color = Color::yellow();
+
+    // A grid of tiles to see the camera move
+    let mut tiles: Vec<Rectangle> = Vec::new();
+    for x in -10..=10 {
+        for y in -6..=6 {
+            let mut tile = Rectangle::new();
+            tile.game_object.transform.position = Vector2 {
+                x: x as f32 * 100.,
+                y: y as f32 * 100.,
+            };
+            tile.game_object.transform.size = Vector2 { x: 10., y: 10. };
+            tiles.push(tile);
+        }
+    }
+
+    // Initialize app before first frame update
+    // Note: Dynamic environment should be initialized outside of init
+    let init = move |_app: &mut App| {};
+
+    // Update and render game objects every frame
+    let update = move |app: &mut App| {
+        let movement = Vector2 {
+            x: app.input.get_axis_horizontal(),
+            y: -app.input.get_axis_vertical(),
+        };
+        player.game_object.r#move(movement * 5.);
+
+        // Zoom with Z and X, rotate with Q and E
+        if app.input.is_key_down(Key::Z) {
+            app.game_view.zoom *= 1.01;
+        }
+        if app.input.is_key_down(Key::X) {
+            app.game_view.zoom /= 1.01;
+        }
+        if app.input.is_key_down(Key::Q) {
+            app.game_view.game_object.transform.rotation -= 0.01;
+        }
+        if app.input.is_key_down(Key::E) {
+            app.game_view.game_object.transform.rotation += 0.01;
+        }
+
+        // follow is called every frame with the target's current transform
+        app.game_view.follow(&player.game_object.transform);
+
+        for tile in tiles.iter_mut() {
+            tile.draw(app);
+        }
+        player.draw(app);
+    };
+
+    // Run app
+    app.run(init, update);
+}
@@ -0,0 +1,62 @@
+use sidekick::app::App;
+use sidekick::components::renderable::Renderable;
+use sidekick::components::transform::Transform;
+use sidekick::ecs::query::Query;
+use sidekick::ecs::world::World;
+use sidekick::entities::circle::Circle;
+use sidekick::entities::rectangle::Rectangle;
+use sidekick::types::vector2::Vector2;
+
+/// A user-defined component
+struct Velocity(Vector2);
+
+fn main() {
+    // Create a sidekick app
+    let app = App::new();
+
+    // Initialize app before first frame update
+    // Note: Dynamic environment should be initialized outside of init
+    let init = move |app: &mut App| {
+        // Spawn a few moving entities
+        for i in 0..5 {
+            let entity = app.spawn_entity();
+
+            let mut transform = Transform::new();
+            transform.position = Vector2 {
+                x: -200. + i as f32 * 100.,
+                y: 0.,
+            };
+            app.world.insert(entity, transform);
+            app.world
+                .insert(entity, Velocity(Vector2::up() * (i as f32 + 1.) * 20.));
+
+            let renderable = if i % 2 == 0 {
+                Renderable::Rectangle(Rectangle::new())
+            } else {
+                Renderable::Circle(Circle::new())
+            };
+            app.world.insert(entity, renderable);
+        }
+
+        // Move every entity that has both a transform and a velocity
+        app.world.add_system(|world: &mut World, app: &mut App| {
+            let query = Query::new().with::<Transform>().with::<Velocity>();
+            for entity in query.entities(world) {
+                let velocity = world.get::<Velocity>(entity).unwrap().0;
+                let transform = world.get_mut::<Transform>(entity).unwrap();
+                transform.position += velocity * app.time.frame_time();
+
+                // Wrap around the screen
+                if transform.position.y > app.height() as f32 / 2. {
+                    transform.position.y = -(app.height() as f32) / 2.;
+                }
+            }
+        });
+    };
+
+    // Entities are updated by systems and drawn automatically
+    let update = move |_app: &mut App| {};
+
+    // Run app
+    app.run(init, update);
+}
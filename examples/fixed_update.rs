@@ -0,0 +1,56 @@
+use sidekick::app::App;
+use sidekick::entities::rectangle::Rectangle;
+use sidekick::types::vector2::Vector2;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const SPEED: f32 = 200.;
+
+fn main() {
+    // Create a sidekick app
+    let app = App::new();
+
+    // State shared between fixed update and update
+    let box_state = Rc::new(RefCell::new(Rectangle::new()));
+    let previous_position = Rc::new(RefCell::new(Vector2::zero()));
+    let mut direction = Vector2::right();
+
+    // Initialize app before first frame update
+    // Note: Dynamic environment should be initialized outside of init
+    let init = move |app: &mut App| {
+        // Simulate at 50 steps per second regardless of how fast frames are rendered
+        app.time.target_frame_rate = 50;
+    };
+
+    // Simulate movement at a fixed time step, so speed does not depend on frame rate
+    let fixed_box = Rc::clone(&box_state);
+    let fixed_previous_position = Rc::clone(&previous_position);
+    let fixed_update = move |app: &mut App| {
+        let mut rectangle = fixed_box.borrow_mut();
+        *fixed_previous_position.borrow_mut() = rectangle.game_object.transform.position;
+
+        rectangle
+            .game_object
+            .r#move(direction * SPEED * app.time.fixed_frame_time());
+
+        // Bounce between window edges
+        if rectangle.game_object.transform.position.x.abs() > app.width() as f32 / 2. {
+            direction = -direction;
+        }
+    };
+
+    // Render between the last two simulated positions every frame
+    let mut render_box = Rectangle::new();
+    let update = move |app: &mut App| {
+        let previous = *previous_position.borrow();
+        let current = box_state.borrow().game_object.transform.position;
+
+        render_box.game_object.transform.position =
+            previous + (current - previous) * app.time.interpolation_alpha();
+        render_box.draw(app);
+    };
+
+    // Run app
+    app.run_fixed(init, fixed_update, update);
+}
@@ -0,0 +1,66 @@
+use sidekick::app::App;
+use sidekick::input::gamepad::{GamepadAxis, GamepadEvent, GamepadStick, VirtualGamepadBackend};
+use sidekick::input::GamepadButton;
+
+fn main() {
+    // Create a sidekick app
+    let mut app = App::new();
+
+    // Gamepads are read from a backend, here a virtual backend driven from code so this example needs no hardware.
+    // With the gilrs feature, App::run reads hardware gamepads when no backend is set
+    let backend = VirtualGamepadBackend::new();
+    app.input.set_gamepad_backend(backend.clone());
+    let gamepad = backend.connect("Virtual Gamepad");
+
+    // Initialize app before first frame update
+    // Note: Dynamic environment should be initialized outside of init
+    let init = move |_app: &mut App| {};
+
+    let mut frame = 0;
+    // Update and render game objects every frame
+    let update = move |app: &mut App| {
+        // Drive the virtual gamepad as if a player was holding it
+        match frame {
+            1 => backend.press(gamepad, GamepadButton::South),
+            2 => backend.release(gamepad, GamepadButton::South),
+            3 => backend.set_axis(gamepad, GamepadAxis::LeftStickX, 0.1),
+            4 => backend.set_axis(gamepad, GamepadAxis::LeftStickX, 0.8),
+            5 => backend.disconnect(gamepad),
+            _ => {}
+        }
+        frame += 1;
+
+        // gamepad_events returns connections, disconnections, buttons and axes received this frame
+        for event in app.input.gamepad_events() {
+            match event {
+                GamepadEvent::Connected(id) => {
+                    println!("{} connected: {:?}", id, app.input.gamepad_name(*id))
+                }
+                GamepadEvent::Disconnected(id) => println!("{} disconnected", id),
+                _ => {}
+            }
+        }
+
+        // on_gamepad_button_down returns true for the frame a gamepad button is pressed
+        if app
+            .input
+            .on_gamepad_button_down(gamepad, GamepadButton::South)
+        {
+            println!("South button pressed");
+            // rumble returns whether the gamepad supports rumble
+            app.input.rumble(gamepad, 1., 0.5, 0.2);
+        }
+
+        // gamepad_stick returns stick position with a dead zone, small movements like 0.1 are ignored
+        if app.input.is_gamepad_connected(gamepad) {
+            println!(
+                "Left stick: {}, horizontal axis: {}",
+                app.input.gamepad_stick(gamepad, GamepadStick::Left),
+                app.input.get_axis_horizontal()
+            );
+        }
+    };
+
+    // Run app without a window for a few frames
+    app.run_headless(init, update, 8);
+}
@@ -0,0 +1,26 @@
+use sidekick::app::App;
+use sidekick::entities::rectangle::Rectangle;
+use sidekick::types::vector2::Vector2;
+
+fn main() {
+    // Create a sidekick app
+    let app = App::new();
+
+    // A rectangle that moves to the right every frame
+    let mut rectangle = Rectangle::new();
+
+    // Initialize app before first frame update
+    // Note: Dynamic environment should be initialized outside of init
+    let init = move |_app: &mut App| {};
+
+    // Update and render game objects every frame
+    let update = move |app: &mut App| {
+        rectangle.game_object.r#move(Vector2::right());
+        rectangle.draw(app);
+    };
+
+    // Run app without a window for 60 frames and inspect what was drawn
+    let frames = app.run_headless(init, update, 60);
+    println!("Ran {} frames", frames.len());
+    println!("Shapes drawn on last frame: {:?}", frames.last());
+}
@@ -0,0 +1,70 @@
+use sidekick::app::App;
+use sidekick::components::renderable::Renderable;
+use sidekick::components::transform::Transform;
+use sidekick::ecs::world::{Entity, World};
+use sidekick::entities::circle::Circle;
+use sidekick::entities::rectangle::Rectangle;
+use sidekick::types::vector2::Vector2;
+
+/// A user-defined component that spins an entity, and every child with it
+struct Spin(f32);
+
+/// Spawn an entity with a transform and a shape
+fn spawn(app: &mut App, position: Vector2, renderable: Renderable) -> Entity {
+    let entity = app.spawn_entity();
+
+    let mut transform = Transform::new();
+    transform.position = position;
+    app.world.insert(entity, transform);
+    app.world.insert(entity, renderable);
+    return entity;
+}
+
+fn main() {
+    // Create a sidekick app
+    let app = App::new();
+
+    // Initialize app before first frame update
+    // Note: Dynamic environment should be initialized outside of init
+    let init = move |app: &mut App| {
+        // A spinning sun with a planet orbiting it, and a moon orbiting the planet
+        let sun = spawn(
+            app,
+            Vector2::zero(),
+            Renderable::Rectangle(Rectangle::new()),
+        );
+        app.world.insert(sun, Spin(0.5));
+
+        let planet = spawn(
+            app,
+            Vector2 { x: 150., y: 0. },
+            Renderable::Circle(Circle::new()),
+        );
+        app.world.insert(planet, Spin(2.0));
+        app.world.set_parent(planet, Some(sun));
+
+        // Children transforms are relative to their parent, so the moon is half the size of the planet
+        let moon = spawn(
+            app,
+            Vector2 { x: 200., y: 0. },
+            Renderable::Circle(Circle::new()),
+        );
+        app.world.set_parent(moon, Some(planet));
+        app.world.get_mut::<Transform>(moon).unwrap().scale = Vector2 { x: 0.5, y: 0.5 };
+
+        // Spin every entity that has a Spin component
+        app.world.add_system(|world: &mut World, app: &mut App| {
+            for entity in world.query::<Spin>() {
+                let speed = world.get::<Spin>(entity).unwrap().0;
+                world.get_mut::<Transform>(entity).unwrap().rotation +=
+                    speed * app.time.frame_time();
+            }
+        });
+    };
+
+    // Entities are updated by systems and drawn automatically
+    let update = move |_app: &mut App| {};
+
+    // Run app
+    app.run(init, update);
+}
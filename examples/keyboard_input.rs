@@ -1,4 +1,5 @@
 use sidekick::app::App;
+use sidekick::input::key_code::KeyCode;
 use sidekick::input::Key;
 
 fn main() {
@@ -34,6 +35,16 @@ fn main() {
         // println!("Horizontal: {}", app.input.get_axis_horizontal());
         // get_axis_vertical returns sum of vertical input keys (WS and Up Down arrows)
         // println!("Vertical: {}", app.input.get_axis_vertical());
+
+        // Key is a physical key, KeyCode is the key it produces in the current keyboard layout
+        // On AZERTY keyboards Key::Q produces KeyCode::A
+        if app.input.on_key_code_down(KeyCode::A) {
+            println!("A pressed");
+        }
+        // key_display_name returns the name of a physical key in the current layout, for on-screen prompts
+        if app.input.on_key_down(Key::Q) {
+            println!("{} pressed", app.input.key_display_name(Key::Q));
+        }
     };
 
     // Run app
@@ -0,0 +1,53 @@
+use sidekick::app::App;
+use sidekick::entities::circle::Circle;
+use sidekick::entities::rectangle::Rectangle;
+use sidekick::types::color::Color;
+use sidekick::types::vector2::Vector2;
+
+fn main() {
+    // Create a sidekick app
+    let mut app = App::new();
+    app.game_view.color = Color::black();
+
+    // A level of 40 000 tiles, added once as static geometry instead of drawn every frame.
+    // Only the tiles in view are drawn, from GPU buffers
+    for x in -100..100 {
+        for y in -100..100 {
+            let mut tile = Rectangle::new();
+            tile.game_object.transform.position = Vector2 {
+                x: x as f32 * 60.,
+                y: y as f32 * 60.,
+            };
+            tile.fill_color = if (x + y) % 2 == 0 {
+                Color::teal()
+            } else {
+                Color::blue()
+            };
+            tile.add_static(&mut app);
+        }
+    }
+
+    // A player moved with WASD or arrow keys, drawn every frame
+    let mut player = Circle::new();
+    player.fill_color = Color::red();
+
+    // Initialize app before first frame update
+    // Note: Dynamic environment should be initialized outside of init
+    let init = move |_app: &mut App| {};
+
+    // Update and render game objects every frame
+    let update = move |app: &mut App| {
+        let movement = Vector2 {
+            x: app.input.get_axis_horizontal(),
+            y: app.input.get_axis_vertical(),
+        };
+        player.game_object.r#move(movement * 10.);
+        app.game_view.follow(&player.game_object.transform);
+
+        // Shapes out of view are skipped, so drawing is cheap however big the level is
+        player.draw(app);
+    };
+
+    // Run app
+    app.run(init, update);
+}
@@ -36,6 +36,21 @@ fn main() {
             // is_mouse_entered returns whether cursor is hovering over current app window
             println!("{}", app.input.is_mouse_entered());
         }
+
+        // Back and forward side buttons
+        if app.input.on_mouse_button_down(MouseButton::Back) {
+            println!("Back mouse button pressed");
+        }
+
+        // scroll_lines returns how far mouse wheel scrolled this frame, scroll_pixels is used by touchpads
+        if app.input.scroll_lines().y != 0. {
+            println!("Scrolled {} lines", app.input.scroll_lines().y);
+        }
+        // mouse_delta returns raw mouse movement this frame, even when cursor is confined
+        // modifiers returns which of Shift, Ctrl, Alt and Logo are held down
+        if app.input.modifiers().shift {
+            println!("Mouse moved {}", app.input.mouse_delta());
+        }
     };
 
     // Run app
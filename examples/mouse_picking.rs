@@ -0,0 +1,61 @@
+use sidekick::app::App;
+use sidekick::entities::rectangle::Rectangle;
+use sidekick::input::{Key, MouseButton};
+use sidekick::types::color::Color;
+use sidekick::types::vector2::Vector2;
+
+fn main() {
+    // Create a sidekick app
+    let app = App::new();
+
+    // A row of rectangles to click on
+    let mut rectangles: Vec<Rectangle> = (0..5)
+        .map(|i| {
+            let mut rectangle = Rectangle::new();
+            rectangle.game_object.transform.position = Vector2 {
+                x: (i as f32 - 2.) * 120.,
+                y: 0.,
+            };
+            rectangle.game_object.transform.size = Vector2 { x: 80., y: 80. };
+            rectangle
+        })
+        .collect();
+
+    // Initialize app before first frame update
+    // Note: Dynamic environment should be initialized outside of init
+    let init = move |_app: &mut App| {};
+
+    // Update and render game objects every frame
+    let update = move |app: &mut App| {
+        // Zoom with mouse wheel and rotate with Q and E, picking still works
+        app.game_view.zoom = (app.game_view.zoom + app.input.scroll_lines().y * 0.1).max(0.1);
+        if app.input.is_key_down(Key::Q) {
+            app.game_view.game_object.transform.rotation -= 0.02;
+        }
+        if app.input.is_key_down(Key::E) {
+            app.game_view.game_object.transform.rotation += 0.02;
+        }
+
+        // mouse_world_position converts mouse position from screen to world space
+        let mouse = app.mouse_world_position();
+        for rectangle in rectangles.iter_mut() {
+            let position = rectangle.game_object.transform.position;
+            let half_size = rectangle.game_object.transform.size / 2.;
+            let is_hovered = (mouse.x - position.x).abs() <= half_size.x
+                && (mouse.y - position.y).abs() <= half_size.y;
+
+            if is_hovered && app.input.on_mouse_button_down(MouseButton::Left) {
+                println!("Clicked rectangle at {}", position);
+            }
+            rectangle.fill_color = if is_hovered {
+                Color::yellow()
+            } else {
+                Color::white()
+            };
+            rectangle.draw(app);
+        }
+    };
+
+    // Run app
+    app.run(init, update);
+}
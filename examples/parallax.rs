@@ -0,0 +1,79 @@
+use sidekick::app::App;
+use sidekick::entities::rectangle::Rectangle;
+use sidekick::types::color::Color;
+use sidekick::types::vector2::Vector2;
+
+fn main() {
+    // Create a sidekick app
+    let mut app = App::new();
+    app.game_view.color = Color {
+        r: 0.4,
+        g: 0.6,
+        b: 0.9,
+        a: 1.,
+    };
+
+    // Layers further back have a lower sort order and move less with the game view
+    let mountains = app.layers.add("Mountains", -20);
+    let hills = app.layers.add("Hills", -10);
+    let hud = app.layers.add("HUD", 10);
+    if let Some(settings) = app.layers.settings_mut(mountains) {
+        settings.parallax = Vector2 { x: 0.2, y: 1. };
+        // Repeat every 300 world units horizontally, forever
+        settings.repeat = Vector2 { x: 300., y: 0. };
+    }
+    if let Some(settings) = app.layers.settings_mut(hills) {
+        settings.parallax = Vector2 { x: 0.5, y: 1. };
+        settings.repeat = Vector2 { x: 200., y: 0. };
+    }
+    // The HUD stays in place on screen
+    if let Some(settings) = app.layers.settings_mut(hud) {
+        settings.parallax = Vector2::zero();
+    }
+
+    // A single mountain and hill are enough, their layers repeat them
+    let mut mountain = Rectangle::new();
+    mountain.layer = mountains;
+    mountain.game_object.transform.size = Vector2 { x: 200., y: 300. };
+    mountain.fill_color = Color::teal();
+    let mut hill = Rectangle::new();
+    hill.layer = hills;
+    hill.game_object.transform.position = Vector2 { x: 0., y: 100. };
+    hill.game_object.transform.size = Vector2 { x: 120., y: 120. };
+    hill.fill_color = Color::green();
+
+    // A player on the default layer moved with A/D or arrow keys
+    let mut player = Rectangle::new();
+    player.game_object.transform.position = Vector2 { x: 0., y: 140. };
+    player.fill_color = Color::yellow();
+
+    // A health bar on the HUD layer
+    let mut health_bar = Rectangle::new();
+    health_bar.layer = hud;
+    health_bar.game_object.transform.position = Vector2 { x: -300., y: -250. };
+    health_bar.game_object.transform.size = Vector2 { x: 150., y: 20. };
+    health_bar.fill_color = Color::red();
+
+    // Initialize app before first frame update
+    // Note: Dynamic environment should be initialized outside of init
+    let init = move |_app: &mut App| {};
+
+    // Update and render game objects every frame
+    let update = move |app: &mut App| {
+        let movement = Vector2 {
+            x: app.input.get_axis_horizontal(),
+            y: 0.,
+        };
+        player.game_object.r#move(movement * 5.);
+        app.game_view.follow(&player.game_object.transform);
+
+        // Draw order does not matter, layers are sorted by their sort order
+        player.draw(app);
+        health_bar.draw(app);
+        hill.draw(app);
+        mountain.draw(app);
+    };
+
+    // Run app
+    app.run(init, update);
+}
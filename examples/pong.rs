@@ -1,6 +1,7 @@
 use sidekick::app::App;
 use sidekick::entities::line::Line;
 use sidekick::entities::rectangle::Rectangle;
+use sidekick::input::action::InputSource;
 use sidekick::input::Key;
 use sidekick::types::vector2::Vector2;
 
@@ -47,23 +48,36 @@ fn main() {
     let init = move |app: &mut App| {
         app.set_title("Pong");
         app.set_size(WIDTH, HEIGHT);
+
+        // Bind controls to actions so gameplay code does not depend on specific keys
+        app.input
+            .actions
+            .bind_action("quit", InputSource::Key(Key::Esc));
+        app.input.actions.bind_axis(
+            "player_1",
+            InputSource::Key(Key::S),
+            InputSource::Key(Key::W),
+        );
+        app.input.actions.bind_axis(
+            "player_2",
+            InputSource::Key(Key::K),
+            InputSource::Key(Key::I),
+        );
     };
     // Update and render game objects every frame
     let update = move |app: &mut App| {
-        if app.input.on_key_down(Key::Esc) {
+        if app.input.on_action_down("quit") {
             app.quit();
         }
 
-        // Players move based on keyboard input
+        // Players move based on input axes
         player_1.game_object.r#move(Vector2 {
             x: 0.,
-            y: -(app.input.is_key_down(Key::S) as i32 as f32)
-                + (app.input.is_key_down(Key::W) as i32 as f32),
+            y: app.input.axis("player_1"),
         });
         player_2.game_object.r#move(Vector2 {
             x: 0.,
-            y: -(app.input.is_key_down(Key::K) as i32 as f32)
-                + (app.input.is_key_down(Key::I) as i32 as f32),
+            y: app.input.axis("player_2"),
         });
 
         // Ball bounce around
@@ -127,7 +141,7 @@ fn main() {
                     ball_movement.x = -ball_movement.x;
                     ball_movement.y = rand::thread_rng().gen_range(-BALL_SPEED..BALL_SPEED);
 
-                    app.game_view.start_shaking(10., 0.5, 1.);
+                    app.game_view.start_shaking(5., 0.5, 1.);
                 }
             }
         } else if ball.game_object.transform.position.x + ball.game_object.transform.size.x / 2.
@@ -150,7 +164,7 @@ fn main() {
                     ball_movement.x = -ball_movement.x;
                     ball_movement.y = rand::thread_rng().gen_range(-BALL_SPEED..BALL_SPEED);
 
-                    app.game_view.start_shaking(10., 0.5, 1.);
+                    app.game_view.start_shaking(5., 0.5, 1.);
                 }
             }
         }
@@ -0,0 +1,57 @@
+use sidekick::app::App;
+use sidekick::input::action::InputSource;
+use sidekick::input::Key;
+
+const BINDINGS_PATH: &str = "bindings.toml";
+
+fn main() {
+    // Create a sidekick app
+    let app = App::new();
+
+    // Initialize app before first frame update
+    // Note: Dynamic environment should be initialized outside of init
+    let init = move |app: &mut App| {
+        // Default bindings, overridden by the bindings file if it exists
+        app.input
+            .actions
+            .bind_action("jump", InputSource::Key(Key::Space));
+        if let Err(err) = app.input.actions.load(BINDINGS_PATH) {
+            println!("Using default bindings: {}", err);
+        }
+        println!("Press R to rebind jump");
+    };
+
+    // Update and render game objects every frame
+    let update = move |app: &mut App| {
+        if app.input.on_action_down("jump") {
+            println!("Jump!");
+        }
+
+        // Listen for the next key or mouse button pressed
+        if !app.input.is_listening() && app.input.on_key_down(Key::R) {
+            app.input.listen_for_input();
+            println!("Press a key or mouse button to bind to jump");
+        }
+
+        if let Some(source) = app.input.captured_input() {
+            // Replace the first binding of jump, or add one if jump has none
+            let old = match app.input.actions.action_bindings("jump").first() {
+                Some(old) => *old,
+                None => source,
+            };
+            match app.input.actions.rebind_action("jump", old, source) {
+                Ok(()) => {
+                    println!("Jump bound to {}", source);
+                    // Save bindings so they survive restarts
+                    if let Err(err) = app.input.actions.save(BINDINGS_PATH) {
+                        println!("Could not save bindings: {}", err);
+                    }
+                }
+                Err(conflict) => println!("Could not rebind jump: {}", conflict),
+            }
+        }
+    };
+
+    // Run app
+    app.run(init, update);
+}
@@ -0,0 +1,65 @@
+use sidekick::app::App;
+use sidekick::entities::rectangle::Rectangle;
+use sidekick::input::recording::InputRecording;
+use sidekick::input::Key;
+
+const RECORDING_PATH: &str = "recordings/session.skir";
+
+fn main() {
+    // Create a sidekick app
+    let mut app = App::new();
+
+    // A rectangle moved with WASD or arrow keys
+    let mut rectangle = Rectangle::new();
+
+    // Run with "--replay" to replay the last saved session without a window
+    let is_replaying = std::env::args().any(|arg| arg == "--replay");
+    let frames = if is_replaying {
+        let recording = InputRecording::load(RECORDING_PATH).expect("No recording saved yet");
+        let frames = recording.frames() as usize;
+        // play_recording replays recorded input from the next frame
+        app.input.play_recording(recording);
+        frames
+    } else {
+        0
+    };
+
+    // Initialize app before first frame update
+    // Note: Dynamic environment should be initialized outside of init
+    let init = move |app: &mut App| {
+        // start_recording records every keyboard, text, modifier and mouse event with its frame
+        if !is_replaying {
+            app.input.start_recording();
+        }
+    };
+
+    // Update and render game objects every frame
+    let update = move |app: &mut App| {
+        // Movement depends on frame time, which replays as recorded
+        let speed = 240. * app.time.frame_time();
+        rectangle.game_object.transform.position.x += app.input.get_axis_horizontal() * speed;
+        rectangle.game_object.transform.position.y -= app.input.get_axis_vertical() * speed;
+        rectangle.draw(app);
+
+        // Save the session so far when Esc is pressed
+        if app.input.on_key_down(Key::Esc) && app.input.is_recording() {
+            if let Some(recording) = app.input.stop_recording() {
+                match recording.save(RECORDING_PATH) {
+                    Ok(()) => println!("Saved {} frames to {}", recording.frames(), RECORDING_PATH),
+                    Err(err) => println!("Could not save recording: {}", err),
+                }
+            }
+            app.quit();
+        }
+    };
+
+    if is_replaying {
+        // Replaying headless runs every frame with the frame time it was recorded with, so the session plays back frame-for-frame
+        let frame_shapes = app.run_headless(init, update, frames);
+        println!("Replayed {} frames", frame_shapes.len());
+        println!("Shapes drawn on last frame: {:?}", frame_shapes.last());
+    } else {
+        // Run app
+        app.run(init, update);
+    }
+}
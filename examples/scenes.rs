@@ -0,0 +1,117 @@
+use sidekick::app::App;
+use sidekick::entities::circle::Circle;
+use sidekick::entities::rectangle::Rectangle;
+use sidekick::input::Key;
+use sidekick::scene::{Scene, Transition};
+use sidekick::types::color::Color;
+use sidekick::types::vector2::Vector2;
+
+/// Main menu, press Enter to start
+struct Menu {
+    button: Rectangle,
+}
+
+impl Scene for Menu {
+    fn enter(&mut self, app: &mut App) {
+        app.game_view.color = Color::black();
+        self.button.game_object.transform.size = Vector2 { x: 200., y: 60. };
+    }
+    fn update(&mut self, app: &mut App) {
+        if app.input.on_key_down(Key::Enter) {
+            app.scenes.replace(Level::new(), Transition::Fade(0.5));
+        }
+    }
+    fn draw(&mut self, app: &mut App) {
+        self.button.draw(app);
+    }
+}
+
+/// A level with a moving player, press Esc to pause
+struct Level {
+    player: Circle,
+}
+
+impl Level {
+    fn new() -> Self {
+        return Self {
+            player: Circle::new(),
+        };
+    }
+}
+
+impl Scene for Level {
+    fn update(&mut self, app: &mut App) {
+        let movement = Vector2 {
+            x: app.input.get_axis_horizontal(),
+            y: app.input.get_axis_vertical(),
+        } * 200.
+            * app.time.frame_time();
+        self.player.game_object.r#move(movement);
+
+        if app.input.on_key_down(Key::Esc) {
+            app.scenes.push(Pause::new(), Transition::None);
+        }
+    }
+    fn draw(&mut self, app: &mut App) {
+        self.player.draw(app);
+    }
+    fn pause(&mut self, _app: &mut App) {
+        println!("Level paused");
+    }
+    fn resume(&mut self, _app: &mut App) {
+        println!("Level resumed");
+    }
+}
+
+/// Pause screen drawn over the level, press Esc to resume or Q to quit to menu
+struct Pause {
+    panel: Rectangle,
+}
+
+impl Pause {
+    fn new() -> Self {
+        let mut panel = Rectangle::new();
+        panel.game_object.transform.size = Vector2 { x: 300., y: 200. };
+        panel.fill_color = Color {
+            r: 0.,
+            g: 0.,
+            b: 0.,
+            a: 0.5,
+        };
+        panel.stroke_size = 2.;
+        return Self { panel };
+    }
+}
+
+impl Scene for Pause {
+    fn update(&mut self, app: &mut App) {
+        if app.input.on_key_down(Key::Esc) {
+            app.scenes.pop(Transition::None);
+        } else if app.input.on_key_down(Key::Q) {
+            app.scenes.pop(Transition::None);
+            app.scenes.replace(
+                Menu {
+                    button: Rectangle::new(),
+                },
+                Transition::Fade(0.5),
+            );
+        }
+    }
+    fn draw(&mut self, app: &mut App) {
+        self.panel.draw(app);
+    }
+    // Level is still drawn under the pause screen
+    fn is_overlay(&self) -> bool {
+        return true;
+    }
+}
+
+fn main() {
+    // Create a sidekick app
+    let app = App::new();
+
+    // Run app starting at the main menu
+    app.run_scene(Menu {
+        button: Rectangle::new(),
+    });
+}
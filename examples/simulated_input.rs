@@ -0,0 +1,42 @@
+use sidekick::app::App;
+use sidekick::entities::rectangle::Rectangle;
+use sidekick::input::{Key, MouseButton};
+use sidekick::types::vector2::Vector2;
+
+fn main() {
+    // Create a sidekick app
+    let app = App::new();
+
+    // A rectangle moved with WASD or arrow keys
+    let mut rectangle = Rectangle::new();
+
+    // Initialize app before first frame update
+    // Note: Dynamic environment should be initialized outside of init
+    let init = move |app: &mut App| {
+        // Simulated input goes through the same path as a player pressing keys, starting from the next frame
+        app.input.simulate_key_hold(Key::D, 30);
+    };
+
+    // Update and render game objects every frame
+    let update = move |app: &mut App| {
+        // A simple bot: once the rectangle stops, click where it is
+        if !app.input.has_simulated_input() && app.input.is_mouse_button_up(MouseButton::Left) {
+            app.input
+                .simulate_mouse_move(rectangle.game_object.transform.position);
+            app.input.simulate_mouse_click(MouseButton::Left);
+        }
+        if app.input.on_mouse_button_down(MouseButton::Left) {
+            println!("Bot clicked at {}", app.input.mouse_position());
+            app.quit();
+        }
+
+        rectangle
+            .game_object
+            .r#move(Vector2::right() * app.input.get_axis_horizontal());
+        rectangle.draw(app);
+    };
+
+    // Run app without a window, simulated input works the same way in a window
+    let frames = app.run_headless(init, update, 60);
+    println!("Ran {} frames", frames.len());
+}
@@ -0,0 +1,53 @@
+use sidekick::app::App;
+use sidekick::entities::rectangle::Rectangle;
+use sidekick::input::Key;
+use sidekick::types::vector2::Vector2;
+
+const BULLET_SPEED: f32 = 400.;
+
+fn main() {
+    // Create a sidekick app
+    let app = App::new();
+
+    // Bullets currently flying
+    let mut bullets: Vec<Rectangle> = Vec::new();
+
+    // Initialize app before first frame update
+    // Note: Dynamic environment should be initialized outside of init
+    let init = move |app: &mut App| {
+        // Get notified whenever an object is spawned or destroyed
+        app.objects.on_spawn(|id| println!("Spawned object {}", id));
+        app.objects
+            .on_destroy(|id| println!("Destroyed object {}", id));
+    };
+
+    // Update and render game objects every frame
+    let update = move |app: &mut App| {
+        // Spawn a new bullet when space is pressed
+        if app.input.on_key_down(Key::Space) {
+            let mut bullet = Rectangle::new();
+            bullet.game_object.transform.size = Vector2 { x: 10., y: 4. };
+            bullet.game_object.spawn(app);
+            bullets.push(bullet);
+        }
+
+        for bullet in bullets.iter_mut() {
+            bullet
+                .game_object
+                .r#move(Vector2::right() * BULLET_SPEED * app.time.frame_time());
+
+            // Destroy bullets once they leave the screen
+            if bullet.game_object.transform.position.x > app.width() as f32 / 2. {
+                bullet.game_object.destroy(app);
+            }
+
+            bullet.draw(app);
+        }
+
+        // Forget about destroyed bullets
+        bullets.retain(|bullet| !bullet.game_object.is_destroyed(app));
+    };
+
+    // Run app
+    app.run(init, update);
+}
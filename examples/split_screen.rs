@@ -0,0 +1,93 @@
+use sidekick::app::App;
+use sidekick::entities::game_view::GameView;
+use sidekick::entities::rectangle::Rectangle;
+use sidekick::input::Key;
+use sidekick::rendering::viewport::Viewport;
+use sidekick::types::color::Color;
+use sidekick::types::vector2::Vector2;
+
+fn main() {
+    // Create a sidekick app
+    let mut app = App::new();
+
+    // Player 1 is shown on the left half of the screen by the main game view
+    app.game_view.viewport = Viewport::left_half();
+    app.game_view.color = Color {
+        r: 0.1,
+        g: 0.1,
+        b: 0.2,
+        a: 1.,
+    };
+    // Player 2 is shown on the right half by an additional camera
+    let mut camera = GameView::new();
+    camera.viewport = Viewport::right_half();
+    camera.color = Color {
+        r: 0.2,
+        g: 0.1,
+        b: 0.1,
+        a: 1.,
+    };
+    // Markers on their own layer are only visible to player 1
+    let markers = app.layers.add("Markers", 1);
+    camera.hide_layer(markers);
+    app.cameras.push(camera);
+
+    // Player 1 is moved with WASD, player 2 with arrow keys
+    let mut player1 = Rectangle::new();
+    player1.fill_color = Color::yellow();
+    player1.game_object.transform.position = Vector2 { x: -100., y: 0. };
+    let mut player2 = Rectangle::new();
+    player2.fill_color = Color::teal();
+    player2.game_object.transform.position = Vector2 { x: 100., y: 0. };
+
+    // A grid of tiles shared by both players, every other tile is a marker for player 1 only
+    let mut tiles: Vec<Rectangle> = Vec::new();
+    for x in -10..=10 {
+        for y in -6..=6 {
+            let mut tile = Rectangle::new();
+            tile.game_object.transform.position = Vector2 {
+                x: x as f32 * 100.,
+                y: y as f32 * 100.,
+            };
+            tile.game_object.transform.size = Vector2 { x: 10., y: 10. };
+            if (x + y) % 2 == 0 {
+                tile.layer = markers;
+                tile.fill_color = Color::green();
+            }
+            tiles.push(tile);
+        }
+    }
+
+    // Initialize app before first frame update
+    // Note: Dynamic environment should be initialized outside of init
+    let init = move |_app: &mut App| {};
+
+    // Update and render game objects every frame
+    let update = move |app: &mut App| {
+        let movement1 = Vector2 {
+            x: (app.input.is_key_down(Key::D) as i32 - app.input.is_key_down(Key::A) as i32) as f32,
+            y: (app.input.is_key_down(Key::S) as i32 - app.input.is_key_down(Key::W) as i32) as f32,
+        };
+        let movement2 = Vector2 {
+            x: (app.input.is_key_down(Key::Right) as i32 - app.input.is_key_down(Key::Left) as i32)
+                as f32,
+            y: (app.input.is_key_down(Key::Down) as i32 - app.input.is_key_down(Key::Up) as i32)
+                as f32,
+        };
+        player1.game_object.r#move(movement1 * 5.);
+        player2.game_object.r#move(movement2 * 5.);
+
+        // Each camera follows its own player
+        app.game_view.follow(&player1.game_object.transform);
+        app.cameras[0].follow(&player2.game_object.transform);
+
+        for tile in tiles.iter_mut() {
+            tile.draw(app);
+        }
+        player1.draw(app);
+        player2.draw(app);
+    };
+
+    // Run app
+    app.run(init, update);
+}
@@ -0,0 +1,43 @@
+use sidekick::app::App;
+use sidekick::input::text::{EditKey, TextInput};
+use sidekick::types::vector2::Vector2;
+
+fn main() {
+    // Create a sidekick app
+    let app = App::new();
+
+    // Name being typed and cursor position in characters
+    let mut name = String::new();
+    let mut cursor: usize = 0;
+
+    // Initialize app before first frame update
+    // Note: Dynamic environment should be initialized outside of init
+    let init = move |app: &mut App| {
+        // Show input method editor candidates near the top left of the screen
+        app.set_ime_position(Vector2 { x: 20., y: 20. });
+        println!("Type your name and press Enter");
+    };
+
+    // Update and render game objects every frame
+    let update = move |app: &mut App| {
+        if app.input.text_input().is_empty() {
+            return;
+        }
+
+        // Typed characters and editing keys (Backspace, Delete, arrows, Home, End) are applied in order
+        app.input.apply_text_input(&mut name, &mut cursor);
+        println!("{}", name);
+
+        let is_enter_pressed = app
+            .input
+            .text_input()
+            .iter()
+            .any(|input| matches!(input, TextInput::Edit(EditKey::Enter, _)));
+        if is_enter_pressed {
+            println!("Hello, {}!", name);
+        }
+    };
+
+    // Run app
+    app.run(init, update);
+}
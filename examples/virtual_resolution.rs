@@ -0,0 +1,65 @@
+use sidekick::app::App;
+use sidekick::entities::rectangle::Rectangle;
+use sidekick::input::Key;
+use sidekick::rendering::resolution::{ScalingPolicy, VirtualResolution};
+use sidekick::types::color::Color;
+use sidekick::types::vector2::Vector2;
+
+fn main() {
+    // Create a resizable sidekick app
+    let mut app = App::new();
+    app.set_resizable(true);
+
+    // Lay the game out in 320x180 virtual pixels, no matter how big the window is
+    app.set_virtual_resolution(VirtualResolution::new(
+        320,
+        180,
+        ScalingPolicy::PixelPerfect,
+    ));
+    app.game_view.color = Color::teal();
+
+    // A player that stays on screen, moved with WASD or arrow keys
+    let mut player = Rectangle::new();
+    player.game_object.transform.size = Vector2 { x: 16., y: 16. };
+    player.game_object.is_bounded = true;
+    player.fill_color = Color::yellow();
+
+    // A marker drawn where the mouse is pointing
+    let mut cursor = Rectangle::new();
+    cursor.game_object.transform.size = Vector2 { x: 4., y: 4. };
+    cursor.fill_color = Color::pink();
+
+    // Initialize app before first frame update
+    // Note: Dynamic environment should be initialized outside of init
+    let init = move |_app: &mut App| {};
+
+    // Update and render game objects every frame
+    let update = move |app: &mut App| {
+        // Switch scaling policy with number keys, then resize the window to compare
+        let policies = [
+            (Key::Num1, ScalingPolicy::Stretch),
+            (Key::Num2, ScalingPolicy::Fit),
+            (Key::Num3, ScalingPolicy::PixelPerfect),
+            (Key::Num4, ScalingPolicy::Expand),
+        ];
+        for (key, policy) in policies.iter() {
+            if app.input.on_key_down(*key) {
+                app.set_virtual_resolution(VirtualResolution::new(320, 180, *policy));
+            }
+        }
+
+        let movement = Vector2 {
+            x: app.input.get_axis_horizontal(),
+            y: -app.input.get_axis_vertical(),
+        };
+        player.game_object.r#move(movement * 2.);
+        player.draw(app);
+
+        // Mouse position is converted from window pixels to the virtual resolution
+        cursor.game_object.transform.position = app.mouse_world_position();
+        cursor.draw(app);
+    };
+
+    // Run app
+    app.run(init, update);
+}
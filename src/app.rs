@@ -1,19 +1,26 @@
+use crate::ecs::world::{Entity, World};
 use crate::entities::game_view::GameView;
+use crate::entities::registry::ObjectRegistry;
 use crate::input::Input;
-use crate::time::Time;
+use crate::rendering::draw_list::{backgrounds, gpu_passes, DrawList, GpuPass};
+use crate::rendering::layers::LayerRegistry;
+use crate::rendering::rasterizer::Rasterizer;
+use crate::rendering::resolution::{ScreenTransform, VirtualResolution};
+use crate::rendering::static_geometry::{StaticBatch, StaticGeometry};
+use crate::scene::{Scene, SceneStack, Transition};
+use crate::time::{FramePacing, Time};
+use crate::types::color::Color;
+use crate::types::image::Image;
 use crate::types::vector2::Vector2;
 
-use std::time::Duration;
-use std::time::Instant;
-
-use glutin::dpi::PhysicalSize;
-use glutin::event::{Event, StartCause, WindowEvent};
+use glutin::dpi::{PhysicalPosition, PhysicalSize};
+use glutin::event::{DeviceEvent, Event, StartCause, WindowEvent};
 use glutin::event_loop::{ControlFlow, EventLoop};
 use glutin::window::{CursorIcon, UserAttentionType, Window};
 
 use rgx::core::*;
 use rgx::kit;
-use rgx::kit::shape2d::{Batch, Shape};
+use rgx::kit::shape2d::Shape;
 
 /// Types of attention to request user
 pub enum AttentionType {
@@ -74,6 +81,14 @@ const DEFAULT_CURSOR_CONFINEMENT: bool = false;
 const DEFAULT_CURSOR_VISIBILITY: bool = true;
 const DEFAULT_FOCUS: bool = true;
 
+/// Return swap chain present mode matching a frame pacing mode
+fn present_mode(frame_pacing: FramePacing) -> PresentMode {
+    return match frame_pacing {
+        FramePacing::VSync => PresentMode::Vsync,
+        FramePacing::Unlimited | FramePacing::TargetFrameRate => PresentMode::NoVsync,
+    };
+}
+
 /// Main game App, everything is wrapped in here
 pub struct App {
     width: u32,
@@ -89,14 +104,24 @@ pub struct App {
     is_mouse_visible: bool,
     mouse_icon: MouseIcon,
     is_focused: bool,
+    is_running: bool,
+    scale_factor: f32,
+    virtual_resolution: Option<VirtualResolution>,
 
     window: Option<Window>,
     pub control_flow: Option<*mut ControlFlow>,
-    pub shapes: Vec<Shape>,
+    pub draw_list: DrawList,
+    pub layers: LayerRegistry,
+    pub static_geometry: StaticGeometry,
+    pub objects: ObjectRegistry,
+    pub world: World,
+    pub scenes: SceneStack,
 
     pub input: Input,
     pub time: Time,
     pub game_view: GameView,
+    /// Additional game views drawn after game_view in order, e.g. for split screen
+    pub cameras: Vec<GameView>,
 }
 
 #[allow(deprecated)]
@@ -118,24 +143,32 @@ impl App {
             is_mouse_visible: DEFAULT_CURSOR_VISIBILITY,
             mouse_icon: MouseIcon::Default,
             is_focused: DEFAULT_FOCUS,
+            is_running: true,
+            scale_factor: 1.,
+            virtual_resolution: None,
 
             window: None,
             control_flow: None,
-            shapes: Vec::new(),
+            draw_list: DrawList::new(),
+            layers: LayerRegistry::new(),
+            static_geometry: StaticGeometry::new(),
+            objects: ObjectRegistry::new(),
+            world: World::new(),
+            scenes: SceneStack::new(),
 
             input: Input::new(),
             time: Time::new(),
             game_view: GameView::new(),
+            cameras: Vec::new(),
         };
     }
 
     /// Set App screen width
     pub fn set_width(&mut self, width: u32) {
         self.width = width;
-        self.window
-            .as_ref()
-            .unwrap()
-            .set_inner_size(PhysicalSize::new(self.width, self.height));
+        if let Some(window) = self.window.as_ref() {
+            window.set_inner_size(PhysicalSize::new(self.width, self.height));
+        }
     }
     /// Return current App screen width
     pub fn width(&self) -> u32 {
@@ -145,10 +178,9 @@ impl App {
     /// Set App screen height
     pub fn set_height(&mut self, height: u32) {
         self.height = height;
-        self.window
-            .as_ref()
-            .unwrap()
-            .set_inner_size(PhysicalSize::new(self.width, self.height));
+        if let Some(window) = self.window.as_ref() {
+            window.set_inner_size(PhysicalSize::new(self.width, self.height));
+        }
     }
     /// Return current App screen height
     pub fn height(&self) -> u32 {
@@ -159,10 +191,9 @@ impl App {
     pub fn set_size(&mut self, width: u32, height: u32) {
         self.width = width;
         self.height = height;
-        self.window
-            .as_ref()
-            .unwrap()
-            .set_inner_size(PhysicalSize::new(self.width, self.height));
+        if let Some(window) = self.window.as_ref() {
+            window.set_inner_size(PhysicalSize::new(self.width, self.height));
+        }
     }
     /// Return current App screen size
     pub fn size(&self) -> Vector2 {
@@ -172,10 +203,65 @@ impl App {
         };
     }
 
+    /// Return number of physical pixels per logical pixel of the screen App is on, 1 without a window
+    pub fn scale_factor(&self) -> f32 {
+        return self.scale_factor;
+    }
+
+    /// Lay the screen out in a fixed virtual resolution scaled to the window by a scaling policy
+    pub fn set_virtual_resolution(&mut self, virtual_resolution: VirtualResolution) {
+        self.virtual_resolution = Some(virtual_resolution);
+    }
+    /// Lay the screen out in window pixels again
+    pub fn clear_virtual_resolution(&mut self) {
+        self.virtual_resolution = None;
+    }
+    /// Return current virtual resolution, None if the screen is laid out in window pixels
+    pub fn virtual_resolution(&self) -> Option<VirtualResolution> {
+        return self.virtual_resolution;
+    }
+    /// Return how the screen is placed in the window
+    pub fn screen_transform(&self) -> ScreenTransform {
+        return match self.virtual_resolution.as_ref() {
+            Some(virtual_resolution) => virtual_resolution.screen_transform(self.size()),
+            None => ScreenTransform::identity(self.size()),
+        };
+    }
+    /// Return size of the screen objects are laid out in, the virtual resolution if set, otherwise App screen size
+    pub fn screen_size(&self) -> Vector2 {
+        return self.screen_transform().size;
+    }
+    /// Return mouse position on the screen objects are laid out in, in virtual pixels if a virtual resolution is set
+    pub fn mouse_screen_position(&mut self) -> Vector2 {
+        let mouse_position = self.input.mouse_position();
+        return self.screen_transform().to_virtual(mouse_position);
+    }
+
+    /// Return world position the mouse cursor is pointing at, through the last drawn game view under the cursor
+    pub fn mouse_world_position(&mut self) -> Vector2 {
+        let mouse_position = self.input.mouse_position();
+        return self
+            .game_views()
+            .into_iter()
+            .rev()
+            .find(|game_view| game_view.contains_screen_point(mouse_position))
+            .unwrap_or(&self.game_view)
+            .screen_to_world(mouse_position);
+    }
+
+    /// Return every game view in the order they are drawn, game_view first
+    pub fn game_views(&self) -> Vec<&GameView> {
+        let mut game_views = vec![&self.game_view];
+        game_views.extend(self.cameras.iter());
+        return game_views;
+    }
+
     /// Set App screen title
     pub fn set_title(&mut self, title: &str) {
         self.title = String::from(title);
-        self.window.as_ref().unwrap().set_title(&self.title);
+        if let Some(window) = self.window.as_ref() {
+            window.set_title(&self.title);
+        }
     }
     /// Return App screen title
     pub fn title(&self) -> &str {
@@ -185,10 +271,9 @@ impl App {
     /// Set whether App is resizable
     pub fn set_resizable(&mut self, is_resizable: bool) {
         self.is_resizable = is_resizable;
-        self.window
-            .as_ref()
-            .unwrap()
-            .set_resizable(self.is_resizable);
+        if let Some(window) = self.window.as_ref() {
+            window.set_resizable(self.is_resizable);
+        }
     }
     /// Return whether App is resizable
     pub fn is_resizable(&self) -> bool {
@@ -198,7 +283,9 @@ impl App {
     /// Set whether App is visible
     pub fn set_visible(&mut self, is_visible: bool) {
         self.is_visible = is_visible;
-        self.window.as_ref().unwrap().set_visible(self.is_visible);
+        if let Some(window) = self.window.as_ref() {
+            window.set_visible(self.is_visible);
+        }
     }
     /// Return whether App is visible
     pub fn is_visible(&self) -> bool {
@@ -208,10 +295,9 @@ impl App {
     /// Set whether App is minimized
     pub fn set_minimized(&mut self, is_minimized: bool) {
         self.is_minimized = is_minimized;
-        self.window
-            .as_ref()
-            .unwrap()
-            .set_minimized(self.is_minimized);
+        if let Some(window) = self.window.as_ref() {
+            window.set_minimized(self.is_minimized);
+        }
     }
     /// Return whether App is minimized
     pub fn is_minimized(&self) -> bool {
@@ -221,10 +307,9 @@ impl App {
     /// Set whether App is maximized
     pub fn set_maximized(&mut self, is_maximized: bool) {
         self.is_minimized = is_maximized;
-        self.window
-            .as_ref()
-            .unwrap()
-            .set_minimized(self.is_maximized);
+        if let Some(window) = self.window.as_ref() {
+            window.set_minimized(self.is_maximized);
+        }
     }
     /// Return whether App is maximized
     pub fn is_maximized(&self) -> bool {
@@ -234,10 +319,9 @@ impl App {
     /// Set whether App is decorated
     pub fn set_decorated(&mut self, is_decorated: bool) {
         self.is_decorated = is_decorated;
-        self.window
-            .as_ref()
-            .unwrap()
-            .set_decorations(self.is_decorated);
+        if let Some(window) = self.window.as_ref() {
+            window.set_decorations(self.is_decorated);
+        }
     }
     /// Return whether App is decorated
     pub fn is_decorated(&self) -> bool {
@@ -247,10 +331,9 @@ impl App {
     /// Set whether App is always on top
     pub fn set_always_on_top(&mut self, is_always_on_top: bool) {
         self.is_always_on_top = is_always_on_top;
-        self.window
-            .as_ref()
-            .unwrap()
-            .set_always_on_top(self.is_always_on_top);
+        if let Some(window) = self.window.as_ref() {
+            window.set_always_on_top(self.is_always_on_top);
+        }
     }
     /// Return whether App is always on top
     pub fn is_always_on_top(&self) -> bool {
@@ -260,15 +343,12 @@ impl App {
     /// Set whether mouse cursor is confined within window bound
     pub fn set_mouse_confined(&mut self, is_mouse_confined: bool) {
         self.is_mouse_confined = is_mouse_confined;
-        match self
-            .window
-            .as_ref()
-            .unwrap()
-            .set_cursor_grab(self.is_mouse_confined)
-        {
-            Ok(_) => {}
-            Err(err) => {
-                println!("Error when setting mouse cursor confinement: {}", err)
+        if let Some(window) = self.window.as_ref() {
+            match window.set_cursor_grab(self.is_mouse_confined) {
+                Ok(_) => {}
+                Err(err) => {
+                    println!("Error when setting mouse cursor confinement: {}", err)
+                }
             }
         }
     }
@@ -280,10 +360,9 @@ impl App {
     /// Set whether mouse cursor is visible
     pub fn set_mouse_visible(&mut self, is_mouse_visible: bool) {
         self.is_mouse_visible = is_mouse_visible;
-        self.window
-            .as_ref()
-            .unwrap()
-            .set_cursor_visible(self.is_mouse_visible);
+        if let Some(window) = self.window.as_ref() {
+            window.set_cursor_visible(self.is_mouse_visible);
+        }
     }
     /// Return whether mouse cursor is visible
     pub fn is_mouse_visible(&self) -> bool {
@@ -331,7 +410,9 @@ impl App {
         };
         self.mouse_icon = mouse_icon;
 
-        self.window.as_ref().unwrap().set_cursor_icon(icon);
+        if let Some(window) = self.window.as_ref() {
+            window.set_cursor_icon(icon);
+        }
     }
     /// Return current mouse icon
     pub fn mouse_icon(self) -> MouseIcon {
@@ -343,29 +424,250 @@ impl App {
         return self.is_focused;
     }
 
+    /// Set position of the input method editor candidate window, in pixels from the top left of the screen
+    pub fn set_ime_position(&mut self, position: Vector2) {
+        if let Some(window) = self.window.as_ref() {
+            window.set_ime_position(PhysicalPosition::new(position.x, position.y));
+        }
+    }
+
     /// Request for user attention
     pub fn request_attention(&mut self, attention_type: AttentionType) {
-        self.window
-            .as_ref()
-            .unwrap()
-            .request_user_attention(match attention_type {
+        if let Some(window) = self.window.as_ref() {
+            window.request_user_attention(match attention_type {
                 AttentionType::Critical => Some(UserAttentionType::Critical),
                 AttentionType::Informational => Some(UserAttentionType::Informational),
             });
+        }
     }
 
     /// Quit current App
     pub fn quit(&mut self) {
+        self.is_running = false;
         if let Some(control_flow) = self.control_flow {
             unsafe { *control_flow = ControlFlow::Exit }
         }
     }
+    /// Return whether App is running, i.e. has not been asked to quit
+    pub fn is_running(&self) -> bool {
+        return self.is_running;
+    }
+
+    /// Spawn a new entity in World
+    pub fn spawn_entity(&mut self) -> Entity {
+        return self.world.spawn(&mut self.objects);
+    }
+    /// Despawn an entity from World
+    pub fn despawn_entity(&mut self, entity: Entity) {
+        self.world.despawn(entity, &mut self.objects);
+    }
+
+    /// Update game logic for a single frame
+    fn update_frame<F, U>(&mut self, fixed_update: &mut F, update: &mut U)
+    where
+        F: FnMut(&mut App) -> (),
+        U: FnMut(&mut App) -> (),
+    {
+        // Objects submit their shapes again every frame
+        self.draw_list.clear();
+        let screen = self.screen_transform();
+        self.game_view.set_screen(screen, self.scale_factor);
+        for camera in self.cameras.iter_mut() {
+            camera.set_screen(screen, self.scale_factor);
+        }
+
+        // Read gamepad events since the previous frame and replay recorded input
+        self.input.begin_frame(self.time.frame_time());
+
+        // Apply scene changes requested during the previous frame
+        self.with_scenes(|scenes, app| scenes.apply_commands(app));
+
+        // User-defined fixed update, called as many times as fixed steps have accumulated
+        self.time.accumulate_fixed_time();
+        while self.time.consume_fixed_step() {
+            fixed_update(self);
+            self.with_scenes(|scenes, app| scenes.fixed_update(app));
+        }
+
+        // User-defined update
+        update(self);
+        self.with_scenes(|scenes, app| scenes.update(app));
+
+        // Run world systems and draw entities. World is moved out so systems can access the App
+        let mut world = std::mem::replace(&mut self.world, World::stand_in());
+        world.run_systems(self);
+        world.render(self);
+
+        // Keep entities spawned and despawned through the App while the World was moved out
+        let mut moved_out_world = std::mem::replace(&mut self.world, world);
+        self.world.append(&mut moved_out_world, &mut self.objects);
+
+        // Move game views towards their targets, then shake them if needed
+        let frame_time = self.time.frame_time();
+        for game_view in std::iter::once(&mut self.game_view).chain(self.cameras.iter_mut()) {
+            game_view.update(frame_time);
+            if game_view.is_shaking {
+                game_view.shake(frame_time);
+            }
+        }
+
+        // Inputs pressed and released this frame are cleared at the frame boundary
+        self.input.end_frame();
+
+        // Clean up objects destroyed this frame before rendering
+        let destroyed = self.objects.flush();
+        self.draw_list.remove_owned_by(&destroyed);
+        self.world.remove_entities(&destroyed);
+    }
+
+    /// Call a function with the scene stack moved out of the App, so scenes can access the App
+    fn with_scenes<F>(&mut self, function: F)
+    where
+        F: FnOnce(&mut SceneStack, &mut App),
+    {
+        let mut scenes = std::mem::replace(&mut self.scenes, SceneStack::new());
+        function(&mut scenes, self);
+
+        // Keep scene changes requested while the stack was moved out
+        scenes.append_commands(&mut self.scenes);
+        self.scenes = scenes;
+    }
+
+    /// Render current shapes on the CPU into an image the size of the App screen
+    pub fn render_to_image(&self) -> Image {
+        return Rasterizer::new(self.width, self.height).render_vertices(
+            &self.draw_list.vertices(
+                &self.game_views(),
+                self.screen_transform(),
+                &self.layers,
+                &self.static_geometry,
+            ),
+            self.clear_color(),
+        );
+    }
+
+    /// Return color the window is cleared to, bar color with a virtual resolution
+    fn clear_color(&self) -> Color {
+        return match self.virtual_resolution.as_ref() {
+            Some(virtual_resolution) => virtual_resolution.bar_color,
+            None => self.game_view.color,
+        };
+    }
+
+    /// Run App without a window or renderer for a fixed number of frames.
+    /// Time is stepped at the target frame rate instead of real time, which makes runs deterministic.
+    /// Return the shapes submitted for rendering at the end of every frame
+    pub fn run_headless<I, U>(self, init: I, update: U, frames: usize) -> Vec<Vec<Shape>>
+    where
+        I: FnMut(&mut App) -> (),
+        U: FnMut(&mut App) -> (),
+    {
+        return self.run_headless_fixed(init, |_app: &mut App| {}, update, frames);
+    }
+
+    /// Run App headless like run_headless, with a fixed update called at fixed frame time
+    pub fn run_headless_fixed<I, F, U>(
+        self,
+        init: I,
+        fixed_update: F,
+        update: U,
+        frames: usize,
+    ) -> Vec<Vec<Shape>>
+    where
+        I: FnMut(&mut App) -> (),
+        F: FnMut(&mut App) -> (),
+        U: FnMut(&mut App) -> (),
+    {
+        let mut frame_shapes: Vec<Vec<Shape>> = Vec::with_capacity(frames);
+        self.run_headless_frames(init, fixed_update, update, frames, |app: &App| {
+            frame_shapes.push(app.draw_list.shapes().cloned().collect());
+        });
+        return frame_shapes;
+    }
+
+    /// Run App headless like run_headless and render its last frame on the CPU once the frame is complete.
+    /// Return None if the App quit before the last frame
+    pub fn render_headless<I, U>(self, init: I, update: U, frames: usize) -> Option<Image>
+    where
+        I: FnMut(&mut App) -> (),
+        U: FnMut(&mut App) -> (),
+    {
+        let mut image: Option<Image> = None;
+        let mut frame: usize = 0;
+        self.run_headless_frames(
+            init,
+            |_app: &mut App| {},
+            update,
+            frames,
+            |app: &App| {
+                frame += 1;
+                if frame == frames {
+                    image = Some(app.render_to_image());
+                }
+            },
+        );
+        return image;
+    }
+
+    /// Run App headless for a number of frames, calling frame_end after every frame is fully updated
+    fn run_headless_frames<I, F, U, E>(
+        mut self,
+        mut init: I,
+        mut fixed_update: F,
+        mut update: U,
+        frames: usize,
+        mut frame_end: E,
+    ) where
+        I: FnMut(&mut App) -> (),
+        F: FnMut(&mut App) -> (),
+        U: FnMut(&mut App) -> (),
+        E: FnMut(&App) -> (),
+    {
+        // User-defined init
+        init(&mut self);
+
+        // Main program loop
+        for frame in 0..frames {
+            if !self.is_running {
+                break;
+            }
+
+            // Update frame time, replayed frames run with the frame time they were recorded with
+            if let Some(frame_time) = self.input.playback_frame_time() {
+                self.time.step(frame_time);
+            } else if frame > 0 {
+                let frame_time = self.time.fixed_frame_time();
+                self.time.step(frame_time);
+            }
+
+            self.update_frame(&mut fixed_update, &mut update);
+            frame_end(&self);
+        }
+    }
 
     /// Run App
-    pub fn run<I, U>(mut self, mut init: I, mut update: U)
+    pub fn run<I, U>(self, init: I, update: U)
     where
         I: FnMut(&mut App) -> () + 'static,
         U: FnMut(&mut App) -> () + 'static,
+    {
+        self.run_fixed(init, |_app: &mut App| {}, update);
+    }
+
+    /// Run App starting with a scene, the scene stack drives the game instead of init and update
+    pub fn run_scene<S: Scene + 'static>(mut self, scene: S) {
+        self.scenes.push(scene, Transition::None);
+        self.run(|_app: &mut App| {}, |_app: &mut App| {});
+    }
+
+    /// Run App with a fixed update that is called zero or more times per frame at fixed frame time.
+    /// Use fixed update for physics and gameplay that should not depend on frame rate,
+    /// and Time::interpolation_alpha() to render between fixed steps in update
+    pub fn run_fixed<I, F, U>(mut self, mut init: I, mut fixed_update: F, mut update: U)
+    where
+        I: FnMut(&mut App) -> () + 'static,
+        F: FnMut(&mut App) -> () + 'static,
+        U: FnMut(&mut App) -> () + 'static,
     {
         // Create event loop for window context
         let event_loop = EventLoop::new();
@@ -397,12 +699,28 @@ impl App {
 
         // Setup render pipeline
         let shape2d_pipeline: kit::shape2d::Pipeline = renderer.pipeline(Blending::default());
+        let mut textures_present_mode = present_mode(self.time.frame_pacing);
         let mut textures = renderer.swap_chain(
             self.window.as_ref().unwrap().inner_size().width as u32,
             self.window.as_ref().unwrap().inner_size().height as u32,
-            PresentMode::default(),
+            textures_present_mode,
         );
 
+        self.scale_factor = self.window.as_ref().unwrap().scale_factor() as f32;
+
+        // Static geometry is uploaded to GPU buffers again only when shapes are added or removed
+        let mut static_batches: Vec<StaticBatch> = Vec::new();
+        let mut static_buffers: Vec<VertexBuffer> = Vec::new();
+        let mut static_version: Option<u64> = None;
+
+        // Read hardware gamepads unless another gamepad backend has been set
+        #[cfg(feature = "gilrs")]
+        if !self.input.has_gamepad_backend() {
+            if let Some(backend) = crate::input::gilrs_backend::GilrsGamepadBackend::new() {
+                self.input.set_gamepad_backend(backend);
+            }
+        }
+
         // User-defined init
         init(&mut self);
 
@@ -411,31 +729,6 @@ impl App {
             *control_flow = ControlFlow::Poll;
             self.control_flow = Some(control_flow);
 
-            // User-defined update
-            update(&mut self);
-
-            // Shake current game view if needed
-            if self.game_view.is_shaking {
-                self.game_view.shake(self.time.frame_time());
-            }
-
-            // Wait until next frame time to update the screen again
-            // let start_time = Instant::now();
-            // match *control_flow {
-            //     ControlFlow::Exit => (),
-            //     _ => {
-            //         let elapsed_time = Instant::now().duration_since(start_time).as_millis() as u32;
-            //         let wait_millis = match 1000 / self.time.target_frame_rate >= elapsed_time {
-            //             true => 1000 / self.time.target_frame_rate - elapsed_time,
-            //             false => 0,
-            //         };
-            //         let next_time = start_time + Duration::from_millis(wait_millis as u64);
-            //         if let Some(control_flow) = self.control_flow {
-            //             unsafe { *control_flow = ControlFlow::WaitUntil(next_time) }
-            //         }
-            //     }
-            // }
-
             // Poll for events in main loop
             match event {
                 Event::NewEvents(StartCause::Init) => {
@@ -447,18 +740,33 @@ impl App {
                 }
                 Event::WindowEvent { event, .. } => match event {
                     WindowEvent::Resized(size) => {
+                        // Keep App screen size in sync so game views and virtual resolution fit the window
+                        self.width = size.width;
+                        self.height = size.height;
                         textures = renderer.swap_chain(
                             size.width as u32,
                             size.height as u32,
-                            PresentMode::default(),
+                            textures_present_mode,
                         );
                     }
+                    WindowEvent::ScaleFactorChanged {
+                        scale_factor,
+                        new_inner_size: _,
+                    } => self.scale_factor = scale_factor as f32,
                     // Handle keyboard input
                     WindowEvent::KeyboardInput {
                         device_id: _,
                         input,
                         is_synthetic: _,
                     } => self.input.update_keyboard_input(input),
+                    // Handle text input
+                    WindowEvent::ReceivedCharacter(character) => {
+                        self.input.update_text_input(character)
+                    }
+                    // Handle modifier keys input
+                    WindowEvent::ModifiersChanged(modifiers) => {
+                        self.input.update_modifiers_input(modifiers)
+                    }
                     // Handle mouse button input
                     WindowEvent::MouseInput {
                         device_id: _,
@@ -472,6 +780,13 @@ impl App {
                         position,
                         modifiers: _,
                     } => self.input.update_mouse_position_input(position),
+                    // Handle mouse wheel input
+                    WindowEvent::MouseWheel {
+                        device_id: _,
+                        delta,
+                        phase: _,
+                        modifiers: _,
+                    } => self.input.update_mouse_wheel_input(delta),
                     // Handle mouse enter/exit input
                     WindowEvent::CursorEntered { device_id: _ } => {
                         self.input.update_mouse_entered_input(true)
@@ -483,56 +798,87 @@ impl App {
                     WindowEvent::CloseRequested => self.quit(),
                     _ => {}
                 },
+                // Handle raw mouse motion while focused, reported even when cursor is confined
+                Event::DeviceEvent {
+                    device_id: _,
+                    event: DeviceEvent::MouseMotion { delta },
+                } if self.is_focused => self.input.update_mouse_motion_input(delta),
                 Event::MainEventsCleared => {
-                    self.window.as_ref().unwrap().request_redraw();
+                    // Update game logic once per frame after all pending events are handled
+                    self.update_frame(&mut fixed_update, &mut update);
 
-                    match *control_flow {
-                        ControlFlow::Exit => (),
-                        _ => {
-                            let next_time = Instant::now()
-                                + Duration::from_secs_f32(1. / self.time.target_frame_rate as f32);
-                            *control_flow = ControlFlow::WaitUntil(next_time);
-                        }
-                    }
+                    self.window.as_ref().unwrap().request_redraw();
                 }
                 Event::RedrawEventsCleared => {
-                    // Update frame time
+                    // Wait for next frame and update frame time
                     self.time.update();
                 }
                 Event::RedrawRequested(_) => {
-                    let mut batch = Batch::new();
+                    // Recreate swap chain if frame pacing switched to or from vertical sync
+                    if present_mode(self.time.frame_pacing) != textures_present_mode {
+                        textures_present_mode = present_mode(self.time.frame_pacing);
+                        let size = self.window.as_ref().unwrap().inner_size();
+                        textures =
+                            renderer.swap_chain(size.width, size.height, textures_present_mode);
+                    }
 
-                    for shape in self.shapes.clone() {
-                        batch.add(shape);
+                    if static_version != Some(self.static_geometry.version()) {
+                        static_version = Some(self.static_geometry.version());
+                        static_batches = self.static_geometry.batches();
+                        static_buffers = static_batches
+                            .iter()
+                            .map(|batch| renderer.vertex_buffer(&batch.vertices))
+                            .collect();
                     }
 
-                    let buffer = batch.finish(&renderer);
+                    // Shapes are triangulated with game view zoom and rotation applied on the CPU,
+                    // except static geometry already in GPU buffers
+                    let game_views = self.game_views();
+                    let background_buffer = renderer.vertex_buffer(&backgrounds(&game_views));
+                    let buffer = renderer.vertex_buffer(&self.draw_list.vertices_for_gpu(
+                        &game_views,
+                        self.screen_transform(),
+                        &self.layers,
+                        &self.static_geometry,
+                        self.size(),
+                    ));
 
                     let mut frame = renderer.frame();
-                    // frame.encoder
 
                     let output = textures.next();
+                    let ortho = kit::ortho(output.width, output.height, Default::default());
+                    let clear_color = self.clear_color();
+                    let clear =
+                        Rgba::new(clear_color.r, clear_color.g, clear_color.b, clear_color.a);
 
-                    // Update render pipeline
-                    renderer.update_pipeline(
-                        &shape2d_pipeline,
-                        kit::ortho(output.width, output.height, Default::default()),
-                        &mut frame,
-                    );
+                    // Draw frame, static geometry in view has game view zoom and rotation applied on the GPU
+                    let passes =
+                        gpu_passes(&game_views, &self.layers, &static_batches, self.size());
+                    for (pass_index, gpu_pass) in passes.into_iter().enumerate() {
+                        let transform = match &gpu_pass {
+                            GpuPass::Static { transform, .. } => ortho * *transform,
+                            _ => ortho,
+                        };
+                        renderer.update_pipeline(&shape2d_pipeline, transform, &mut frame);
 
-                    // Draw frame
-                    {
                         let pass = &mut frame.pass(
-                            PassOp::Clear(Rgba::new(
-                                self.game_view.color.r,
-                                self.game_view.color.g,
-                                self.game_view.color.b,
-                                self.game_view.color.a,
-                            )),
+                            if pass_index == 0 {
+                                PassOp::Clear(clear)
+                            } else {
+                                PassOp::Load()
+                            },
                             &output,
                         );
                         pass.set_pipeline(&shape2d_pipeline);
-                        pass.draw_buffer(&buffer);
+                        match gpu_pass {
+                            GpuPass::Backgrounds => pass.draw_buffer(&background_buffer),
+                            GpuPass::Static { batch, ranges, .. } => {
+                                for range in ranges {
+                                    pass.draw_buffer_range(&static_buffers[batch], range);
+                                }
+                            }
+                            GpuPass::Shapes => pass.draw_buffer(&buffer),
+                        }
                     }
                     renderer.present(frame);
                 }
@@ -541,3 +887,184 @@ impl App {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::circle::Circle;
+    use crate::entities::rectangle::Rectangle;
+    use crate::test::diff_images;
+
+    /// Render current shapes on the CPU in the order of the passes drawn on the GPU
+    fn render_gpu_passes(app: &App) -> Image {
+        let game_views = app.game_views();
+        let batches = app.static_geometry.batches();
+        let mut vertices = Vec::new();
+        for gpu_pass in gpu_passes(&game_views, &app.layers, &batches, app.size()) {
+            match gpu_pass {
+                GpuPass::Backgrounds => vertices.append(&mut backgrounds(&game_views)),
+                GpuPass::Static {
+                    batch,
+                    transform,
+                    ranges,
+                } => {
+                    for range in ranges {
+                        for vertex in
+                            batches[batch].vertices[range.start as usize..range.end as usize].iter()
+                        {
+                            let mut vertex = *vertex;
+                            vertex.position = transform * vertex.position;
+                            vertices.push(vertex);
+                        }
+                    }
+                }
+                GpuPass::Shapes => vertices.append(&mut app.draw_list.vertices_for_gpu(
+                    &game_views,
+                    app.screen_transform(),
+                    &app.layers,
+                    &app.static_geometry,
+                    app.size(),
+                )),
+            }
+        }
+        return Rasterizer::new(app.width, app.height)
+            .render_vertices(&vertices, app.clear_color());
+    }
+
+    /// Update a frame drawing a circle and compare the GPU passes against the CPU render
+    fn assert_gpu_passes_match(app: &mut App) {
+        let mut circle = Circle::new();
+        circle.game_object.transform.radius = 10.;
+        circle.fill_color = Color::green();
+        app.update_frame(&mut |_app: &mut App| {}, &mut |app: &mut App| {
+            circle.draw(app)
+        });
+
+        let expected = app.render_to_image();
+        let (mismatched_pixels, _) = diff_images(&expected, &render_gpu_passes(app), 0);
+        assert_eq!(mismatched_pixels, 0);
+    }
+
+    /// Return center of the first rectangle in shapes
+    fn rectangle_center(shapes: &[Shape]) -> Option<Vector2> {
+        return shapes.iter().find_map(|shape| match shape {
+            Shape::Rectangle(rect, ..) => Some(Vector2 {
+                x: (rect.x1 + rect.x2) / 2.,
+                y: (rect.y1 + rect.y2) / 2.,
+            }),
+            _ => None,
+        });
+    }
+
+    #[test]
+    fn run_headless_collects_shapes_of_every_frame() {
+        let mut rectangle = Rectangle::new();
+        rectangle.game_object.transform.size = Vector2 { x: 10., y: 10. };
+        let mut times = Vec::new();
+        let mut shakes = Vec::new();
+        let frame_shapes = App::new().run_headless(
+            |app: &mut App| app.game_view.start_shaking(2., 0.04, 1.),
+            |app: &mut App| {
+                // Moves 1 unit every frame at 60 frames per second
+                rectangle.game_object.transform.position.x += 60. * app.time.frame_time();
+                rectangle.draw(app);
+                times.push(app.time.time());
+                let offset = app.game_view.center() - app.game_view.game_object.transform.position;
+                shakes.push((app.game_view.is_shaking, offset));
+            },
+            5,
+        );
+
+        assert_eq!(frame_shapes.len(), 5);
+        for (frame, shapes) in frame_shapes.iter().enumerate() {
+            assert_eq!(shapes.len(), 1);
+            let center = rectangle_center(shapes).unwrap();
+            assert!((center.x - frame as f32).abs() < 1e-4 && center.y == 0.);
+        }
+        for (frame, time) in times.iter().enumerate() {
+            assert!((time - frame as f32 / 60.).abs() < 1e-6);
+        }
+
+        // Shake offsets the game view until its duration runs out with time
+        let is_shaking: Vec<bool> = shakes.iter().map(|shake| shake.0).collect();
+        assert_eq!(is_shaking, vec![true, true, true, true, false]);
+        for (_, offset) in shakes[1..4].iter() {
+            assert!(offset.x.abs() <= 2. && offset.y.abs() <= 2.);
+        }
+        assert!(shakes[4].1 == Vector2::zero());
+    }
+
+    #[test]
+    fn run_headless_does_not_step_first_frame() {
+        let mut frame_times = Vec::new();
+        let mut fixed_updates = 0;
+        App::new().run_headless_fixed(
+            |_app: &mut App| {},
+            |_app: &mut App| fixed_updates += 1,
+            |app: &mut App| frame_times.push((app.time.frame_time(), app.time.time())),
+            4,
+        );
+        assert_eq!(frame_times[0], (0., 0.));
+        assert_eq!(frame_times[1].0, 1. / 60.);
+        assert_eq!(frame_times[3].0, 1. / 60.);
+        // No fixed step accumulates on the first frame, one on each following frame
+        assert_eq!(fixed_updates, 3);
+    }
+
+    #[test]
+    fn run_headless_stops_when_app_quits() {
+        let mut frame = 0;
+        let frame_shapes = App::new().run_headless(
+            |_app: &mut App| {},
+            |app: &mut App| {
+                if frame == 2 {
+                    app.quit();
+                }
+                frame += 1;
+            },
+            10,
+        );
+        assert_eq!(frame_shapes.len(), 3);
+    }
+
+    #[test]
+    fn gpu_passes_draw_static_geometry_in_front_of_background() {
+        let mut app = App::new();
+        app.set_size(64, 48);
+        let mut rectangle = Rectangle::new();
+        rectangle.game_object.transform.size = Vector2 { x: 40., y: 30. };
+        rectangle.fill_color = Color::red();
+        rectangle.add_static(&mut app);
+
+        assert_gpu_passes_match(&mut app);
+        // Static geometry covers the background around the circle
+        let image = render_gpu_passes(&app);
+        let pixel = image.pixel(14, 24).map(|pixel| (pixel.r, pixel.g, pixel.b));
+        assert_eq!(pixel, Some((255, 0, 0)));
+    }
+
+    #[test]
+    fn gpu_passes_match_cpu_with_layers_and_zoom() {
+        let mut app = App::new();
+        app.set_size(64, 48);
+        let background = app.layers.add("Background", -1);
+        if let Some(settings) = app.layers.settings_mut(background) {
+            settings.parallax = Vector2 { x: 0.5, y: 0.5 };
+        }
+        app.game_view.zoom = 1.5;
+        app.game_view.set_center(Vector2 { x: 5., y: -3. });
+
+        let mut far = Rectangle::new();
+        far.game_object.transform.size = Vector2 { x: 60., y: 20. };
+        far.fill_color = Color::blue();
+        far.layer = background;
+        far.add_static(&mut app);
+        let mut near = Rectangle::new();
+        near.game_object.transform.position = Vector2 { x: 8., y: 4. };
+        near.game_object.transform.size = Vector2 { x: 16., y: 16. };
+        near.fill_color = Color::red();
+        near.add_static(&mut app);
+
+        assert_gpu_passes_match(&mut app);
+    }
+}
@@ -0,0 +1,34 @@
+use crate::components::transform::Transform;
+use crate::ecs::world::Entity;
+
+/// A component that points to the parent of an entity.
+/// The Transform of an entity with a parent is relative to its parent
+pub struct Parent(pub Entity);
+
+impl Copy for Parent {}
+
+impl Clone for Parent {
+    fn clone(&self) -> Parent {
+        *self
+    }
+}
+
+/// A component that lists the children of an entity
+pub struct Children(pub Vec<Entity>);
+
+impl Clone for Children {
+    fn clone(&self) -> Children {
+        return Children(self.0.clone());
+    }
+}
+
+/// A component that holds the world-space transform of an entity, updated every frame from the hierarchy
+pub struct GlobalTransform(pub Transform);
+
+impl Copy for GlobalTransform {}
+
+impl Clone for GlobalTransform {
+    fn clone(&self) -> GlobalTransform {
+        *self
+    }
+}
@@ -1 +1,3 @@
+pub mod hierarchy;
+pub mod renderable;
 pub mod transform;
@@ -0,0 +1,48 @@
+use crate::app::App;
+use crate::entities::circle::Circle;
+use crate::entities::game_object::GameObject;
+use crate::entities::line::Line;
+use crate::entities::rectangle::Rectangle;
+
+/// A component that makes an entity drawn every frame as one of the basic shapes
+pub enum Renderable {
+    Rectangle(Rectangle),
+    Circle(Circle),
+    Line(Line),
+}
+
+impl Copy for Renderable {}
+
+impl Clone for Renderable {
+    fn clone(&self) -> Renderable {
+        *self
+    }
+}
+
+impl Renderable {
+    /// Return game object of shape
+    pub fn game_object(&self) -> &GameObject {
+        return match self {
+            Renderable::Rectangle(rectangle) => &rectangle.game_object,
+            Renderable::Circle(circle) => &circle.game_object,
+            Renderable::Line(line) => &line.game_object,
+        };
+    }
+    /// Return mutable game object of shape
+    pub fn game_object_mut(&mut self) -> &mut GameObject {
+        return match self {
+            Renderable::Rectangle(rectangle) => &mut rectangle.game_object,
+            Renderable::Circle(circle) => &mut circle.game_object,
+            Renderable::Line(line) => &mut line.game_object,
+        };
+    }
+
+    /// Render shape on screen
+    pub fn draw(&mut self, app: &mut App) {
+        match self {
+            Renderable::Rectangle(rectangle) => rectangle.draw(app),
+            Renderable::Circle(circle) => circle.draw(app),
+            Renderable::Line(line) => line.draw(app),
+        }
+    }
+}
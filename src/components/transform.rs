@@ -5,6 +5,7 @@ use std::fmt;
 pub struct Transform {
     pub position: Vector2,
     pub rotation: f32,
+    pub scale: Vector2,
     pub size: Vector2,
     pub radius: f32,
 }
@@ -33,8 +34,91 @@ impl Transform {
         return Self {
             position: Vector2::zero(),
             rotation: 0.0,
+            scale: Vector2::identity(),
             size: Vector2 { x: 50.0, y: 50.0 },
             radius: 25.0,
         };
     }
+
+    /// Return size of object after scaling
+    pub fn scaled_size(&self) -> Vector2 {
+        return self.size * self.scale;
+    }
+    /// Return radius of object after scaling by the largest scale axis
+    pub fn scaled_radius(&self) -> f32 {
+        return self.radius * self.scale.x.abs().max(self.scale.y.abs());
+    }
+
+    /// Return smallest and largest positions covered by an object of this transform's scaled size or radius,
+    /// whichever is larger, including rotation
+    pub fn bounds(&self) -> (Vector2, Vector2) {
+        let size = self.scaled_size();
+        let (sin, cos) = self.rotation.sin_cos();
+        let radius = self.scaled_radius();
+        let half_size = Vector2 {
+            x: ((size.x * cos).abs() + (size.y * sin).abs()) / 2.,
+            y: ((size.x * sin).abs() + (size.y * cos).abs()) / 2.,
+        };
+        let half_size = Vector2 {
+            x: half_size.x.max(radius),
+            y: half_size.y.max(radius),
+        };
+        return (self.position - half_size, self.position + half_size);
+    }
+
+    /// Transform a point from the local space of this transform to the space this transform is in.
+    /// Points are rotated the same way shapes are rotated when rendered
+    pub fn transform_point(&self, point: Vector2) -> Vector2 {
+        return self.position + rotate(point * self.scale, self.rotation);
+    }
+    /// Transform a point from the space this transform is in to the local space of this transform
+    pub fn inverse_transform_point(&self, point: Vector2) -> Vector2 {
+        return rotate(point - self.position, -self.rotation) / non_zero(self.scale);
+    }
+
+    /// Return this transform, relative to a parent, combined with the parent transform
+    pub fn combined_with_parent(&self, parent: &Transform) -> Transform {
+        return Transform {
+            position: parent.transform_point(self.position),
+            rotation: parent.rotation + self.rotation,
+            scale: parent.scale * self.scale,
+            size: self.size,
+            radius: self.radius,
+        };
+    }
+    /// Return this transform relative to a parent transform, is the inverse of combined_with_parent
+    pub fn relative_to_parent(&self, parent: &Transform) -> Transform {
+        return Transform {
+            position: parent.inverse_transform_point(self.position),
+            rotation: self.rotation - parent.rotation,
+            scale: self.scale / non_zero(parent.scale),
+            size: self.size,
+            radius: self.radius,
+        };
+    }
+}
+
+/// Rotate a vector by an angle, in the same direction shapes are rotated when rendered
+fn rotate(vector: Vector2, angle: f32) -> Vector2 {
+    let (sin, cos) = angle.sin_cos();
+    return Vector2 {
+        x: vector.x * cos + vector.y * sin,
+        y: -vector.x * sin + vector.y * cos,
+    };
+}
+
+/// Replace zero scale axes with a tiny value so scale can be divided by
+fn non_zero(scale: Vector2) -> Vector2 {
+    return Vector2 {
+        x: if scale.x == 0.0 {
+            f32::EPSILON
+        } else {
+            scale.x
+        },
+        y: if scale.y == 0.0 {
+            f32::EPSILON
+        } else {
+            scale.y
+        },
+    };
 }
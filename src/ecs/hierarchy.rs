@@ -0,0 +1,316 @@
+use crate::components::hierarchy::{Children, GlobalTransform, Parent};
+use crate::components::transform::Transform;
+use crate::ecs::query::Query;
+use crate::ecs::world::{Entity, World};
+
+/// Parent/child relationships between entities.
+/// Children move, rotate and scale with their parent, their Transform is relative to their parent
+impl World {
+    /// Set parent of an entity, or detach it from its parent with None.
+    /// World position, rotation and scale of the entity are preserved.
+    /// Return false if parent does not exist or is the entity itself or one of its descendants
+    pub fn set_parent(&mut self, child: Entity, parent: Option<Entity>) -> bool {
+        if !self.contains(child) {
+            return false;
+        }
+        if let Some(parent) = parent {
+            if !self.contains(parent) || parent == child || self.is_ancestor(child, parent) {
+                return false;
+            }
+        }
+
+        // Preserve world transform across re-parenting
+        let world_transform = self.world_transform(child);
+
+        // Detach from current parent
+        if let Some(Parent(old_parent)) = self.remove::<Parent>(child) {
+            if let Some(children) = self.get_mut::<Children>(old_parent) {
+                children.0.retain(|entity| *entity != child);
+            }
+        }
+
+        // Attach to new parent
+        if let Some(parent) = parent {
+            self.insert(child, Parent(parent));
+            match self.get_mut::<Children>(parent) {
+                Some(children) => children.0.push(child),
+                None => self.insert(parent, Children(vec![child])),
+            }
+        }
+
+        if let Some(world_transform) = world_transform {
+            let local_transform = match parent.and_then(|parent| self.world_transform(parent)) {
+                Some(parent_transform) => world_transform.relative_to_parent(&parent_transform),
+                None => world_transform,
+            };
+            self.insert(child, local_transform);
+        }
+        return true;
+    }
+
+    /// Return parent of an entity
+    pub fn parent(&self, entity: Entity) -> Option<Entity> {
+        return self.get::<Parent>(entity).map(|parent| parent.0);
+    }
+    /// Return children of an entity
+    pub fn children(&self, entity: Entity) -> Vec<Entity> {
+        return match self.get::<Children>(entity) {
+            Some(children) => children.0.clone(),
+            None => Vec::new(),
+        };
+    }
+    /// Return every descendant of an entity, parents before their children
+    pub fn descendants(&self, entity: Entity) -> Vec<Entity> {
+        let mut descendants = Vec::new();
+        let mut stack = self.children(entity);
+        stack.reverse();
+        while let Some(descendant) = stack.pop() {
+            descendants.push(descendant);
+            let mut children = self.children(descendant);
+            children.reverse();
+            stack.append(&mut children);
+        }
+        return descendants;
+    }
+    /// Return whether an entity is an ancestor of another
+    pub fn is_ancestor(&self, ancestor: Entity, entity: Entity) -> bool {
+        let mut current = self.parent(entity);
+        while let Some(parent) = current {
+            if parent == ancestor {
+                return true;
+            }
+            current = self.parent(parent);
+        }
+        return false;
+    }
+
+    /// Return world-space transform of an entity by combining transforms up the hierarchy
+    pub fn world_transform(&self, entity: Entity) -> Option<Transform> {
+        let transform = *self.get::<Transform>(entity)?;
+        return match self.parent(entity) {
+            Some(parent) => match self.world_transform(parent) {
+                Some(parent_transform) => Some(transform.combined_with_parent(&parent_transform)),
+                None => Some(transform),
+            },
+            None => Some(transform),
+        };
+    }
+
+    /// Propagate transforms down the hierarchy and store them as GlobalTransform components.
+    /// Only meant to be called internally within sidekick
+    pub fn propagate_transforms(&mut self) {
+        let roots = Query::new()
+            .with::<Transform>()
+            .without::<Parent>()
+            .entities(self);
+
+        for root in roots {
+            let transform = *self.get::<Transform>(root).unwrap();
+            self.insert(root, GlobalTransform(transform));
+
+            for descendant in self.descendants(root) {
+                let parent_transform = self
+                    .parent(descendant)
+                    .and_then(|parent| self.get::<GlobalTransform>(parent))
+                    .map(|global_transform| global_transform.0);
+                // Drop stale global transforms so children of entities without Transform use their own
+                let transform = match self.get::<Transform>(descendant) {
+                    Some(transform) => *transform,
+                    None => {
+                        self.remove::<GlobalTransform>(descendant);
+                        continue;
+                    }
+                };
+
+                let global_transform = match parent_transform {
+                    Some(parent_transform) => transform.combined_with_parent(&parent_transform),
+                    None => transform,
+                };
+                self.insert(descendant, GlobalTransform(global_transform));
+            }
+        }
+    }
+
+    /// Remove entities from the children list of their parents, and detach their remaining children
+    pub(crate) fn detach_from_parents(&mut self, entities: &[Entity]) {
+        for entity in entities.iter() {
+            if let Some(parent) = self.parent(*entity) {
+                if let Some(children) = self.get_mut::<Children>(parent) {
+                    children.0.retain(|child| child != entity);
+                }
+            }
+            for child in self.children(*entity) {
+                if !entities.contains(&child) {
+                    self.set_parent(child, None);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::hierarchy::{Children, GlobalTransform};
+    use crate::components::transform::Transform;
+    use crate::ecs::world::{Entity, World};
+    use crate::entities::registry::ObjectRegistry;
+    use crate::types::vector2::Vector2;
+
+    /// Spawn an entity with a transform
+    fn spawn_at(
+        world: &mut World,
+        objects: &mut ObjectRegistry,
+        x: f32,
+        y: f32,
+        rotation: f32,
+        scale: f32,
+    ) -> Entity {
+        let entity = world.spawn(objects);
+        let mut transform = Transform::new();
+        transform.position = Vector2 { x, y };
+        transform.rotation = rotation;
+        transform.scale = Vector2 { x: scale, y: scale };
+        world.insert(entity, transform);
+        return entity;
+    }
+
+    /// Assert that two transforms have the same position, rotation and scale
+    fn assert_same_transform(a: &Transform, b: &Transform) {
+        assert!((a.position.x - b.position.x).abs() < 1e-4, "{} != {}", a, b);
+        assert!((a.position.y - b.position.y).abs() < 1e-4, "{} != {}", a, b);
+        assert!((a.rotation - b.rotation).abs() < 1e-5, "{} != {}", a, b);
+        assert!((a.scale.x - b.scale.x).abs() < 1e-5, "{} != {}", a, b);
+        assert!((a.scale.y - b.scale.y).abs() < 1e-5, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn set_parent_keeps_world_transform() {
+        let mut objects = ObjectRegistry::new();
+        let mut world = World::new();
+        let first = spawn_at(&mut world, &mut objects, 10., 0., 1.5, 2.);
+        let second = spawn_at(&mut world, &mut objects, -3., 4., 0.3, 0.5);
+        let child = spawn_at(&mut world, &mut objects, 10., 5., 0.2, 1.);
+        let world_transform = world.world_transform(child).unwrap();
+
+        assert!(world.set_parent(child, Some(first)));
+        assert!(world.parent(child) == Some(first));
+        assert!(world.children(first) == vec![child]);
+        assert_same_transform(&world.world_transform(child).unwrap(), &world_transform);
+        assert!((world.get::<Transform>(child).unwrap().scale.x - 0.5).abs() < 1e-5);
+
+        // Reparenting moves child from one children list to the other
+        assert!(world.set_parent(child, Some(second)));
+        assert!(world.children(first).is_empty());
+        assert!(world.children(second) == vec![child]);
+        assert_same_transform(&world.world_transform(child).unwrap(), &world_transform);
+
+        // Moving parent moves child
+        world.get_mut::<Transform>(second).unwrap().position.x += 1.;
+        let moved = world.world_transform(child).unwrap();
+        assert!((moved.position.x - world_transform.position.x - 1.).abs() < 1e-4);
+        world.get_mut::<Transform>(second).unwrap().position.x -= 1.;
+
+        assert!(world.set_parent(child, None));
+        assert!(world.parent(child).is_none());
+        assert!(world.children(second).is_empty());
+        assert_same_transform(world.get::<Transform>(child).unwrap(), &world_transform);
+    }
+
+    #[test]
+    fn cycles_are_rejected() {
+        let mut objects = ObjectRegistry::new();
+        let mut world = World::new();
+        let root = spawn_at(&mut world, &mut objects, 0., 0., 0., 1.);
+        let middle = spawn_at(&mut world, &mut objects, 0., 0., 0., 1.);
+        let leaf = spawn_at(&mut world, &mut objects, 0., 0., 0., 1.);
+        assert!(world.set_parent(middle, Some(root)));
+        assert!(world.set_parent(leaf, Some(middle)));
+
+        assert!(world.is_ancestor(root, leaf));
+        assert!(world.is_ancestor(middle, leaf));
+        assert!(!world.is_ancestor(leaf, root));
+        assert!(!world.is_ancestor(leaf, leaf));
+        assert!(world.descendants(root) == vec![middle, leaf]);
+
+        assert!(!world.set_parent(root, Some(leaf)));
+        assert!(!world.set_parent(root, Some(middle)));
+        assert!(!world.set_parent(root, Some(root)));
+        assert!(world.parent(root).is_none());
+        assert!(world.children(leaf).is_empty());
+
+        // Entities outside of World cannot be parents or children
+        let outside = objects.spawn();
+        assert!(!world.set_parent(leaf, Some(outside)));
+        assert!(!world.set_parent(outside, Some(leaf)));
+        assert!(world.parent(leaf) == Some(middle));
+    }
+
+    #[test]
+    fn despawn_cascades_to_descendants() {
+        let mut objects = ObjectRegistry::new();
+        let mut world = World::new();
+        let root = spawn_at(&mut world, &mut objects, 0., 0., 0., 1.);
+        let middle = spawn_at(&mut world, &mut objects, 0., 0., 0., 1.);
+        let leaf = spawn_at(&mut world, &mut objects, 0., 0., 0., 1.);
+        let sibling = spawn_at(&mut world, &mut objects, 0., 0., 0., 1.);
+        world.set_parent(middle, Some(root));
+        world.set_parent(leaf, Some(middle));
+        world.set_parent(sibling, Some(root));
+
+        world.despawn(middle, &mut objects);
+        assert!(world.entities() == [root, sibling]);
+        assert!(!world.contains(leaf));
+        assert!(!objects.is_alive(leaf));
+        assert!(world.children(root) == vec![sibling]);
+        assert!(world.get::<Transform>(leaf).is_none());
+
+        world.despawn(root, &mut objects);
+        assert!(world.is_empty());
+        assert!(world.query::<Children>().is_empty());
+    }
+
+    #[test]
+    fn propagate_transforms_stores_world_transforms() {
+        let mut objects = ObjectRegistry::new();
+        let mut world = World::new();
+        let root = spawn_at(&mut world, &mut objects, 5., 5., 0.5, 2.);
+        let middle = spawn_at(&mut world, &mut objects, 0., 0., 0., 1.);
+        let leaf = spawn_at(&mut world, &mut objects, 0., 0., 0., 1.);
+        world.set_parent(middle, Some(root));
+        world.set_parent(leaf, Some(middle));
+
+        // Transforms relative to parents
+        let mut transform = Transform::new();
+        transform.position = Vector2 { x: 1., y: 0. };
+        transform.rotation = 0.25;
+        world.insert(middle, transform);
+        transform.position = Vector2 { x: 0., y: 3. };
+        transform.rotation = 0.;
+        transform.scale = Vector2 { x: 0.5, y: 0.5 };
+        world.insert(leaf, transform);
+
+        world.propagate_transforms();
+        for entity in [root, middle, leaf].iter() {
+            let global_transform = world.get::<GlobalTransform>(*entity).unwrap();
+            assert_same_transform(
+                &global_transform.0,
+                &world.world_transform(*entity).unwrap(),
+            );
+        }
+
+        // Leaf is 3 units below middle, scaled by root and rotated by root and middle
+        let leaf_transform = world.get::<GlobalTransform>(leaf).unwrap().0;
+        let middle_transform = world.get::<GlobalTransform>(middle).unwrap().0;
+        let offset = leaf_transform.position - middle_transform.position;
+        assert!((offset.x * offset.x + offset.y * offset.y - 36.).abs() < 1e-3);
+        assert!((leaf_transform.rotation - 0.75).abs() < 1e-5);
+        assert!((leaf_transform.scale.x - 1.).abs() < 1e-5);
+
+        // Entities without Transform are skipped, their children use their own transform
+        world.remove::<Transform>(middle);
+        world.propagate_transforms();
+        assert!(world.get::<GlobalTransform>(middle).is_none());
+        let leaf_transform = world.get::<GlobalTransform>(leaf).unwrap().0;
+        assert_same_transform(&leaf_transform, world.get::<Transform>(leaf).unwrap());
+    }
+}
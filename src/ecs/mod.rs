@@ -0,0 +1,3 @@
+pub mod hierarchy;
+pub mod query;
+pub mod world;
@@ -0,0 +1,48 @@
+use crate::ecs::world::{Entity, World};
+
+use std::any::TypeId;
+
+/// Query of entities by which components they have and do not have
+pub struct Query {
+    with: Vec<TypeId>,
+    without: Vec<TypeId>,
+}
+
+impl Query {
+    /// Default constructor to initialize a Query that matches every entity
+    pub fn new() -> Self {
+        return Self {
+            with: Vec::new(),
+            without: Vec::new(),
+        };
+    }
+
+    /// Only match entities that have a component of type T
+    pub fn with<T: 'static>(mut self) -> Self {
+        self.with.push(TypeId::of::<T>());
+        return self;
+    }
+    /// Only match entities that do not have a component of type T
+    pub fn without<T: 'static>(mut self) -> Self {
+        self.without.push(TypeId::of::<T>());
+        return self;
+    }
+
+    /// Return matching entities of a World in spawn order
+    pub fn entities(&self, world: &World) -> Vec<Entity> {
+        return world
+            .entities()
+            .iter()
+            .filter(|entity| {
+                self.with
+                    .iter()
+                    .all(|type_id| world.has_type(**entity, *type_id))
+                    && !self
+                        .without
+                        .iter()
+                        .any(|type_id| world.has_type(**entity, *type_id))
+            })
+            .copied()
+            .collect();
+    }
+}
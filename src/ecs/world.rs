@@ -0,0 +1,412 @@
+use crate::app::App;
+use crate::components::hierarchy::{GlobalTransform, Parent};
+use crate::components::renderable::Renderable;
+use crate::components::transform::Transform;
+use crate::ecs::query::Query;
+use crate::entities::registry::{ObjectId, ObjectRegistry};
+
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, HashSet};
+
+/// An entity is the id of a game object registered with the App, so entities share the object lifecycle
+pub type Entity = ObjectId;
+
+/// Storage of every component of a single type
+trait ComponentStorage {
+    fn contains(&self, entity: Entity) -> bool;
+    fn remove_entity(&mut self, entity: Entity);
+    /// Move every component into storage of the same type
+    fn move_into(&mut self, other: &mut dyn ComponentStorage);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: 'static> ComponentStorage for HashMap<Entity, T> {
+    fn contains(&self, entity: Entity) -> bool {
+        return self.contains_key(&entity);
+    }
+    fn remove_entity(&mut self, entity: Entity) {
+        self.remove(&entity);
+    }
+    fn move_into(&mut self, other: &mut dyn ComponentStorage) {
+        if let Some(other) = other.as_any_mut().downcast_mut::<HashMap<Entity, T>>() {
+            other.extend(self.drain());
+        }
+    }
+    fn as_any(&self) -> &dyn Any {
+        return self;
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        return self;
+    }
+}
+
+/// A system is run every frame with access to the World and the App
+type System = Box<dyn FnMut(&mut World, &mut App)>;
+
+/// Entity-component-system world that stores entities, their components, and systems run every frame.
+/// Entities with a Renderable component are drawn every frame, at their Transform if they have one
+pub struct World {
+    entities: Vec<Entity>,
+    entity_set: HashSet<Entity>,
+    /// Whether this World stands in for a World moved out of the App, recording despawns of entities it does not contain
+    is_stand_in: bool,
+    /// Entities despawned that are not in this World, despawned from the World this one is appended to
+    despawned: Vec<Entity>,
+    components: HashMap<TypeId, Box<dyn ComponentStorage>>,
+    systems: Vec<System>,
+}
+
+impl World {
+    /// Default constructor to initialize an empty World
+    pub fn new() -> Self {
+        return Self {
+            entities: Vec::new(),
+            entity_set: HashSet::new(),
+            is_stand_in: false,
+            despawned: Vec::new(),
+            components: HashMap::new(),
+            systems: Vec::new(),
+        };
+    }
+
+    /// Return an empty World standing in for a World moved out of the App, to append to it afterwards.
+    /// Only meant to be called internally within sidekick
+    pub(crate) fn stand_in() -> Self {
+        let mut world = World::new();
+        world.is_stand_in = true;
+        return world;
+    }
+
+    /// Spawn a new entity without components
+    pub fn spawn(&mut self, objects: &mut ObjectRegistry) -> Entity {
+        let entity = objects.spawn();
+        self.entities.push(entity);
+        self.entity_set.insert(entity);
+        return entity;
+    }
+
+    /// Despawn an entity and all of its descendants, removing all of their components
+    pub fn despawn(&mut self, entity: Entity, objects: &mut ObjectRegistry) {
+        // Entities of the World this one stands in for are despawned from it once appended
+        if self.is_stand_in && !self.contains(entity) && objects.is_alive(entity) {
+            self.despawned.push(entity);
+        }
+        let mut entities = vec![entity];
+        entities.append(&mut self.descendants(entity));
+
+        for entity in entities.iter() {
+            objects.destroy(*entity);
+        }
+        self.remove_entities(&entities);
+    }
+
+    /// Return whether an entity exists in World
+    pub fn contains(&self, entity: Entity) -> bool {
+        return self.entity_set.contains(&entity);
+    }
+
+    /// Return every entity in spawn order
+    pub fn entities(&self) -> &[Entity] {
+        return &self.entities;
+    }
+    /// Return number of entities
+    pub fn len(&self) -> usize {
+        return self.entities.len();
+    }
+    /// Return whether World has no entity
+    pub fn is_empty(&self) -> bool {
+        return self.entities.is_empty();
+    }
+
+    /// Add a component to an entity, replacing any component of the same type
+    pub fn insert<T: 'static>(&mut self, entity: Entity, component: T) {
+        if !self.contains(entity) {
+            return;
+        }
+        self.components
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(HashMap::<Entity, T>::new()))
+            .as_any_mut()
+            .downcast_mut::<HashMap<Entity, T>>()
+            .unwrap()
+            .insert(entity, component);
+    }
+
+    /// Remove a component from an entity and return it
+    pub fn remove<T: 'static>(&mut self, entity: Entity) -> Option<T> {
+        return self.storage_mut::<T>()?.remove(&entity);
+    }
+
+    /// Return a reference to a component of an entity
+    pub fn get<T: 'static>(&self, entity: Entity) -> Option<&T> {
+        return self.storage::<T>()?.get(&entity);
+    }
+    /// Return a mutable reference to a component of an entity
+    pub fn get_mut<T: 'static>(&mut self, entity: Entity) -> Option<&mut T> {
+        return self.storage_mut::<T>()?.get_mut(&entity);
+    }
+
+    /// Return whether an entity has a component of type T
+    pub fn has<T: 'static>(&self, entity: Entity) -> bool {
+        return self.has_type(entity, TypeId::of::<T>());
+    }
+    /// Return whether an entity has a component of a type id
+    pub fn has_type(&self, entity: Entity, type_id: TypeId) -> bool {
+        return match self.components.get(&type_id) {
+            Some(storage) => storage.contains(entity),
+            None => false,
+        };
+    }
+
+    /// Return entities that have a component of type T. Use Query to match combinations of components
+    pub fn query<T: 'static>(&self) -> Vec<Entity> {
+        return Query::new().with::<T>().entities(self);
+    }
+
+    /// Add a system that is run every frame after the user-defined update
+    pub fn add_system<S>(&mut self, system: S)
+    where
+        S: FnMut(&mut World, &mut App) + 'static,
+    {
+        self.systems.push(Box::new(system));
+    }
+
+    /// Run every system in the order they were added. Only meant to be called internally within sidekick
+    pub fn run_systems(&mut self, app: &mut App) {
+        let mut systems = std::mem::take(&mut self.systems);
+        for system in systems.iter_mut() {
+            system(self, app);
+        }
+
+        // Keep systems added while running
+        systems.append(&mut self.systems);
+        self.systems = systems;
+    }
+
+    /// Draw every entity with a Renderable component at its world-space transform.
+    /// Only meant to be called internally within sidekick
+    pub fn render(&mut self, app: &mut App) {
+        self.propagate_transforms();
+
+        for entity in self.query::<Renderable>() {
+            let transform = self.get::<GlobalTransform>(entity).map(|global| global.0);
+            let is_root = !self.has::<Parent>(entity);
+            let renderable = self.get_mut::<Renderable>(entity).unwrap();
+
+            if let Some(transform) = transform {
+                renderable.game_object_mut().transform = transform;
+            }
+            renderable.game_object_mut().set_id(Some(entity));
+            renderable.draw(app);
+
+            // Bounded objects can be moved while drawing, children are positioned by their parents
+            if !is_root {
+                continue;
+            }
+            let transform = renderable.game_object().transform;
+            if let Some(entity_transform) = self.get_mut::<Transform>(entity) {
+                *entity_transform = transform;
+            }
+        }
+    }
+
+    /// Remove entities and all of their components. Only meant to be called internally within sidekick
+    pub fn remove_entities(&mut self, entities: &[Entity]) {
+        if entities.is_empty() {
+            return;
+        }
+        self.detach_from_parents(entities);
+        for entity in entities.iter() {
+            self.entity_set.remove(entity);
+        }
+        let entity_set = &self.entity_set;
+        self.entities.retain(|entity| entity_set.contains(entity));
+        for storage in self.components.values_mut() {
+            for entity in entities.iter() {
+                storage.remove_entity(*entity);
+            }
+        }
+    }
+
+    /// Move entities, components and systems of another World into this one,
+    /// and despawn entities that were despawned through the other World
+    pub(crate) fn append(&mut self, other: &mut World, objects: &mut ObjectRegistry) {
+        for entity in other.entities.drain(..) {
+            if self.entity_set.insert(entity) {
+                self.entities.push(entity);
+            }
+        }
+        other.entity_set.clear();
+        for (type_id, mut storage) in other.components.drain() {
+            match self.components.get_mut(&type_id) {
+                Some(existing) => storage.move_into(existing.as_mut()),
+                None => {
+                    self.components.insert(type_id, storage);
+                }
+            }
+        }
+        self.systems.append(&mut other.systems);
+        for entity in std::mem::take(&mut other.despawned) {
+            self.despawn(entity, objects);
+        }
+    }
+
+    /// Return storage of components of type T
+    fn storage<T: 'static>(&self) -> Option<&HashMap<Entity, T>> {
+        return self
+            .components
+            .get(&TypeId::of::<T>())?
+            .as_any()
+            .downcast_ref::<HashMap<Entity, T>>();
+    }
+    /// Return mutable storage of components of type T
+    fn storage_mut<T: 'static>(&mut self) -> Option<&mut HashMap<Entity, T>> {
+        return self
+            .components
+            .get_mut(&TypeId::of::<T>())?
+            .as_any_mut()
+            .downcast_mut::<HashMap<Entity, T>>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::query::Query;
+
+    struct Health(i32);
+    struct Speed(f32);
+
+    #[test]
+    fn spawn_insert_and_query() {
+        let mut objects = ObjectRegistry::new();
+        let mut world = World::new();
+        let first = world.spawn(&mut objects);
+        let second = world.spawn(&mut objects);
+        let third = world.spawn(&mut objects);
+        world.insert(first, Health(3));
+        world.insert(second, Health(5));
+        world.insert(second, Speed(2.));
+        world.insert(third, Speed(1.));
+
+        assert_eq!(world.len(), 3);
+        assert!(world.entities() == [first, second, third]);
+        assert!(world.query::<Health>() == vec![first, second]);
+        assert!(
+            Query::new()
+                .with::<Health>()
+                .with::<Speed>()
+                .entities(&world)
+                == vec![second]
+        );
+        assert!(
+            Query::new()
+                .with::<Speed>()
+                .without::<Health>()
+                .entities(&world)
+                == vec![third]
+        );
+
+        // Inserting replaces components, and entities that are not in World get no components
+        world.insert(first, Health(4));
+        assert_eq!(world.get::<Health>(first).map(|health| health.0), Some(4));
+        let outside = objects.spawn();
+        world.insert(outside, Health(1));
+        assert!(!world.has::<Health>(outside));
+
+        assert_eq!(
+            world.remove::<Health>(first).map(|health| health.0),
+            Some(4)
+        );
+        assert!(world.query::<Health>() == vec![second]);
+    }
+
+    #[test]
+    fn despawn_twice_and_stale_entities() {
+        let mut objects = ObjectRegistry::new();
+        let mut world = World::new();
+        let entity = world.spawn(&mut objects);
+        world.insert(entity, Health(1));
+
+        world.despawn(entity, &mut objects);
+        world.despawn(entity, &mut objects);
+        assert!(!world.contains(entity));
+        assert!(!objects.is_alive(entity));
+        assert!(world.query::<Health>().is_empty());
+
+        // Objects that are not entities of this World are left alone
+        objects.flush();
+        let object = objects.spawn();
+        world.despawn(entity, &mut objects);
+        world.despawn(object, &mut objects);
+        assert!(world.despawned.is_empty());
+    }
+
+    #[test]
+    fn stand_in_records_despawns_once() {
+        let mut objects = ObjectRegistry::new();
+        let mut world = World::new();
+        let parent = world.spawn(&mut objects);
+        let child = world.spawn(&mut objects);
+        world.set_parent(child, Some(parent));
+
+        let mut stand_in = World::stand_in();
+        let spawned = stand_in.spawn(&mut objects);
+        stand_in.insert(spawned, Speed(1.));
+        stand_in.despawn(parent, &mut objects);
+        stand_in.despawn(parent, &mut objects);
+        assert_eq!(stand_in.despawned.len(), 1);
+
+        // Despawns cascade to descendants once appended, and entities spawned in the stand-in are kept
+        world.append(&mut stand_in, &mut objects);
+        assert!(!world.contains(parent));
+        assert!(!world.contains(child));
+        assert!(!objects.is_alive(child));
+        assert!(world.entities() == [spawned]);
+        assert_eq!(world.get::<Speed>(spawned).map(|speed| speed.0), Some(1.));
+        assert!(world.despawned.is_empty());
+    }
+
+    #[test]
+    fn spawn_and_despawn_through_app_inside_systems() {
+        let mut entities: Vec<Entity> = Vec::new();
+        let mut frame_states = Vec::new();
+        App::new().run_headless(
+            |app: &mut App| {
+                let parent = app.spawn_entity();
+                let child = app.spawn_entity();
+                app.world.set_parent(child, Some(parent));
+                app.world
+                    .add_system(move |world: &mut World, app: &mut App| {
+                        if app.input.frame() == 0 {
+                            // The App's World is moved out while systems run
+                            assert!(app.world.is_empty());
+                            let spawned = app.spawn_entity();
+                            app.world.insert(spawned, Health(7));
+                            app.despawn_entity(parent);
+                            assert!(world.contains(child));
+                        }
+                    });
+            },
+            |app: &mut App| {
+                if app.input.frame() == 0 {
+                    entities = app.world.entities().to_vec();
+                } else {
+                    let entity_count = app.world.len();
+                    let health: Vec<i32> = app
+                        .world
+                        .query::<Health>()
+                        .into_iter()
+                        .map(|entity| app.world.get::<Health>(entity).unwrap().0)
+                        .collect();
+                    frame_states.push((entity_count, health, app.world.despawned.len()));
+                }
+            },
+            3,
+        );
+
+        assert_eq!(entities.len(), 2);
+        assert_eq!(frame_states, vec![(1, vec![7], 0), (1, vec![7], 0)]);
+    }
+}
@@ -1,11 +1,12 @@
 use crate::app::App;
 use crate::entities::game_object::GameObject;
-use crate::entities::game_object::RenderLayer;
+use crate::rendering::layers::RenderLayer;
+use crate::rendering::static_geometry::StaticShapeId;
 use crate::types::color::Color;
+use crate::types::vector2::Vector2;
 
 use rgx::core::*;
 use rgx::kit::shape2d::{Fill, Shape};
-use rgx::kit::ZDepth;
 use rgx::math::Point2;
 
 /// A circle 🤷‍♂️
@@ -16,9 +17,6 @@ pub struct Circle {
     pub stroke_size: f32,
     pub stroke_color: Color,
     pub layer: RenderLayer,
-
-    index: usize,
-    is_init: bool,
 }
 
 impl Copy for Circle {}
@@ -38,65 +36,52 @@ impl Circle {
             fill_color: Color::white(),
             stroke_size: 0.,
             stroke_color: Color::white(),
-            layer: RenderLayer::Layer1,
-
-            index: 0,
-            is_init: false,
+            layer: RenderLayer::new(),
         };
     }
 
     /// Render circle on screen
     pub fn draw(&mut self, app: &mut App) {
-        if self.is_init {
-            app.shapes.remove(self.index);
-        } else {
-            self.is_init = true;
+        // Hidden and destroyed objects are not drawn
+        if !self.game_object.is_active(app) {
+            return;
         }
 
-        let x = self.game_object.transform.position.x;
-        let y = self.game_object.transform.position.y;
-
-        let radius = self.game_object.transform.radius;
-
-        let viewport_x = if self.game_object.is_parallax {
-            0.
-        } else {
-            app.game_view.game_object.transform.position.x
-        };
-        let viewport_y = if self.game_object.is_parallax {
-            0.
-        } else {
-            app.game_view.game_object.transform.position.y
-        };
+        let radius = self.game_object.transform.scaled_radius();
 
         // Bound object in game view
         if self.game_object.is_bounded {
-            let max_x = app.width() as f32 / 2. - radius + viewport_x / 2.;
-            let min_x = -(app.width() as f32) / 2. + radius + viewport_x / 2.;
-            if x > max_x {
-                self.game_object.transform.position.x = max_x;
-            } else if x < min_x {
-                self.game_object.transform.position.x = min_x;
-            }
-
-            let max_y = app.height() as f32 / 2. - radius + viewport_y / 2.;
-            let min_y = -(app.height() as f32) / 2. + radius + viewport_y / 2.;
-            if y > max_y {
-                self.game_object.transform.position.y = max_y;
-            } else if y < min_y {
-                self.game_object.transform.position.y = min_y;
-            }
+            self.game_object.keep_in_view(
+                &app.game_view,
+                Vector2 {
+                    x: radius,
+                    y: radius,
+                },
+                app.layers.parallax(self.layer),
+            );
         }
 
-        app.shapes.push(
-            Shape::circle(
-                Point2::new(
-                    x + app.width() as f32 / 2. - viewport_x / 2.,
-                    y + app.height() as f32 / 2. - viewport_y / 2.,
-                ),
-                self.game_object.transform.radius,
-                32,
-            )
+        app.draw_list.submit_object(
+            self.shape(),
+            &self.game_object,
+            self.layer,
+            self.stroke_size,
+        );
+    }
+
+    /// Add circle as it is now to the App's static geometry, to draw it every frame without calling draw,
+    /// for level geometry that does not move
+    pub fn add_static(&self, app: &mut App) -> StaticShapeId {
+        return app.static_geometry.add(self.shape(), self.layer);
+    }
+
+    /// Return shape of circle in world coordinates
+    pub fn shape(&self) -> Shape {
+        let radius = self.game_object.transform.scaled_radius();
+        let x = self.game_object.transform.position.x;
+        let y = self.game_object.transform.position.y;
+
+        return Shape::circle(Point2::new(x, y), radius, 32)
             .fill(Fill::solid(Rgba::new(
                 self.fill_color.r,
                 self.fill_color.g,
@@ -111,10 +96,6 @@ impl Circle {
                     self.stroke_color.b,
                     self.stroke_color.a,
                 ),
-            )
-            .zdepth(ZDepth::from(self.layer as i32 as f32 / 10.)),
-        );
-
-        self.index = app.shapes.len() - 1;
+            );
     }
 }
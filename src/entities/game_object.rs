@@ -1,33 +1,16 @@
+use crate::app::App;
 use crate::components::transform::Transform;
+use crate::entities::game_view::GameView;
+use crate::entities::registry::ObjectId;
 use crate::types::vector2::Vector2;
 
-pub enum RenderLayer {
-    Layer1 = 0,
-    Layer2 = 1,
-    Layer3 = 2,
-    Layer4 = 3,
-    Layer5 = 4,
-    Layer6 = 5,
-    Layer7 = 6,
-    Layer8 = 7,
-    Layer9 = 8,
-    Layer10 = 9,
-}
-
-impl Copy for RenderLayer {}
-
-impl Clone for RenderLayer {
-    fn clone(&self) -> RenderLayer {
-        *self
-    }
-}
-
 /// A game object is an entity that exist in the game world
 pub struct GameObject {
     pub transform: Transform,
     pub is_visible: bool,
-    pub is_parallax: bool,
     pub is_bounded: bool,
+    id: Option<ObjectId>,
+    is_destroyed: bool,
 }
 
 impl Copy for GameObject {}
@@ -44,8 +27,9 @@ impl GameObject {
         return Self {
             transform: Transform::new(),
             is_visible: true,
-            is_parallax: false,
             is_bounded: false,
+            id: None,
+            is_destroyed: false,
         };
     }
 
@@ -69,8 +53,63 @@ impl GameObject {
         self.transform.size += scale;
     }
 
-    /// Destroy current game object
-    pub fn destroy(&mut self) {
-        // TODO: destroy object
+    /// Clamp position so an object with a half size on a layer with a parallax factor stays within the area shown by a game view
+    pub fn keep_in_view(&mut self, game_view: &GameView, half_size: Vector2, parallax: Vector2) {
+        let (min, max) = game_view.visible_area(parallax);
+        let position = &mut self.transform.position;
+        // Objects larger than the view are centered instead
+        position.x = if max.x - min.x <= half_size.x * 2. {
+            (min.x + max.x) / 2.
+        } else {
+            position.x.clamp(min.x + half_size.x, max.x - half_size.x)
+        };
+        position.y = if max.y - min.y <= half_size.y * 2. {
+            (min.y + max.y) / 2.
+        } else {
+            position.y.clamp(min.y + half_size.y, max.y - half_size.y)
+        };
+    }
+
+    /// Register current game object with the App so its lifecycle is tracked.
+    /// A destroyed game object is registered again with a new id, so it can be reused
+    pub fn spawn(&mut self, app: &mut App) {
+        let is_alive = match self.id {
+            Some(id) => app.objects.is_alive(id),
+            None => false,
+        };
+        if !is_alive {
+            self.id = Some(app.objects.spawn());
+        }
+        self.is_destroyed = false;
+    }
+
+    /// Destroy current game object, it stops being drawn and is removed from rendering at the end of the frame
+    pub fn destroy(&mut self, app: &mut App) {
+        self.is_destroyed = true;
+        if let Some(id) = self.id {
+            app.objects.destroy(id);
+        }
+    }
+
+    /// Return id of current game object if it has been spawned
+    pub fn id(&self) -> Option<ObjectId> {
+        return self.id;
+    }
+    /// Set id of current game object, is used to attach objects to entities
+    pub(crate) fn set_id(&mut self, id: Option<ObjectId>) {
+        self.id = id;
+    }
+
+    /// Return whether current game object has been destroyed
+    pub fn is_destroyed(&self, app: &App) -> bool {
+        return match self.id {
+            Some(id) => self.is_destroyed || !app.objects.is_alive(id),
+            None => self.is_destroyed,
+        };
+    }
+
+    /// Return whether current game object should be drawn, i.e. it is visible and not destroyed
+    pub fn is_active(&self, app: &App) -> bool {
+        return self.is_visible && !self.is_destroyed(app);
     }
 }
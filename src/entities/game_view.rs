@@ -1,19 +1,67 @@
+use crate::components::transform::Transform;
 use crate::entities::game_object::GameObject;
+use crate::rendering::layers::RenderLayer;
+use crate::rendering::resolution::ScreenTransform;
+use crate::rendering::viewport::Viewport;
 use crate::types::color::Color;
 use crate::types::vector2::Vector2;
 
 use rand::Rng;
 
-/// Current game viewport
+/// How the game view follows a target
+pub struct CameraFollow {
+    /// Half size of the area around the screen center the target can move in without moving the game view
+    pub dead_zone: Vector2,
+    /// Time in seconds for the game view to move most of the way to the target, 0 to snap to the target
+    pub damping: f32,
+    /// Seconds of target movement the game view looks ahead of the target, 0 to not look ahead
+    pub look_ahead: f32,
+}
+
+impl Copy for CameraFollow {}
+
+impl Clone for CameraFollow {
+    fn clone(&self) -> CameraFollow {
+        *self
+    }
+}
+
+impl CameraFollow {
+    /// Default constructor to initialize follow that snaps to the target
+    pub fn new() -> Self {
+        return Self {
+            dead_zone: Vector2::zero(),
+            damping: 0.,
+            look_ahead: 0.,
+        };
+    }
+}
+
+/// Current game viewport, also known as the camera.
+/// Rotating the game object rotates the camera, rotating the world the opposite way on screen
 pub struct GameView {
     pub game_object: GameObject,
     pub color: Color,
+    /// How much the world is magnified, greater than 1 zooms in
+    pub zoom: f32,
+    /// Area of the screen game view is drawn to, the rest of the screen is left to other game views
+    pub viewport: Viewport,
+    hidden_layers: Vec<RenderLayer>,
+    screen: ScreenTransform,
+    scale_factor: f32,
+
+    /// How the game view follows its target
+    pub follow_settings: CameraFollow,
+    target: Option<Vector2>,
+    previous_target: Option<Vector2>,
+    /// Smallest and largest world positions the game view can show, None to not clamp
+    pub bounds: Option<(Vector2, Vector2)>,
 
     pub is_shaking: bool,
     shake_intensity: f32,
     shake_duration: f32,
     shake_decrease_factor: f32,
-    original_position: Vector2,
+    shake_offset: Vector2,
 }
 
 impl GameView {
@@ -22,33 +70,240 @@ impl GameView {
         return Self {
             game_object: GameObject::new(),
             color: Color::black(),
+            zoom: 1.,
+            viewport: Viewport::full(),
+            hidden_layers: Vec::new(),
+            screen: ScreenTransform::identity(Vector2::zero()),
+            scale_factor: 1.,
+            follow_settings: CameraFollow::new(),
+            target: None,
+            previous_target: None,
+            bounds: None,
             is_shaking: false,
             shake_intensity: 0.,
             shake_duration: 0.,
             shake_decrease_factor: 0.,
-            original_position: Vector2::zero(),
+            shake_offset: Vector2::zero(),
+        };
+    }
+
+    /// Return world position at the center of the screen, including shake.
+    /// This is the game view position offset by shake
+    pub fn center(&self) -> Vector2 {
+        return self.game_object.transform.position + self.shake_offset;
+    }
+    /// Move game view so a world position is at the center of the screen
+    pub fn set_center(&mut self, center: Vector2) {
+        self.game_object.transform.position = center;
+    }
+
+    /// Return smallest and largest world positions on screen for a layer with a parallax factor, ignoring rotation
+    pub fn visible_area(&self, parallax: Vector2) -> (Vector2, Vector2) {
+        let center = self.center() * parallax;
+        let half_size = self.virtual_viewport_size() / 2. / self.non_zero_zoom().abs();
+        return (center - half_size, center + half_size);
+    }
+    /// Return smallest and largest world positions on screen for a layer with a parallax factor,
+    /// growing to fit the screen corners when the game view is rotated
+    pub fn visible_bounds(&self, parallax: Vector2) -> (Vector2, Vector2) {
+        let center = self.center() * parallax;
+        let half_size = self.virtual_viewport_size() / 2. / self.non_zero_zoom().abs();
+        let (sin, cos) = self.game_object.transform.rotation.sin_cos();
+        let half_size = Vector2 {
+            x: (half_size.x * cos).abs() + (half_size.y * sin).abs(),
+            y: (half_size.x * sin).abs() + (half_size.y * cos).abs(),
+        };
+        return (center - half_size, center + half_size);
+    }
+
+    /// Follow a target, call every frame with the target's current transform
+    pub fn follow(&mut self, target: &Transform) {
+        self.target = Some(target.position);
+    }
+    /// Stop following the current target
+    pub fn stop_following(&mut self) {
+        self.target = None;
+        self.previous_target = None;
+    }
+    /// Return whether game view is following a target
+    pub fn is_following(&self) -> bool {
+        return self.target.is_some();
+    }
+
+    /// Move game view towards its target and keep it within bounds.
+    /// Only meant to be called internally within sidekick, once per frame
+    pub fn update(&mut self, frame_time: f32) {
+        let mut center = self.game_object.transform.position;
+
+        if let Some(target) = self.target {
+            // Look ahead in the direction the target is moving
+            let velocity = match self.previous_target {
+                Some(previous) if frame_time > 0. => (target - previous) / frame_time,
+                _ => Vector2::zero(),
+            };
+            self.previous_target = Some(target);
+            let goal = target + velocity * self.follow_settings.look_ahead;
+
+            // Only move far enough to keep the goal inside the dead zone
+            let dead_zone = self.follow_settings.dead_zone;
+            let mut desired = center;
+            desired.x = goal.x.clamp(center.x - dead_zone.x, center.x + dead_zone.x);
+            desired.x = center.x + (goal.x - desired.x);
+            desired.y = goal.y.clamp(center.y - dead_zone.y, center.y + dead_zone.y);
+            desired.y = center.y + (goal.y - desired.y);
+
+            // Exponential smoothing so damping does not depend on frame rate
+            let damping = self.follow_settings.damping;
+            let amount = if damping > 0. {
+                1. - (-frame_time * 3. / damping).exp()
+            } else {
+                1.
+            };
+            center = center + (desired - center) * amount;
+        }
+
+        if let Some((min, max)) = self.bounds {
+            center = self.clamped_center(center, min, max);
+        }
+        self.set_center(center);
+    }
+
+    /// Clamp a center position so the screen stays within bounds, centering on bounds smaller than the screen.
+    /// A rotated game view is clamped by the area around its rotated screen corners
+    fn clamped_center(&self, center: Vector2, min: Vector2, max: Vector2) -> Vector2 {
+        let (min_visible, max_visible) = self.visible_bounds(Vector2::identity());
+        let half_size = (max_visible - min_visible) / 2.;
+        let clamp = |value: f32, min: f32, max: f32, half_size: f32| {
+            if max - min <= half_size * 2. {
+                (min + max) / 2.
+            } else {
+                value.clamp(min + half_size, max - half_size)
+            }
+        };
+        return Vector2 {
+            x: clamp(center.x, min.x, max.x, half_size.x),
+            y: clamp(center.y, min.y, max.y, half_size.y),
+        };
+    }
+    /// Return size of the screen the game view is drawn to, in virtual pixels
+    pub fn screen_size(&self) -> Vector2 {
+        return self.screen.size;
+    }
+    /// Return how the screen the game view is drawn to is placed in the window
+    pub fn screen_transform(&self) -> ScreenTransform {
+        return self.screen;
+    }
+    /// Return top left corner of viewport on screen, in physical pixels
+    pub fn viewport_origin(&self) -> Vector2 {
+        return self
+            .screen
+            .to_window(self.viewport.origin(self.screen.size));
+    }
+    /// Return size of viewport on screen, in physical pixels
+    pub fn viewport_size(&self) -> Vector2 {
+        let origin = self.viewport.origin(self.screen.size);
+        return self
+            .screen
+            .to_window(origin + self.viewport.size(self.screen.size))
+            - self.screen.to_window(origin);
+    }
+    /// Return whether a screen position in physical pixels is inside the viewport
+    pub fn contains_screen_point(&self, screen: Vector2) -> bool {
+        let min = self.viewport_origin();
+        let max = min + self.viewport_size();
+        return screen.x >= min.x && screen.x < max.x && screen.y >= min.y && screen.y < max.y;
+    }
+
+    /// Draw a render layer, every layer is drawn by default
+    pub fn show_layer(&mut self, layer: RenderLayer) {
+        self.hidden_layers.retain(|hidden| *hidden != layer);
+    }
+    /// Stop drawing a render layer, e.g. to hide one player's HUD from the other player's view
+    pub fn hide_layer(&mut self, layer: RenderLayer) {
+        if !self.hidden_layers.contains(&layer) {
+            self.hidden_layers.push(layer);
+        }
+    }
+    /// Return whether a render layer is drawn
+    pub fn is_layer_visible(&self, layer: RenderLayer) -> bool {
+        return !self.hidden_layers.contains(&layer);
+    }
+    /// Return number of physical pixels per logical pixel of the screen the game view is drawn to
+    pub fn scale_factor(&self) -> f32 {
+        return self.scale_factor;
+    }
+
+    /// Convert a world position to a screen position in physical pixels from the top left corner
+    pub fn world_to_screen(&self, world: Vector2) -> Vector2 {
+        return self.world_to_screen_with_parallax(world, Vector2::identity());
+    }
+    /// Convert a world position to a screen position for a shape that moves with the game view by a parallax factor
+    pub fn world_to_screen_with_parallax(&self, world: Vector2, parallax: Vector2) -> Vector2 {
+        let offset = rotate(
+            world - self.center() * parallax,
+            -self.game_object.transform.rotation,
+        );
+        return self
+            .screen
+            .to_window(offset * self.non_zero_zoom() + self.viewport_center());
+    }
+    /// Convert a screen position in physical pixels from the top left corner to a world position,
+    /// e.g. to find what the mouse is pointing at
+    pub fn screen_to_world(&self, screen: Vector2) -> Vector2 {
+        let offset =
+            (self.screen.to_virtual(screen) - self.viewport_center()) / self.non_zero_zoom();
+        return rotate(offset, self.game_object.transform.rotation) + self.center();
+    }
+    /// Convert a world position to a screen position in logical pixels, e.g. for UI laid out in logical pixels
+    pub fn world_to_logical_screen(&self, world: Vector2) -> Vector2 {
+        return self.world_to_screen(world) / self.scale_factor;
+    }
+    /// Convert a screen position in logical pixels to a world position
+    pub fn logical_screen_to_world(&self, screen: Vector2) -> Vector2 {
+        return self.screen_to_world(screen * self.scale_factor);
+    }
+
+    /// Set placement and scale factor of the screen. Only meant to be called internally within sidekick
+    pub fn set_screen(&mut self, screen: ScreenTransform, scale_factor: f32) {
+        self.screen = screen;
+        self.scale_factor = scale_factor;
+    }
+
+    /// Return size of viewport in virtual pixels
+    fn virtual_viewport_size(&self) -> Vector2 {
+        return self.viewport.size(self.screen.size);
+    }
+    /// Return center of viewport in virtual pixels
+    fn viewport_center(&self) -> Vector2 {
+        return self.viewport.origin(self.screen.size) + self.virtual_viewport_size() / 2.;
+    }
+
+    /// Return zoom that can be divided by
+    fn non_zero_zoom(&self) -> f32 {
+        return if self.zoom.abs() < f32::EPSILON {
+            f32::EPSILON
+        } else {
+            self.zoom
         };
     }
 
-    /// Start shaking the current game view
+    /// Start shaking the current game view, moving it up to intensity world units in each direction
     pub fn start_shaking(&mut self, intensity: f32, duration: f32, decrease_factor: f32) {
         self.shake_intensity = intensity;
         self.shake_duration = duration;
         self.shake_decrease_factor = decrease_factor;
 
         self.is_shaking = true;
-        self.original_position = self.game_object.transform.position;
     }
 
     /// Stop shaking the current game view
     pub fn stop_shaking(&mut self) {
         self.is_shaking = false;
-        self.game_object.transform.position = self.original_position;
-        self.original_position = Vector2::zero();
+        self.shake_offset = Vector2::zero();
     }
 
-    /// Randomize viewport position during shake period.
-    /// Note: Shaking only affects non-parallax game objects.
+    /// Randomize viewport offset during shake period.
+    /// Note: Shaking only affects layers that move with the game view.
     pub fn shake(&mut self, frame_time: f32) {
         // Decrease shake duration every frame
         self.shake_duration -= frame_time * self.shake_decrease_factor;
@@ -56,13 +311,205 @@ impl GameView {
         // If shake duration runs out then stop shaking
         if self.shake_duration <= 0. {
             self.stop_shaking();
+            return;
         }
 
-        // Randomize viewport position in intensity range
-        self.game_object.transform.position = self.original_position
-            + Vector2 {
-                x: rand::thread_rng().gen_range(-self.shake_intensity..self.shake_intensity),
-                y: rand::thread_rng().gen_range(-self.shake_intensity..self.shake_intensity),
-            };
+        // Randomize viewport offset in intensity range, leaving its position free to follow a target
+        self.shake_offset = Vector2 {
+            x: rand::thread_rng().gen_range(-self.shake_intensity..self.shake_intensity),
+            y: rand::thread_rng().gen_range(-self.shake_intensity..self.shake_intensity),
+        };
+    }
+}
+
+/// Rotate a vector by an angle, in the same direction shapes are rotated when rendered
+fn rotate(vector: Vector2, angle: f32) -> Vector2 {
+    let (sin, cos) = angle.sin_cos();
+    return Vector2 {
+        x: vector.x * cos + vector.y * sin,
+        y: -vector.x * sin + vector.y * cos,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Return a game view drawn to a 200x100 screen
+    fn new_game_view() -> GameView {
+        let mut game_view = GameView::new();
+        game_view.set_screen(ScreenTransform::identity(Vector2 { x: 200., y: 100. }), 1.);
+        return game_view;
+    }
+
+    /// Return a transform at a position
+    fn at(x: f32, y: f32) -> Transform {
+        let mut transform = Transform::new();
+        transform.position = Vector2 { x, y };
+        return transform;
+    }
+
+    fn assert_near(a: Vector2, b: Vector2) {
+        assert!(
+            (a.x - b.x).abs() < 1e-3 && (a.y - b.y).abs() < 1e-3,
+            "{} != {}",
+            a,
+            b
+        );
+    }
+
+    #[test]
+    fn follow_snaps_to_target_outside_dead_zone() {
+        let mut game_view = new_game_view();
+        game_view.follow(&at(30., -20.));
+        game_view.update(1. / 60.);
+        assert_near(game_view.center(), Vector2 { x: 30., y: -20. });
+
+        // Target moving inside the dead zone does not move the game view
+        game_view.follow_settings.dead_zone = Vector2 { x: 10., y: 5. };
+        game_view.follow(&at(38., -16.));
+        game_view.update(1. / 60.);
+        assert_near(game_view.center(), Vector2 { x: 30., y: -20. });
+
+        // Leaving the dead zone moves the game view just enough to keep the target on its edge
+        game_view.follow(&at(50., -40.));
+        game_view.update(1. / 60.);
+        assert_near(game_view.center(), Vector2 { x: 40., y: -35. });
+
+        game_view.stop_following();
+        game_view.update(1. / 60.);
+        assert!(!game_view.is_following());
+        assert_near(game_view.center(), Vector2 { x: 40., y: -35. });
+    }
+
+    #[test]
+    fn damping_does_not_depend_on_frame_rate() {
+        let mut game_view = new_game_view();
+        game_view.follow_settings.damping = 0.5;
+        game_view.follow(&at(100., 0.));
+        game_view.update(0.5);
+        // Moves 1 - e^-3 of the way, about 95%, in damping seconds
+        let expected = 100. * (1. - (-3f32).exp());
+        assert_near(game_view.center(), Vector2 { x: expected, y: 0. });
+
+        let mut game_view_2 = new_game_view();
+        game_view_2.follow_settings.damping = 0.5;
+        for _ in 0..30 {
+            game_view_2.follow(&at(100., 0.));
+            game_view_2.update(0.5 / 30.);
+        }
+        assert_near(game_view_2.center(), game_view.center());
+    }
+
+    #[test]
+    fn look_ahead_leads_moving_target() {
+        let mut game_view = new_game_view();
+        game_view.follow_settings.look_ahead = 0.5;
+        game_view.follow(&at(0., 0.));
+        game_view.update(0.1);
+        assert_near(game_view.center(), Vector2::zero());
+
+        // Target moves 60 units per second to the right and 20 up
+        game_view.follow(&at(6., -2.));
+        game_view.update(0.1);
+        assert_near(game_view.center(), Vector2 { x: 36., y: -12. });
+
+        // Stopped targets are not looked ahead of, and a zero frame time has no velocity
+        game_view.follow(&at(6., -2.));
+        game_view.update(0.);
+        assert_near(game_view.center(), Vector2 { x: 6., y: -2. });
+    }
+
+    #[test]
+    fn bounds_keep_screen_inside() {
+        let mut game_view = new_game_view();
+        game_view.bounds = Some((Vector2 { x: -500., y: -500. }, Vector2 { x: 500., y: 500. }));
+        game_view.follow(&at(1000., -1000.));
+        game_view.update(1. / 60.);
+        assert_near(game_view.center(), Vector2 { x: 400., y: -450. });
+
+        // Zooming in shows less of the world so the game view can move closer to the edges
+        game_view.zoom = 2.;
+        game_view.update(1. / 60.);
+        assert_near(game_view.center(), Vector2 { x: 450., y: -475. });
+
+        // A quarter turn swaps the extent of the screen in the world
+        game_view.zoom = 1.;
+        game_view.game_object.transform.rotation = std::f32::consts::FRAC_PI_2;
+        game_view.update(1. / 60.);
+        assert_near(game_view.center(), Vector2 { x: 450., y: -400. });
+        let (min, max) = game_view.visible_bounds(Vector2::identity());
+        assert!(min.x >= -500. - 1e-3 && max.x <= 500. + 1e-3);
+        assert!(min.y >= -500. - 1e-3 && max.y <= 500. + 1e-3);
+
+        // Bounds narrower than the screen center it on that axis
+        game_view.game_object.transform.rotation = 0.;
+        game_view.bounds = Some((Vector2 { x: 0., y: 0. }, Vector2 { x: 100., y: 300. }));
+        game_view.update(1. / 60.);
+        assert_near(game_view.center(), Vector2 { x: 50., y: 50. });
+    }
+
+    #[test]
+    fn world_to_screen_under_zoom_and_rotation() {
+        let mut game_view = new_game_view();
+        game_view.set_center(Vector2 { x: 30., y: -10. });
+        assert_near(
+            game_view.world_to_screen(Vector2 { x: 30., y: -10. }),
+            Vector2 { x: 100., y: 50. },
+        );
+
+        // Zoom magnifies distances from the center
+        game_view.zoom = 2.;
+        assert_near(
+            game_view.world_to_screen(Vector2 { x: 40., y: -5. }),
+            Vector2 { x: 120., y: 60. },
+        );
+
+        // Rotating the game view a quarter turn turns the world the opposite way on screen
+        game_view.zoom = 1.;
+        game_view.game_object.transform.rotation = std::f32::consts::FRAC_PI_2;
+        assert_near(
+            game_view.world_to_screen(Vector2 { x: 40., y: -10. }),
+            Vector2 { x: 100., y: 60. },
+        );
+    }
+
+    #[test]
+    fn screen_to_world_inverts_world_to_screen() {
+        // Letterboxed screen scaled 3 times, with a scale factor of 2
+        let screen = ScreenTransform {
+            size: Vector2 { x: 200., y: 100. },
+            scale: Vector2 { x: 3., y: 3. },
+            offset: Vector2 { x: 0., y: 50. },
+        };
+        for &zoom in [1., 0.5, 2.5].iter() {
+            for &rotation in [0., 0.4, -2.].iter() {
+                for viewport in [Viewport::full(), Viewport::right_half()].iter() {
+                    let mut game_view = GameView::new();
+                    game_view.set_screen(screen, 2.);
+                    game_view.set_center(Vector2 { x: -12., y: 7. });
+                    game_view.zoom = zoom;
+                    game_view.game_object.transform.rotation = rotation;
+                    game_view.viewport = *viewport;
+
+                    for &(x, y) in [(0., 0.), (-12., 7.), (150., -40.), (-3.5, 99.)].iter() {
+                        let world = Vector2 { x, y };
+                        let screen = game_view.world_to_screen(world);
+                        assert_near(game_view.screen_to_world(screen), world);
+
+                        let logical = game_view.world_to_logical_screen(world);
+                        assert_near(logical * 2., screen);
+                        assert_near(game_view.logical_screen_to_world(logical), world);
+                    }
+
+                    // The game view center is drawn at the center of its viewport
+                    let (origin, size) = (game_view.viewport_origin(), game_view.viewport_size());
+                    assert_near(
+                        game_view.world_to_screen(game_view.center()),
+                        origin + size / 2.,
+                    );
+                }
+            }
+        }
     }
 }
@@ -1,12 +1,12 @@
 use crate::app::App;
 use crate::entities::game_object::GameObject;
-use crate::entities::game_object::RenderLayer;
+use crate::rendering::layers::RenderLayer;
+use crate::rendering::static_geometry::StaticShapeId;
 use crate::types::color::Color;
 use crate::types::vector2::Vector2;
 
 use rgx::core::*;
 use rgx::kit::shape2d::{Fill, Shape};
-use rgx::kit::ZDepth;
 use rgx::math::Point2;
 
 /// A line 🤷‍♂️
@@ -17,9 +17,6 @@ pub struct Line {
     pub stroke_size: f32,
     pub stroke_color: Color,
     pub layer: RenderLayer,
-
-    index: usize,
-    is_init: bool,
 }
 
 impl Copy for Line {}
@@ -39,92 +36,75 @@ impl Line {
             fill_color: Color::white(),
             stroke_size: 1.,
             stroke_color: Color::white(),
-            layer: RenderLayer::Layer1,
-
-            index: 0,
-            is_init: false,
+            layer: RenderLayer::new(),
         };
     }
 
     /// Render line on screen
     pub fn draw(&mut self, app: &mut App) {
-        if self.is_init {
-            app.shapes.remove(self.index);
-        } else {
-            self.is_init = true;
+        // Hidden and destroyed objects are not drawn
+        if !self.game_object.is_active(app) {
+            return;
         }
 
-        let x = self.game_object.transform.position.x;
-        let y = self.game_object.transform.position.y;
-
-        let radius = self.game_object.transform.radius;
-
-        let viewport_x = if self.game_object.is_parallax {
-            0.
-        } else {
-            app.game_view.game_object.transform.position.x
-        };
-        let viewport_y = if self.game_object.is_parallax {
-            0.
-        } else {
-            app.game_view.game_object.transform.position.y
-        };
+        let radius = self.game_object.transform.radius * self.game_object.transform.scale.y;
 
         // Bound object in game view
         if self.game_object.is_bounded {
-            let max_x = app.width() as f32 / 2. + viewport_x / 2.;
-            let min_x = -(app.width() as f32) / 2. + viewport_x / 2.;
-            if x > max_x {
-                self.game_object.transform.position.x = max_x;
-            } else if x < min_x {
-                self.game_object.transform.position.x = min_x;
-            }
-
-            let max_y = app.height() as f32 / 2. - radius + viewport_y / 2.;
-            let min_y = -(app.height() as f32) / 2. + radius + viewport_y / 2.;
-            if y > max_y {
-                self.game_object.transform.position.y = max_y;
-            } else if y < min_y {
-                self.game_object.transform.position.y = min_y;
-            }
+            self.game_object.keep_in_view(
+                &app.game_view,
+                Vector2 { x: 0., y: radius },
+                app.layers.parallax(self.layer),
+            );
         }
 
-        let vertex_0 = Vector2 {
-            x: x + app.width() as f32 / 2. - viewport_x / 2.,
-            y: y + app.height() as f32 / 2. - radius - viewport_y / 2.,
-        };
-        let vertex_1 = Vector2 {
-            x: x + app.width() as f32 / 2. - viewport_x / 2.,
-            y: y + app.height() as f32 / 2. + radius - viewport_y / 2.,
-        };
+        app.draw_list.submit_object(
+            self.shape(),
+            &self.game_object,
+            self.layer,
+            self.stroke_size,
+        );
+    }
+
+    /// Add line as it is now to the App's static geometry, to draw it every frame without calling draw,
+    /// for level geometry that does not move
+    pub fn add_static(&self, app: &mut App) -> StaticShapeId {
+        return app.static_geometry.add(self.shape(), self.layer);
+    }
+
+    /// Return shape of line in world coordinates
+    pub fn shape(&self) -> Shape {
+        let radius = self.game_object.transform.radius * self.game_object.transform.scale.y;
+        let x = self.game_object.transform.position.x;
+        let y = self.game_object.transform.position.y;
+
+        let vertex_0 = Vector2 { x, y: y - radius };
+        let vertex_1 = Vector2 { x, y: y + radius };
 
         let center = Vector2 {
             x: (vertex_0.x + vertex_1.x) / 2.,
             y: (vertex_0.y + vertex_1.y) / 2.,
         };
 
-        app.shapes.push(
-            Shape::line([vertex_0.x, vertex_0.y], [vertex_1.x, vertex_1.y])
-                .stroke(
-                    self.stroke_size,
-                    Rgba::new(
-                        self.stroke_color.r,
-                        self.stroke_color.g,
-                        self.stroke_color.b,
-                        self.stroke_color.a,
-                    ),
-                )
-                .fill(Fill::solid(Rgba::new(
-                    self.fill_color.r,
-                    self.fill_color.g,
-                    self.fill_color.b,
-                    self.fill_color.a,
-                )))
-                .rotation(
-                    self.game_object.transform.rotation,
-                    Point2::new(center.x, center.y),
-                )
-                .zdepth(ZDepth::from(self.layer as i32 as f32 / 10.)),
-        );
+        return Shape::line([vertex_0.x, vertex_0.y], [vertex_1.x, vertex_1.y])
+            .stroke(
+                self.stroke_size,
+                Rgba::new(
+                    self.stroke_color.r,
+                    self.stroke_color.g,
+                    self.stroke_color.b,
+                    self.stroke_color.a,
+                ),
+            )
+            .fill(Fill::solid(Rgba::new(
+                self.fill_color.r,
+                self.fill_color.g,
+                self.fill_color.b,
+                self.fill_color.a,
+            )))
+            .rotation(
+                self.game_object.transform.rotation,
+                Point2::new(center.x, center.y),
+            );
     }
 }
@@ -3,4 +3,5 @@ pub mod game_object;
 pub mod game_view;
 pub mod line;
 pub mod rectangle;
+pub mod registry;
 pub mod text;
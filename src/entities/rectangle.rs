@@ -1,12 +1,12 @@
 use crate::app::App;
 use crate::entities::game_object::GameObject;
-use crate::entities::game_object::RenderLayer;
+use crate::rendering::layers::RenderLayer;
+use crate::rendering::static_geometry::StaticShapeId;
 use crate::types::color::Color;
 use crate::types::vector2::Vector2;
 
 use rgx::core::*;
 use rgx::kit::shape2d::{Fill, Shape};
-use rgx::kit::ZDepth;
 use rgx::math::Point2;
 
 /// A rectangle 🤷‍♂️
@@ -17,9 +17,6 @@ pub struct Rectangle {
     pub stroke_size: f32,
     pub stroke_color: Color,
     pub layer: RenderLayer,
-
-    index: usize,
-    is_init: bool,
 }
 
 impl Copy for Rectangle {}
@@ -39,66 +36,62 @@ impl Rectangle {
             fill_color: Color::white(),
             stroke_size: 0.,
             stroke_color: Color::white(),
-            layer: RenderLayer::Layer1,
-
-            index: 0,
-            is_init: false,
+            layer: RenderLayer::new(),
         };
     }
 
     /// Render rectangle on screen
     pub fn draw(&mut self, app: &mut App) {
-        if self.is_init {
-            app.shapes.remove(self.index);
-        } else {
-            self.is_init = true;
+        // Hidden and destroyed objects are not drawn
+        if !self.game_object.is_active(app) {
+            return;
         }
 
-        let x = self.game_object.transform.position.x;
-        let y = self.game_object.transform.position.y;
-
-        let width = self.game_object.transform.size.x;
-        let height = self.game_object.transform.size.y;
-
-        let viewport_x = if self.game_object.is_parallax {
-            0.
-        } else {
-            app.game_view.game_object.transform.position.x
-        };
-        let viewport_y = if self.game_object.is_parallax {
-            0.
-        } else {
-            app.game_view.game_object.transform.position.y
-        };
+        let width = self.game_object.transform.scaled_size().x;
+        let height = self.game_object.transform.scaled_size().y;
 
         // Bound object in game view
         if self.game_object.is_bounded {
-            let max_x = app.width() as f32 / 2. - width / 2. + viewport_x / 2.;
-            let min_x = -(app.width() as f32) / 2. + width / 2. + viewport_x / 2.;
-            if x > max_x {
-                self.game_object.transform.position.x = max_x;
-            } else if x < min_x {
-                self.game_object.transform.position.x = min_x;
-            }
-
-            let max_y = app.height() as f32 / 2. - height / 2. + viewport_y / 2.;
-            let min_y = -(app.height() as f32) / 2. + height / 2. + viewport_y / 2.;
-            if y > max_y {
-                self.game_object.transform.position.y = max_y;
-            } else if y < min_y {
-                self.game_object.transform.position.y = min_y;
-            }
+            self.game_object.keep_in_view(
+                &app.game_view,
+                Vector2 {
+                    x: width / 2.,
+                    y: height / 2.,
+                },
+                app.layers.parallax(self.layer),
+            );
         }
 
+        app.draw_list.submit_object(
+            self.shape(),
+            &self.game_object,
+            self.layer,
+            self.stroke_size,
+        );
+    }
+
+    /// Add rectangle as it is now to the App's static geometry, to draw it every frame without calling draw,
+    /// for level geometry that does not move
+    pub fn add_static(&self, app: &mut App) -> StaticShapeId {
+        return app.static_geometry.add(self.shape(), self.layer);
+    }
+
+    /// Return shape of rectangle in world coordinates
+    pub fn shape(&self) -> Shape {
+        let width = self.game_object.transform.scaled_size().x;
+        let height = self.game_object.transform.scaled_size().y;
+        let x = self.game_object.transform.position.x;
+        let y = self.game_object.transform.position.y;
+
         // Vertex 0: Top left
         let vertex_0 = Vector2 {
-            x: x + app.width() as f32 / 2. - width / 2. - viewport_x / 2.,
-            y: y + app.height() as f32 / 2. - height / 2. - viewport_y / 2.,
+            x: x - width / 2.,
+            y: y - height / 2.,
         };
         // Vertex 1: Bottom right
         let vertex_1 = Vector2 {
-            x: x + app.width() as f32 / 2. + width / 2. - viewport_x / 2.,
-            y: y + app.height() as f32 / 2. + height / 2. - viewport_y / 2.,
+            x: x + width / 2.,
+            y: y + height / 2.,
         };
 
         let center = Vector2 {
@@ -106,28 +99,25 @@ impl Rectangle {
             y: (vertex_0.y + vertex_1.y) / 2.,
         };
 
-        app.shapes.push(
-            Shape::rect([vertex_0.x, vertex_0.y], [vertex_1.x, vertex_1.y])
-                .stroke(
-                    self.stroke_size,
-                    Rgba::new(
-                        self.stroke_color.r,
-                        self.stroke_color.g,
-                        self.stroke_color.b,
-                        self.stroke_color.a,
-                    ),
-                )
-                .fill(Fill::solid(Rgba::new(
-                    self.fill_color.r,
-                    self.fill_color.g,
-                    self.fill_color.b,
-                    self.fill_color.a,
-                )))
-                .rotation(
-                    self.game_object.transform.rotation,
-                    Point2::new(center.x, center.y),
-                )
-                .zdepth(ZDepth::from(self.layer as i32 as f32 / 10.)),
-        );
+        return Shape::rect([vertex_0.x, vertex_0.y], [vertex_1.x, vertex_1.y])
+            .stroke(
+                self.stroke_size,
+                Rgba::new(
+                    self.stroke_color.r,
+                    self.stroke_color.g,
+                    self.stroke_color.b,
+                    self.stroke_color.a,
+                ),
+            )
+            .fill(Fill::solid(Rgba::new(
+                self.fill_color.r,
+                self.fill_color.g,
+                self.fill_color.b,
+                self.fill_color.a,
+            )))
+            .rotation(
+                self.game_object.transform.rotation,
+                Point2::new(center.x, center.y),
+            );
     }
 }
@@ -0,0 +1,163 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// Handle of a game object registered with the App.
+/// A handle stays invalid after its object is destroyed, even if the slot is reused by a new object
+pub struct ObjectId {
+    index: u32,
+    generation: u32,
+}
+
+impl fmt::Display for ObjectId {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}v{}", self.index, self.generation)
+    }
+}
+
+impl fmt::Debug for ObjectId {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "ObjectId({}v{})", self.index, self.generation)
+    }
+}
+
+impl Copy for ObjectId {}
+
+impl Clone for ObjectId {
+    fn clone(&self) -> ObjectId {
+        *self
+    }
+}
+
+impl PartialEq for ObjectId {
+    fn eq(&self, other: &ObjectId) -> bool {
+        return self.index == other.index && self.generation == other.generation;
+    }
+}
+
+impl Eq for ObjectId {}
+
+impl Hash for ObjectId {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+        self.generation.hash(state);
+    }
+}
+
+impl ObjectId {
+    /// Return slot index of object, slots are reused after objects are destroyed
+    pub fn index(&self) -> u32 {
+        return self.index;
+    }
+    /// Return how many times the slot of this object has been reused
+    pub fn generation(&self) -> u32 {
+        return self.generation;
+    }
+}
+
+/// Keeps track of which game objects are alive, and runs callbacks when they are spawned or destroyed.
+/// Destroyed objects stop being alive immediately and are cleaned up at the end of the frame
+pub struct ObjectRegistry {
+    generations: Vec<u32>,
+    is_alive: Vec<bool>,
+    free_indices: Vec<u32>,
+    pending_destroy: Vec<ObjectId>,
+    on_spawn: Vec<Box<dyn FnMut(ObjectId)>>,
+    on_destroy: Vec<Box<dyn FnMut(ObjectId)>>,
+}
+
+impl ObjectRegistry {
+    /// Default constructor to initialize an empty ObjectRegistry
+    pub fn new() -> Self {
+        return Self {
+            generations: Vec::new(),
+            is_alive: Vec::new(),
+            free_indices: Vec::new(),
+            pending_destroy: Vec::new(),
+            on_spawn: Vec::new(),
+            on_destroy: Vec::new(),
+        };
+    }
+
+    /// Register a new object and return its id
+    pub fn spawn(&mut self) -> ObjectId {
+        let id = match self.free_indices.pop() {
+            Some(index) => {
+                self.is_alive[index as usize] = true;
+                ObjectId {
+                    index,
+                    generation: self.generations[index as usize],
+                }
+            }
+            None => {
+                self.generations.push(0);
+                self.is_alive.push(true);
+                ObjectId {
+                    index: self.generations.len() as u32 - 1,
+                    generation: 0,
+                }
+            }
+        };
+
+        for callback in self.on_spawn.iter_mut() {
+            callback(id);
+        }
+        return id;
+    }
+
+    /// Destroy an object, it is removed from rendering at the end of the current frame
+    pub fn destroy(&mut self, id: ObjectId) {
+        if !self.is_alive(id) {
+            return;
+        }
+        self.is_alive[id.index as usize] = false;
+        self.pending_destroy.push(id);
+    }
+
+    /// Return whether an object is registered and not destroyed
+    pub fn is_alive(&self, id: ObjectId) -> bool {
+        let index = id.index as usize;
+        return index < self.generations.len()
+            && self.generations[index] == id.generation
+            && self.is_alive[index];
+    }
+
+    /// Return number of objects alive
+    pub fn len(&self) -> usize {
+        return self.is_alive.iter().filter(|is_alive| **is_alive).count();
+    }
+    /// Return whether no object is alive
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    /// Add a callback that is run whenever an object is spawned
+    pub fn on_spawn<C>(&mut self, callback: C)
+    where
+        C: FnMut(ObjectId) + 'static,
+    {
+        self.on_spawn.push(Box::new(callback));
+    }
+    /// Add a callback that is run at the end of the frame an object is destroyed
+    pub fn on_destroy<C>(&mut self, callback: C)
+    where
+        C: FnMut(ObjectId) + 'static,
+    {
+        self.on_destroy.push(Box::new(callback));
+    }
+
+    /// Free objects destroyed this frame and return their ids. Only meant to be called internally within sidekick
+    pub fn flush(&mut self) -> Vec<ObjectId> {
+        let destroyed: Vec<ObjectId> = self.pending_destroy.drain(..).collect();
+
+        for id in destroyed.iter() {
+            // Bump generation so stale ids of this slot are never alive again
+            self.generations[id.index as usize] += 1;
+            self.free_indices.push(id.index);
+
+            for callback in self.on_destroy.iter_mut() {
+                callback(*id);
+            }
+        }
+        return destroyed;
+    }
+}
@@ -0,0 +1,557 @@
+use crate::input::gamepad::GamepadAxis;
+use crate::input::key_code::KeyCode;
+use crate::input::{GamepadButton, Key, MouseButton};
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A physical input that can be bound to actions and axes
+pub enum InputSource {
+    Key(Key),
+    KeyCode(KeyCode),
+    MouseButton(MouseButton),
+    /// A button on any connected gamepad
+    GamepadButton(GamepadButton),
+    /// Positive half of an axis on any connected gamepad, e.g. stick right or up
+    GamepadAxisPositive(GamepadAxis),
+    /// Negative half of an axis on any connected gamepad, e.g. stick left or down
+    GamepadAxisNegative(GamepadAxis),
+}
+
+impl Copy for InputSource {}
+
+impl Clone for InputSource {
+    fn clone(&self) -> InputSource {
+        *self
+    }
+}
+
+impl PartialEq for InputSource {
+    fn eq(&self, other: &InputSource) -> bool {
+        return match (self, other) {
+            (InputSource::Key(a), InputSource::Key(b)) => a == b,
+            (InputSource::KeyCode(a), InputSource::KeyCode(b)) => a == b,
+            (InputSource::MouseButton(a), InputSource::MouseButton(b)) => a == b,
+            (InputSource::GamepadButton(a), InputSource::GamepadButton(b)) => a == b,
+            (InputSource::GamepadAxisPositive(a), InputSource::GamepadAxisPositive(b)) => a == b,
+            (InputSource::GamepadAxisNegative(a), InputSource::GamepadAxisNegative(b)) => a == b,
+            _ => false,
+        };
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Key(key) => write!(formatter, "Key:{}", key.name()),
+            InputSource::KeyCode(key_code) => write!(formatter, "Code:{}", key_code.name()),
+            InputSource::MouseButton(mouse_button) => {
+                write!(formatter, "Mouse:{}", mouse_button.name())
+            }
+            InputSource::GamepadButton(button) => write!(formatter, "Gamepad:{}", button.name()),
+            InputSource::GamepadAxisPositive(axis) => {
+                write!(formatter, "GamepadAxis:+{}", axis.name())
+            }
+            InputSource::GamepadAxisNegative(axis) => {
+                write!(formatter, "GamepadAxis:-{}", axis.name())
+            }
+        }
+    }
+}
+
+impl InputSource {
+    /// Return input with a name such as "Key:Space", "Code:Z" or "Mouse:Left", as used in binding files
+    pub fn from_name(name: &str) -> Option<InputSource> {
+        let (device, input) = name.split_once(':')?;
+        return match device {
+            "Key" => Key::from_name(input).map(InputSource::Key),
+            "Code" => KeyCode::from_name(input).map(InputSource::KeyCode),
+            "Mouse" => MouseButton::from_name(input).map(InputSource::MouseButton),
+            "Gamepad" => GamepadButton::from_name(input).map(InputSource::GamepadButton),
+            "GamepadAxis" => match input.split_at(input.len().min(1)) {
+                ("+", axis) => GamepadAxis::from_name(axis).map(InputSource::GamepadAxisPositive),
+                ("-", axis) => GamepadAxis::from_name(axis).map(InputSource::GamepadAxisNegative),
+                _ => None,
+            },
+            _ => None,
+        };
+    }
+}
+
+/// Actions and axes that an input is already bound to, returned when rebinding would bind it twice
+pub struct BindingConflict {
+    pub source: InputSource,
+    pub actions: Vec<String>,
+    pub axes: Vec<String>,
+}
+
+impl fmt::Display for BindingConflict {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = self
+            .actions
+            .iter()
+            .chain(self.axes.iter())
+            .map(|name| name.as_str())
+            .collect();
+        write!(
+            formatter,
+            "{} is already bound to {}",
+            self.source,
+            names.join(", ")
+        )
+    }
+}
+
+/// A pair of inputs driving an axis, negative input gives -1 and positive input gives 1
+pub struct AxisBinding {
+    pub negative: InputSource,
+    pub positive: InputSource,
+}
+
+impl Copy for AxisBinding {}
+
+impl Clone for AxisBinding {
+    fn clone(&self) -> AxisBinding {
+        *self
+    }
+}
+
+/// Named actions (e.g. "jump") and axes (e.g. "move_x") bound to physical inputs.
+/// An action is down while any of its inputs is down, an axis sums all of its bindings
+pub struct ActionMap {
+    actions: HashMap<String, Vec<InputSource>>,
+    axes: HashMap<String, Vec<AxisBinding>>,
+}
+
+impl ActionMap {
+    /// Default constructor to initialize an empty action map
+    pub fn new() -> Self {
+        return Self {
+            actions: HashMap::new(),
+            axes: HashMap::new(),
+        };
+    }
+
+    /// Bind an input to an action, an action can have any number of inputs
+    pub fn bind_action(&mut self, action: &str, source: InputSource) {
+        let sources = self.actions.entry(String::from(action)).or_default();
+        if !sources.contains(&source) {
+            sources.push(source);
+        }
+    }
+    /// Unbind an input from an action
+    pub fn unbind_action(&mut self, action: &str, source: InputSource) {
+        if let Some(sources) = self.actions.get_mut(action) {
+            sources.retain(|bound| *bound != source);
+        }
+    }
+    /// Remove an action and all of its bindings
+    pub fn remove_action(&mut self, action: &str) {
+        self.actions.remove(action);
+    }
+    /// Return inputs bound to an action
+    pub fn action_bindings(&self, action: &str) -> &[InputSource] {
+        return match self.actions.get(action) {
+            Some(sources) => sources,
+            None => &[],
+        };
+    }
+    /// Return names of every action
+    pub fn actions(&self) -> Vec<&str> {
+        return self.actions.keys().map(|action| action.as_str()).collect();
+    }
+
+    /// Bind a pair of negative and positive inputs to an axis, an axis can have any number of pairs
+    pub fn bind_axis(&mut self, axis: &str, negative: InputSource, positive: InputSource) {
+        self.axes
+            .entry(String::from(axis))
+            .or_default()
+            .push(AxisBinding { negative, positive });
+    }
+    /// Remove an axis and all of its bindings
+    pub fn remove_axis(&mut self, axis: &str) {
+        self.axes.remove(axis);
+    }
+    /// Return input pairs bound to an axis
+    pub fn axis_bindings(&self, axis: &str) -> &[AxisBinding] {
+        return match self.axes.get(axis) {
+            Some(bindings) => bindings,
+            None => &[],
+        };
+    }
+    /// Return names of every axis
+    pub fn axes(&self) -> Vec<&str> {
+        return self.axes.keys().map(|axis| axis.as_str()).collect();
+    }
+
+    /// Return actions and axes an input is bound to, or None if it is not bound
+    pub fn find_conflict(&self, source: InputSource) -> Option<BindingConflict> {
+        let mut actions: Vec<String> = self
+            .actions
+            .iter()
+            .filter(|(_, sources)| sources.contains(&source))
+            .map(|(action, _)| action.clone())
+            .collect();
+        let mut axes: Vec<String> = self
+            .axes
+            .iter()
+            .filter(|(_, bindings)| {
+                bindings
+                    .iter()
+                    .any(|binding| binding.negative == source || binding.positive == source)
+            })
+            .map(|(axis, _)| axis.clone())
+            .collect();
+
+        if actions.is_empty() && axes.is_empty() {
+            return None;
+        }
+        actions.sort();
+        axes.sort();
+        return Some(BindingConflict {
+            source,
+            actions,
+            axes,
+        });
+    }
+
+    /// Replace an input bound to an action with another input.
+    /// Nothing is changed if the new input is already bound to another action or axis
+    pub fn rebind_action(
+        &mut self,
+        action: &str,
+        old: InputSource,
+        new: InputSource,
+    ) -> Result<(), BindingConflict> {
+        if let Some(mut conflict) = self.find_conflict(new) {
+            conflict.actions.retain(|name| name != action);
+            if !conflict.actions.is_empty() || !conflict.axes.is_empty() {
+                return Err(conflict);
+            }
+        }
+
+        let sources = self.actions.entry(String::from(action)).or_default();
+        if sources.contains(&new) {
+            sources.retain(|source| *source != old || old == new);
+            return Ok(());
+        }
+        match sources.iter().position(|source| *source == old) {
+            Some(index) => sources[index] = new,
+            None => sources.push(new),
+        }
+        return Ok(());
+    }
+    /// Replace an input bound to an axis with another input, on either side of every pair.
+    /// Nothing is changed if the new input is already bound to another action or axis
+    pub fn rebind_axis(
+        &mut self,
+        axis: &str,
+        old: InputSource,
+        new: InputSource,
+    ) -> Result<(), BindingConflict> {
+        if let Some(mut conflict) = self.find_conflict(new) {
+            conflict.axes.retain(|name| name != axis);
+            if !conflict.actions.is_empty() || !conflict.axes.is_empty() {
+                return Err(conflict);
+            }
+        }
+
+        if let Some(bindings) = self.axes.get_mut(axis) {
+            for binding in bindings.iter_mut() {
+                if binding.negative == old {
+                    binding.negative = new;
+                }
+                if binding.positive == old {
+                    binding.positive = new;
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    /// Return bindings as a TOML document with an [actions] and an [axes] table
+    pub fn to_toml(&self) -> String {
+        let mut actions = toml::value::Table::new();
+        for (action, sources) in self.actions.iter() {
+            let sources = sources
+                .iter()
+                .map(|source| toml::Value::String(source.to_string()))
+                .collect();
+            actions.insert(action.clone(), toml::Value::Array(sources));
+        }
+
+        let mut axes = toml::value::Table::new();
+        for (axis, bindings) in self.axes.iter() {
+            let bindings = bindings
+                .iter()
+                .map(|binding| {
+                    toml::Value::Array(vec![
+                        toml::Value::String(binding.negative.to_string()),
+                        toml::Value::String(binding.positive.to_string()),
+                    ])
+                })
+                .collect();
+            axes.insert(axis.clone(), toml::Value::Array(bindings));
+        }
+
+        let mut document = toml::value::Table::new();
+        document.insert(String::from("actions"), toml::Value::Table(actions));
+        document.insert(String::from("axes"), toml::Value::Table(axes));
+        return toml::Value::Table(document).to_string();
+    }
+
+    /// Read bindings from a TOML document written by to_toml.
+    /// Actions and axes in the document replace existing ones, others are left untouched
+    pub fn load_toml(&mut self, document: &str) -> io::Result<()> {
+        let document: toml::Value = document.parse().map_err(invalid_data)?;
+
+        let mut actions: Vec<(String, Vec<InputSource>)> = Vec::new();
+        if let Some(table) = document.get("actions") {
+            for (action, sources) in table
+                .as_table()
+                .ok_or_else(|| invalid_data("actions: expected a table"))?
+            {
+                let sources = parse_array(sources, action)?
+                    .iter()
+                    .map(|source| parse_source(source, action))
+                    .collect::<io::Result<Vec<InputSource>>>()?;
+                actions.push((action.clone(), sources));
+            }
+        }
+
+        let mut axes: Vec<(String, Vec<AxisBinding>)> = Vec::new();
+        if let Some(table) = document.get("axes") {
+            for (axis, bindings) in table
+                .as_table()
+                .ok_or_else(|| invalid_data("axes: expected a table"))?
+            {
+                let mut pairs = Vec::new();
+                for pair in parse_array(bindings, axis)? {
+                    let pair = parse_array(pair, axis)?;
+                    if pair.len() != 2 {
+                        return Err(invalid_data(format!(
+                            "{}: axis binding must be a [negative, positive] pair",
+                            axis
+                        )));
+                    }
+                    pairs.push(AxisBinding {
+                        negative: parse_source(&pair[0], axis)?,
+                        positive: parse_source(&pair[1], axis)?,
+                    });
+                }
+                axes.push((axis.clone(), pairs));
+            }
+        }
+
+        // Only apply once the whole document is valid
+        self.actions.extend(actions);
+        self.axes.extend(axes);
+        return Ok(());
+    }
+
+    /// Save bindings to a TOML file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        if let Some(parent) = path.as_ref().parent() {
+            fs::create_dir_all(parent)?;
+        }
+        return fs::write(path, self.to_toml());
+    }
+    /// Load bindings from a TOML file, replacing actions and axes defined in the file
+    pub fn load<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        return self.load_toml(&fs::read_to_string(path)?);
+    }
+}
+
+/// Return a TOML value as an array
+fn parse_array<'a>(value: &'a toml::Value, name: &str) -> io::Result<&'a Vec<toml::Value>> {
+    return value
+        .as_array()
+        .ok_or_else(|| invalid_data(format!("{}: expected an array", name)));
+}
+
+/// Return a TOML value as an input
+fn parse_source(value: &toml::Value, name: &str) -> io::Result<InputSource> {
+    return value
+        .as_str()
+        .and_then(InputSource::from_name)
+        .ok_or_else(|| invalid_data(format!("{}: unknown input {}", name, value)));
+}
+
+/// Convert any displayable error to an invalid data IO error
+fn invalid_data<E: fmt::Display>(err: E) -> io::Error {
+    return io::Error::new(io::ErrorKind::InvalidData, err.to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Return one input of every kind
+    fn every_source() -> Vec<InputSource> {
+        return vec![
+            InputSource::Key(Key::Space),
+            InputSource::KeyCode(KeyCode::Z),
+            InputSource::MouseButton(MouseButton::Left),
+            InputSource::GamepadButton(GamepadButton::South),
+            InputSource::GamepadAxisPositive(GamepadAxis::LeftStickX),
+            InputSource::GamepadAxisNegative(GamepadAxis::RightTrigger),
+        ];
+    }
+
+    #[test]
+    fn input_names_round_trip() {
+        for source in every_source() {
+            let name = source.to_string();
+            assert!(InputSource::from_name(&name) == Some(source), "{}", name);
+        }
+        assert!(InputSource::from_name("Space").is_none());
+        assert!(InputSource::from_name("Key:Nothing").is_none());
+        assert!(InputSource::from_name("GamepadAxis:LeftStickX").is_none());
+        assert!(InputSource::from_name("GamepadAxis:").is_none());
+    }
+
+    #[test]
+    fn toml_round_trip_keeps_every_binding() {
+        let mut action_map = ActionMap::new();
+        for source in every_source() {
+            action_map.bind_action("everything", source);
+        }
+        action_map.bind_action("jump", InputSource::Key(Key::Space));
+        action_map.bind_axis(
+            "move_x",
+            InputSource::KeyCode(KeyCode::A),
+            InputSource::KeyCode(KeyCode::D),
+        );
+        action_map.bind_axis(
+            "move_x",
+            InputSource::GamepadAxisNegative(GamepadAxis::LeftStickX),
+            InputSource::GamepadAxisPositive(GamepadAxis::LeftStickX),
+        );
+
+        let mut loaded = ActionMap::new();
+        loaded.load_toml(&action_map.to_toml()).unwrap();
+
+        let mut actions = loaded.actions();
+        actions.sort();
+        assert_eq!(actions, vec!["everything", "jump"]);
+        assert!(loaded.action_bindings("everything") == &every_source()[..]);
+        assert!(loaded.action_bindings("jump") == &[InputSource::Key(Key::Space)][..]);
+
+        assert_eq!(loaded.axes(), vec!["move_x"]);
+        let bindings = loaded.axis_bindings("move_x");
+        assert_eq!(bindings.len(), 2);
+        assert!(bindings[0].negative == InputSource::KeyCode(KeyCode::A));
+        assert!(bindings[0].positive == InputSource::KeyCode(KeyCode::D));
+        assert!(bindings[1].negative == InputSource::GamepadAxisNegative(GamepadAxis::LeftStickX));
+        assert!(bindings[1].positive == InputSource::GamepadAxisPositive(GamepadAxis::LeftStickX));
+    }
+
+    #[test]
+    fn malformed_documents_are_rejected() {
+        let mut action_map = ActionMap::new();
+        action_map.bind_action("jump", InputSource::Key(Key::Space));
+
+        let documents = [
+            "actions = [",
+            "actions = 1",
+            "[actions]\njump = \"Key:Space\"",
+            "[actions]\njump = [\"Key:Nothing\"]",
+            "[actions]\njump = [1]",
+            "[axes]\nmove_x = [[\"Code:A\"]]",
+            "[axes]\nmove_x = [\"Code:A\", \"Code:D\"]",
+            // Valid actions are not applied when axes are invalid
+            "[actions]\njump = [\"Code:W\"]\n[axes]\nmove_x = [[\"Code:A\", \"Code:Nothing\"]]",
+        ];
+        for document in documents.iter() {
+            let error = action_map.load_toml(document).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData, "{}", document);
+        }
+        assert_eq!(action_map.actions(), vec!["jump"]);
+        assert!(action_map.action_bindings("jump") == &[InputSource::Key(Key::Space)][..]);
+        assert!(action_map.axes().is_empty());
+    }
+
+    #[test]
+    fn conflicts_list_every_action_and_axis() {
+        let space = InputSource::Key(Key::Space);
+        let mut action_map = ActionMap::new();
+        assert!(action_map.find_conflict(space).is_none());
+
+        action_map.bind_action("jump", space);
+        action_map.bind_action("confirm", space);
+        action_map.bind_axis("move_y", InputSource::KeyCode(KeyCode::S), space);
+
+        let conflict = action_map.find_conflict(space).unwrap();
+        assert!(conflict.source == space);
+        assert_eq!(conflict.actions, vec!["confirm", "jump"]);
+        assert_eq!(conflict.axes, vec!["move_y"]);
+        assert_eq!(
+            conflict.to_string(),
+            "Key:Space is already bound to confirm, jump, move_y"
+        );
+        assert!(action_map
+            .find_conflict(InputSource::KeyCode(KeyCode::Space))
+            .is_none());
+    }
+
+    #[test]
+    fn rebind_action_replaces_input_unless_it_conflicts() {
+        let (a, b, c) = (
+            InputSource::KeyCode(KeyCode::A),
+            InputSource::KeyCode(KeyCode::B),
+            InputSource::KeyCode(KeyCode::C),
+        );
+        let mut action_map = ActionMap::new();
+        action_map.bind_action("jump", a);
+        action_map.bind_action("jump", b);
+        action_map.bind_action("fire", c);
+
+        // Replaced in place
+        assert!(action_map
+            .rebind_action("jump", a, InputSource::Key(Key::Space))
+            .is_ok());
+        assert!(action_map.action_bindings("jump") == &[InputSource::Key(Key::Space), b][..]);
+
+        // Rebinding to an input of the same action only removes the old input
+        assert!(action_map
+            .rebind_action("jump", InputSource::Key(Key::Space), b)
+            .is_ok());
+        assert!(action_map.action_bindings("jump") == &[b][..]);
+
+        // Inputs of other actions are not stolen
+        let conflict = action_map.rebind_action("jump", b, c).unwrap_err();
+        assert_eq!(conflict.actions, vec!["fire"]);
+        assert!(action_map.action_bindings("jump") == &[b][..]);
+        assert!(action_map.action_bindings("fire") == &[c][..]);
+    }
+
+    #[test]
+    fn rebind_axis_replaces_input_unless_it_conflicts() {
+        let (a, d) = (
+            InputSource::KeyCode(KeyCode::A),
+            InputSource::KeyCode(KeyCode::D),
+        );
+        let stick_left = InputSource::GamepadAxisNegative(GamepadAxis::LeftStickX);
+        let mut action_map = ActionMap::new();
+        action_map.bind_axis("move_x", a, d);
+        action_map.bind_axis("move_x", stick_left, a);
+        action_map.bind_action("jump", InputSource::Key(Key::Space));
+
+        // Replaced on both sides of every pair
+        let left = InputSource::KeyCode(KeyCode::Left);
+        assert!(action_map.rebind_axis("move_x", a, left).is_ok());
+        let bindings = action_map.axis_bindings("move_x");
+        assert!(bindings[0].negative == left && bindings[0].positive == d);
+        assert!(bindings[1].negative == stick_left && bindings[1].positive == left);
+
+        let conflict = action_map
+            .rebind_axis("move_x", d, InputSource::Key(Key::Space))
+            .unwrap_err();
+        assert_eq!(conflict.actions, vec!["jump"]);
+        assert!(conflict.axes.is_empty());
+        assert!(action_map.axis_bindings("move_x")[0].positive == d);
+    }
+}
@@ -0,0 +1,451 @@
+use crate::input::GamepadButton;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+/// Id of a connected gamepad, ids are not reused after a gamepad disconnects
+pub struct GamepadId(pub u32);
+
+impl fmt::Display for GamepadId {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "Gamepad {}", self.0)
+    }
+}
+
+impl Copy for GamepadId {}
+
+impl Clone for GamepadId {
+    fn clone(&self) -> GamepadId {
+        *self
+    }
+}
+
+impl PartialEq for GamepadId {
+    fn eq(&self, other: &GamepadId) -> bool {
+        return self.0 == other.0;
+    }
+}
+
+/// Analog gamepad axes. Sticks range from -1 to 1 with up and right positive, triggers from 0 to 1
+pub enum GamepadAxis {
+    LeftStickX = 0,
+    LeftStickY = 1,
+    RightStickX = 2,
+    RightStickY = 3,
+    LeftTrigger = 4,
+    RightTrigger = 5,
+}
+
+impl Copy for GamepadAxis {}
+
+impl Clone for GamepadAxis {
+    fn clone(&self) -> GamepadAxis {
+        *self
+    }
+}
+
+impl PartialEq for GamepadAxis {
+    fn eq(&self, other: &GamepadAxis) -> bool {
+        return *self as u32 == *other as u32;
+    }
+}
+
+// Every gamepad axis, used to look axes up by name
+const GAMEPAD_AXES: [GamepadAxis; 6] = [
+    GamepadAxis::LeftStickX,
+    GamepadAxis::LeftStickY,
+    GamepadAxis::RightStickX,
+    GamepadAxis::RightStickY,
+    GamepadAxis::LeftTrigger,
+    GamepadAxis::RightTrigger,
+];
+
+impl GamepadAxis {
+    /// Return every gamepad axis
+    pub fn all() -> &'static [GamepadAxis] {
+        return &GAMEPAD_AXES;
+    }
+
+    /// Return name of gamepad axis, as used in binding files
+    pub fn name(&self) -> &'static str {
+        return match self {
+            GamepadAxis::LeftStickX => "LeftStickX",
+            GamepadAxis::LeftStickY => "LeftStickY",
+            GamepadAxis::RightStickX => "RightStickX",
+            GamepadAxis::RightStickY => "RightStickY",
+            GamepadAxis::LeftTrigger => "LeftTrigger",
+            GamepadAxis::RightTrigger => "RightTrigger",
+        };
+    }
+    /// Return gamepad axis with a name
+    pub fn from_name(name: &str) -> Option<GamepadAxis> {
+        return GAMEPAD_AXES
+            .iter()
+            .find(|axis| axis.name() == name)
+            .copied();
+    }
+
+    /// Return whether axis is a trigger
+    pub fn is_trigger(&self) -> bool {
+        return matches!(self, GamepadAxis::LeftTrigger | GamepadAxis::RightTrigger);
+    }
+}
+
+/// Gamepad sticks
+pub enum GamepadStick {
+    Left,
+    Right,
+}
+
+impl Copy for GamepadStick {}
+
+impl Clone for GamepadStick {
+    fn clone(&self) -> GamepadStick {
+        *self
+    }
+}
+
+/// Events reported by a gamepad backend
+pub enum GamepadEvent {
+    Connected(GamepadId),
+    Disconnected(GamepadId),
+    ButtonPressed(GamepadId, GamepadButton),
+    ButtonReleased(GamepadId, GamepadButton),
+    /// Raw axis value before dead zones are applied
+    AxisChanged(GamepadId, GamepadAxis, f32),
+}
+
+impl Copy for GamepadEvent {}
+
+impl Clone for GamepadEvent {
+    fn clone(&self) -> GamepadEvent {
+        *self
+    }
+}
+
+/// A source of gamepad events, e.g. a platform gamepad library or a virtual gamepad for tests
+pub trait GamepadBackend {
+    /// Return events that happened since the last poll, in order
+    fn poll_events(&mut self) -> Vec<GamepadEvent>;
+    /// Rumble a gamepad with strong and weak motor intensities between 0 and 1 for a duration in seconds.
+    /// Return whether the gamepad supports rumble
+    fn rumble(&mut self, id: GamepadId, strong: f32, weak: f32, duration: f32) -> bool;
+    /// Return name of a connected gamepad
+    fn name(&self, _id: GamepadId) -> Option<String> {
+        return None;
+    }
+}
+
+/// A rumble request received by a virtual gamepad
+pub struct Rumble {
+    pub strong: f32,
+    pub weak: f32,
+    pub duration: f32,
+}
+
+impl Copy for Rumble {}
+
+impl Clone for Rumble {
+    fn clone(&self) -> Rumble {
+        *self
+    }
+}
+
+/// State shared between clones of a virtual gamepad backend
+struct VirtualGamepads {
+    next_id: u32,
+    names: HashMap<u32, String>,
+    events: Vec<GamepadEvent>,
+    rumbles: HashMap<u32, Rumble>,
+}
+
+/// Gamepad backend driven from code, to test gamepad input without hardware.
+/// Clones share the same gamepads, so keep a clone to drive the backend given to Input
+pub struct VirtualGamepadBackend {
+    gamepads: Rc<RefCell<VirtualGamepads>>,
+}
+
+impl Clone for VirtualGamepadBackend {
+    fn clone(&self) -> VirtualGamepadBackend {
+        return VirtualGamepadBackend {
+            gamepads: Rc::clone(&self.gamepads),
+        };
+    }
+}
+
+impl VirtualGamepadBackend {
+    /// Default constructor to initialize a virtual backend without gamepads
+    pub fn new() -> Self {
+        return Self {
+            gamepads: Rc::new(RefCell::new(VirtualGamepads {
+                next_id: 0,
+                names: HashMap::new(),
+                events: Vec::new(),
+                rumbles: HashMap::new(),
+            })),
+        };
+    }
+
+    /// Connect a new virtual gamepad and return its id
+    pub fn connect(&self, name: &str) -> GamepadId {
+        let mut gamepads = self.gamepads.borrow_mut();
+        let id = GamepadId(gamepads.next_id);
+        gamepads.next_id += 1;
+        gamepads.names.insert(id.0, String::from(name));
+        gamepads.events.push(GamepadEvent::Connected(id));
+        return id;
+    }
+    /// Disconnect a virtual gamepad
+    pub fn disconnect(&self, id: GamepadId) {
+        let mut gamepads = self.gamepads.borrow_mut();
+        if gamepads.names.remove(&id.0).is_some() {
+            gamepads.rumbles.remove(&id.0);
+            gamepads.events.push(GamepadEvent::Disconnected(id));
+        }
+    }
+
+    /// Press a button of a virtual gamepad
+    pub fn press(&self, id: GamepadId, button: GamepadButton) {
+        self.push_event(id, GamepadEvent::ButtonPressed(id, button));
+    }
+    /// Release a button of a virtual gamepad
+    pub fn release(&self, id: GamepadId, button: GamepadButton) {
+        self.push_event(id, GamepadEvent::ButtonReleased(id, button));
+    }
+    /// Move an axis of a virtual gamepad
+    pub fn set_axis(&self, id: GamepadId, axis: GamepadAxis, value: f32) {
+        self.push_event(id, GamepadEvent::AxisChanged(id, axis, value));
+    }
+
+    /// Return last rumble requested on a virtual gamepad
+    pub fn last_rumble(&self, id: GamepadId) -> Option<Rumble> {
+        return self.gamepads.borrow().rumbles.get(&id.0).copied();
+    }
+
+    /// Queue an event if gamepad is connected
+    fn push_event(&self, id: GamepadId, event: GamepadEvent) {
+        let mut gamepads = self.gamepads.borrow_mut();
+        if gamepads.names.contains_key(&id.0) {
+            gamepads.events.push(event);
+        }
+    }
+}
+
+impl GamepadBackend for VirtualGamepadBackend {
+    fn poll_events(&mut self) -> Vec<GamepadEvent> {
+        return std::mem::take(&mut self.gamepads.borrow_mut().events);
+    }
+
+    fn rumble(&mut self, id: GamepadId, strong: f32, weak: f32, duration: f32) -> bool {
+        let mut gamepads = self.gamepads.borrow_mut();
+        if !gamepads.names.contains_key(&id.0) {
+            return false;
+        }
+        gamepads.rumbles.insert(
+            id.0,
+            Rumble {
+                strong,
+                weak,
+                duration,
+            },
+        );
+        return true;
+    }
+
+    fn name(&self, id: GamepadId) -> Option<String> {
+        return self.gamepads.borrow().names.get(&id.0).cloned();
+    }
+}
+
+/// Button and axis state of a connected gamepad
+pub(crate) struct GamepadState {
+    pub(crate) id: GamepadId,
+    pub(crate) buttons_down: Vec<GamepadButton>,
+    pub(crate) buttons_pressed: Vec<GamepadButton>,
+    pub(crate) buttons_released: Vec<GamepadButton>,
+    pub(crate) axes: [f32; 6],
+    pub(crate) previous_axes: [f32; 6],
+}
+
+impl GamepadState {
+    /// Default constructor to initialize state of a newly connected gamepad
+    pub(crate) fn new(id: GamepadId) -> Self {
+        return Self {
+            id,
+            buttons_down: Vec::new(),
+            buttons_pressed: Vec::new(),
+            buttons_released: Vec::new(),
+            axes: [0.; 6],
+            previous_axes: [0.; 6],
+        };
+    }
+}
+
+/// Apply a dead zone to an axis value, rescaling the rest of the range so values start at 0
+pub(crate) fn apply_dead_zone(value: f32, dead_zone: f32) -> f32 {
+    if value.abs() <= dead_zone {
+        return 0.;
+    }
+    let rescaled = (value.abs() - dead_zone) / (1. - dead_zone).max(f32::EPSILON);
+    return rescaled.min(1.) * value.signum();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::Input;
+    use crate::types::vector2::Vector2;
+
+    /// Return Input reading gamepads from a virtual backend, and a clone of the backend to drive it
+    fn virtual_input() -> (Input, VirtualGamepadBackend) {
+        let backend = VirtualGamepadBackend::new();
+        let mut input = Input::new();
+        input.set_gamepad_backend(backend.clone());
+        return (input, backend);
+    }
+
+    #[test]
+    fn connect_and_disconnect_events() {
+        let (mut input, backend) = virtual_input();
+        let id = backend.connect("Pad");
+
+        input.begin_frame(0.);
+        assert!(input.is_gamepad_connected(id));
+        assert_eq!(input.gamepad_name(id), Some(String::from("Pad")));
+        assert!(
+            matches!(input.gamepad_events(), [GamepadEvent::Connected(event_id)] if *event_id == id)
+        );
+        input.end_frame();
+
+        backend.disconnect(id);
+        input.begin_frame(0.);
+        assert!(!input.is_gamepad_connected(id));
+        assert_eq!(input.gamepad_name(id), None);
+        assert!(
+            matches!(input.gamepad_events(), [GamepadEvent::Disconnected(event_id)] if *event_id == id)
+        );
+        input.end_frame();
+
+        // Events of disconnected gamepads are dropped and ids are not reused
+        backend.press(id, GamepadButton::South);
+        let next = backend.connect("Pad");
+        input.begin_frame(0.);
+        assert!(next != id);
+        assert_eq!(input.gamepad_events().len(), 1);
+        assert!(!input.is_gamepad_button_down(id, GamepadButton::South));
+    }
+
+    #[test]
+    fn two_gamepads_at_once() {
+        let (mut input, backend) = virtual_input();
+        let first = backend.connect("First");
+        let second = backend.connect("Second");
+        backend.press(second, GamepadButton::East);
+        backend.set_axis(first, GamepadAxis::LeftTrigger, 1.);
+
+        input.begin_frame(0.);
+        assert!(input.gamepads() == vec![first, second]);
+        assert!(input.is_gamepad_button_down(second, GamepadButton::East));
+        assert!(!input.is_gamepad_button_down(first, GamepadButton::East));
+        assert_eq!(input.gamepad_axis(first, GamepadAxis::LeftTrigger), 1.);
+        assert_eq!(input.gamepad_axis(second, GamepadAxis::LeftTrigger), 0.);
+        input.end_frame();
+
+        backend.disconnect(first);
+        input.begin_frame(0.);
+        assert!(input.gamepads() == vec![second]);
+        assert!(input.is_gamepad_button_down(second, GamepadButton::East));
+    }
+
+    #[test]
+    fn button_edges_across_frames() {
+        let (mut input, backend) = virtual_input();
+        let id = backend.connect("Pad");
+        input.begin_frame(0.);
+        input.end_frame();
+
+        backend.press(id, GamepadButton::South);
+        input.begin_frame(0.);
+        assert!(input.on_gamepad_button_down(id, GamepadButton::South));
+        assert!(input.is_gamepad_button_down(id, GamepadButton::South));
+        input.end_frame();
+
+        // Held buttons are only pressed on their first frame, and pressing again while held does nothing
+        backend.press(id, GamepadButton::South);
+        input.begin_frame(0.);
+        assert!(!input.on_gamepad_button_down(id, GamepadButton::South));
+        assert!(input.is_gamepad_button_down(id, GamepadButton::South));
+        input.end_frame();
+
+        backend.release(id, GamepadButton::South);
+        input.begin_frame(0.);
+        assert!(input.on_gamepad_button_up(id, GamepadButton::South));
+        assert!(input.is_gamepad_button_up(id, GamepadButton::South));
+        input.end_frame();
+
+        input.begin_frame(0.);
+        assert!(!input.on_gamepad_button_up(id, GamepadButton::South));
+    }
+
+    #[test]
+    fn dead_zone_rescales_remaining_range() {
+        assert_eq!(apply_dead_zone(0.1, 0.2), 0.);
+        assert_eq!(apply_dead_zone(-0.2, 0.2), 0.);
+        assert!((apply_dead_zone(0.6, 0.2) - 0.5).abs() < 1e-6);
+        assert!((apply_dead_zone(-0.6, 0.2) + 0.5).abs() < 1e-6);
+        assert_eq!(apply_dead_zone(1., 0.2), 1.);
+        assert_eq!(apply_dead_zone(1., 1.), 0.);
+    }
+
+    #[test]
+    fn dead_zones_on_sticks_and_triggers() {
+        let (mut input, backend) = virtual_input();
+        input.stick_dead_zone = 0.2;
+        input.trigger_dead_zone = 0.1;
+        let id = backend.connect("Pad");
+        backend.set_axis(id, GamepadAxis::LeftStickX, 0.15);
+        backend.set_axis(id, GamepadAxis::LeftStickY, 0.);
+        backend.set_axis(id, GamepadAxis::RightStickX, 0.6);
+        backend.set_axis(id, GamepadAxis::LeftTrigger, 0.05);
+        backend.set_axis(id, GamepadAxis::RightTrigger, 0.55);
+        input.begin_frame(0.);
+
+        assert_eq!(input.gamepad_axis(id, GamepadAxis::LeftStickX), 0.);
+        assert!((input.gamepad_axis(id, GamepadAxis::RightStickX) - 0.5).abs() < 1e-6);
+        assert_eq!(input.gamepad_axis(id, GamepadAxis::LeftTrigger), 0.);
+        assert!((input.gamepad_axis(id, GamepadAxis::RightTrigger) - 0.5).abs() < 1e-6);
+
+        // Sticks use a radial dead zone on both axes together
+        assert!(input.gamepad_stick(id, GamepadStick::Left) == Vector2::zero());
+        let right = input.gamepad_stick(id, GamepadStick::Right);
+        assert!((right.x - 0.5).abs() < 1e-6 && right.y == 0.);
+        input.end_frame();
+
+        // Triggers do not go below 0 and sticks do not go past 1
+        backend.set_axis(id, GamepadAxis::LeftTrigger, -1.);
+        backend.set_axis(id, GamepadAxis::LeftStickY, -3.);
+        input.begin_frame(0.);
+        assert_eq!(input.gamepad_axis(id, GamepadAxis::LeftTrigger), 0.);
+        assert_eq!(input.gamepad_axis(id, GamepadAxis::LeftStickY), -1.);
+    }
+
+    #[test]
+    fn rumble_reaches_backend() {
+        let (mut input, backend) = virtual_input();
+        let id = backend.connect("Pad");
+        input.begin_frame(0.);
+
+        assert!(input.rumble(id, 0.5, 2., 0.25));
+        let rumble = backend.last_rumble(id).unwrap();
+        assert_eq!(rumble.strong, 0.5);
+        assert_eq!(rumble.weak, 1.);
+        assert_eq!(rumble.duration, 0.25);
+
+        backend.disconnect(id);
+        assert!(!input.rumble(id, 1., 1., 1.));
+        assert!(backend.last_rumble(id).is_none());
+        assert!(!Input::new().rumble(id, 1., 1., 1.));
+    }
+}
@@ -0,0 +1,195 @@
+use crate::input::gamepad::{GamepadAxis, GamepadBackend, GamepadEvent, GamepadId};
+use crate::input::GamepadButton;
+
+use gilrs::ff::{BaseEffect, BaseEffectType, Effect, EffectBuilder, Repeat, Replay, Ticks};
+use gilrs::{Axis, Button, EventType, Gilrs};
+
+use std::collections::HashMap;
+
+/// Gamepad backend that reads connected hardware gamepads through gilrs.
+/// Only available with the "gilrs" feature, and used by App::run when no other backend is set
+pub struct GilrsGamepadBackend {
+    gilrs: Gilrs,
+    next_id: u32,
+    ids: HashMap<gilrs::GamepadId, u32>,
+    // Effects stop when they are dropped, so the last rumble of each gamepad is kept
+    rumbles: HashMap<u32, Effect>,
+    // Gamepads connected before the backend was created are reported on the first poll
+    pending_events: Vec<GamepadEvent>,
+}
+
+impl GilrsGamepadBackend {
+    /// Default constructor to initialize backend, None if gamepads cannot be read on this platform
+    pub fn new() -> Option<Self> {
+        let gilrs = Gilrs::new().ok()?;
+        let mut backend = Self {
+            gilrs,
+            next_id: 0,
+            ids: HashMap::new(),
+            rumbles: HashMap::new(),
+            pending_events: Vec::new(),
+        };
+        let connected: Vec<gilrs::GamepadId> = backend.gilrs.gamepads().map(|(id, _)| id).collect();
+        for gilrs_id in connected {
+            let id = backend.connect(gilrs_id);
+            backend.pending_events.push(GamepadEvent::Connected(id));
+        }
+        return Some(backend);
+    }
+
+    /// Give a newly connected gamepad a new id, ids are not reused after a gamepad disconnects
+    fn connect(&mut self, gilrs_id: gilrs::GamepadId) -> GamepadId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.ids.insert(gilrs_id, id);
+        return GamepadId(id);
+    }
+
+    /// Return gilrs id of a connected gamepad
+    fn gilrs_id(&self, id: GamepadId) -> Option<gilrs::GamepadId> {
+        return self
+            .ids
+            .iter()
+            .find(|(_, gamepad_id)| **gamepad_id == id.0)
+            .map(|(gilrs_id, _)| *gilrs_id);
+    }
+}
+
+impl GamepadBackend for GilrsGamepadBackend {
+    fn poll_events(&mut self) -> Vec<GamepadEvent> {
+        let mut events = std::mem::take(&mut self.pending_events);
+        while let Some(event) = self.gilrs.next_event() {
+            if let EventType::Connected = event.event {
+                let id = self.connect(event.id);
+                events.push(GamepadEvent::Connected(id));
+                continue;
+            }
+            let id = match self.ids.get(&event.id) {
+                Some(id) => GamepadId(*id),
+                None => continue,
+            };
+
+            match event.event {
+                EventType::Disconnected => {
+                    self.ids.remove(&event.id);
+                    self.rumbles.remove(&id.0);
+                    events.push(GamepadEvent::Disconnected(id));
+                }
+                EventType::ButtonPressed(button, _) => {
+                    if let Some(button) = gamepad_button(button) {
+                        events.push(GamepadEvent::ButtonPressed(id, button));
+                    }
+                }
+                EventType::ButtonReleased(button, _) => {
+                    if let Some(button) = gamepad_button(button) {
+                        events.push(GamepadEvent::ButtonReleased(id, button));
+                    }
+                }
+                // Analog triggers are reported as buttons with a value
+                EventType::ButtonChanged(Button::LeftTrigger2, value, _) => {
+                    events.push(GamepadEvent::AxisChanged(
+                        id,
+                        GamepadAxis::LeftTrigger,
+                        value,
+                    ));
+                }
+                EventType::ButtonChanged(Button::RightTrigger2, value, _) => {
+                    events.push(GamepadEvent::AxisChanged(
+                        id,
+                        GamepadAxis::RightTrigger,
+                        value,
+                    ));
+                }
+                EventType::AxisChanged(axis, value, _) => {
+                    if let Some(axis) = gamepad_axis(axis) {
+                        events.push(GamepadEvent::AxisChanged(id, axis, value));
+                    }
+                }
+                _ => {}
+            }
+        }
+        return events;
+    }
+
+    fn rumble(&mut self, id: GamepadId, strong: f32, weak: f32, duration: f32) -> bool {
+        let gilrs_id = match self.gilrs_id(id) {
+            Some(gilrs_id) => gilrs_id,
+            None => return false,
+        };
+        if !self.gilrs.gamepad(gilrs_id).is_ff_supported() {
+            return false;
+        }
+
+        let ticks = Ticks::from_ms((duration.max(0.) * 1000.) as u32);
+        let scheduling = Replay {
+            play_for: ticks,
+            ..Default::default()
+        };
+        let magnitude = |intensity: f32| (intensity.clamp(0., 1.) * u16::MAX as f32) as u16;
+        let effect = EffectBuilder::new()
+            .add_effect(BaseEffect {
+                kind: BaseEffectType::Strong {
+                    magnitude: magnitude(strong),
+                },
+                scheduling,
+                ..Default::default()
+            })
+            .add_effect(BaseEffect {
+                kind: BaseEffectType::Weak {
+                    magnitude: magnitude(weak),
+                },
+                scheduling,
+                ..Default::default()
+            })
+            .gamepads(&[gilrs_id])
+            .repeat(Repeat::For(ticks))
+            .finish(&mut self.gilrs);
+
+        return match effect {
+            Ok(effect) => {
+                let is_playing = effect.play().is_ok();
+                self.rumbles.insert(id.0, effect);
+                is_playing
+            }
+            Err(_) => false,
+        };
+    }
+
+    fn name(&self, id: GamepadId) -> Option<String> {
+        let gilrs_id = self.gilrs_id(id)?;
+        return Some(String::from(self.gilrs.gamepad(gilrs_id).name()));
+    }
+}
+
+/// Return gamepad button of a gilrs button, None for buttons sidekick does not have
+fn gamepad_button(button: Button) -> Option<GamepadButton> {
+    return match button {
+        Button::South => Some(GamepadButton::South),
+        Button::East => Some(GamepadButton::East),
+        Button::West => Some(GamepadButton::West),
+        Button::North => Some(GamepadButton::North),
+        Button::LeftTrigger => Some(GamepadButton::LeftBumper),
+        Button::RightTrigger => Some(GamepadButton::RightBumper),
+        Button::Select => Some(GamepadButton::Select),
+        Button::Start => Some(GamepadButton::Start),
+        Button::Mode => Some(GamepadButton::Mode),
+        Button::LeftThumb => Some(GamepadButton::LeftStick),
+        Button::RightThumb => Some(GamepadButton::RightStick),
+        Button::DPadUp => Some(GamepadButton::DPadUp),
+        Button::DPadDown => Some(GamepadButton::DPadDown),
+        Button::DPadLeft => Some(GamepadButton::DPadLeft),
+        Button::DPadRight => Some(GamepadButton::DPadRight),
+        _ => None,
+    };
+}
+
+/// Return gamepad axis of a gilrs axis, None for axes sidekick does not have
+fn gamepad_axis(axis: Axis) -> Option<GamepadAxis> {
+    return match axis {
+        Axis::LeftStickX => Some(GamepadAxis::LeftStickX),
+        Axis::LeftStickY => Some(GamepadAxis::LeftStickY),
+        Axis::RightStickX => Some(GamepadAxis::RightStickX),
+        Axis::RightStickY => Some(GamepadAxis::RightStickY),
+        _ => None,
+    };
+}
@@ -0,0 +1,754 @@
+use glutin::event::VirtualKeyCode;
+
+/// Layout-aware keyboard keys, the key a physical key produces in the current keyboard layout.
+/// Use Key for physical keys that stay in place across layouts, e.g. WASD movement
+pub enum KeyCode {
+    Num0,
+    Num1,
+    Num2,
+    Num3,
+    Num4,
+    Num5,
+    Num6,
+    Num7,
+    Num8,
+    Num9,
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+    Esc,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+    F21,
+    F22,
+    F23,
+    F24,
+    PrintScreen,
+    ScrollLock,
+    Pause,
+    Insert,
+    Home,
+    Delete,
+    End,
+    PageDown,
+    PageUp,
+    Left,
+    Up,
+    Right,
+    Down,
+    Backspace,
+    Enter,
+    Space,
+    Compose,
+    Caret,
+    NumLock,
+    Numpad0,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+    NumpadAdd,
+    NumpadDivide,
+    NumpadDecimal,
+    NumpadComma,
+    NumpadEnter,
+    NumpadEquals,
+    NumpadMultiply,
+    NumpadSubtract,
+    AbntC1,
+    AbntC2,
+    Apostrophe,
+    Menu,
+    Asterisk,
+    At,
+    Ax,
+    BackSlash,
+    Calculator,
+    CapsLock,
+    Colon,
+    Comma,
+    Convert,
+    Equals,
+    GraveAccent,
+    Kana,
+    Kanji,
+    LeftAlt,
+    LeftBracket,
+    LeftCtrl,
+    LeftShift,
+    Meta,
+    Mail,
+    MediaSelect,
+    MediaStop,
+    Minus,
+    Mute,
+    MyComputer,
+    NavigateForward,
+    NavigateBackward,
+    NextTrack,
+    NoConvert,
+    IntlBackslash,
+    Period,
+    PlayPause,
+    Plus,
+    Power,
+    PrevTrack,
+    RightAlt,
+    RightBracket,
+    RightCtrl,
+    RightShift,
+    RightMeta,
+    Semicolon,
+    ForwardSlash,
+    Sleep,
+    Stop,
+    SysRq,
+    Tab,
+    Underline,
+    Unlabeled,
+    VolumeDown,
+    VolumeUp,
+    Wake,
+    WebBack,
+    WebFavorites,
+    WebForward,
+    WebHome,
+    WebRefresh,
+    WebSearch,
+    WebStop,
+    Yen,
+    Copy,
+    Paste,
+    Cut,
+}
+
+impl Copy for KeyCode {}
+
+impl Clone for KeyCode {
+    fn clone(&self) -> KeyCode {
+        *self
+    }
+}
+
+impl PartialEq for KeyCode {
+    fn eq(&self, other: &KeyCode) -> bool {
+        return *self as u32 == *other as u32;
+    }
+}
+
+// Every layout-aware key, used to look key codes up by name
+const KEY_CODES: [KeyCode; 163] = [
+    KeyCode::Num0,
+    KeyCode::Num1,
+    KeyCode::Num2,
+    KeyCode::Num3,
+    KeyCode::Num4,
+    KeyCode::Num5,
+    KeyCode::Num6,
+    KeyCode::Num7,
+    KeyCode::Num8,
+    KeyCode::Num9,
+    KeyCode::A,
+    KeyCode::B,
+    KeyCode::C,
+    KeyCode::D,
+    KeyCode::E,
+    KeyCode::F,
+    KeyCode::G,
+    KeyCode::H,
+    KeyCode::I,
+    KeyCode::J,
+    KeyCode::K,
+    KeyCode::L,
+    KeyCode::M,
+    KeyCode::N,
+    KeyCode::O,
+    KeyCode::P,
+    KeyCode::Q,
+    KeyCode::R,
+    KeyCode::S,
+    KeyCode::T,
+    KeyCode::U,
+    KeyCode::V,
+    KeyCode::W,
+    KeyCode::X,
+    KeyCode::Y,
+    KeyCode::Z,
+    KeyCode::Esc,
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+    KeyCode::F9,
+    KeyCode::F10,
+    KeyCode::F11,
+    KeyCode::F12,
+    KeyCode::F13,
+    KeyCode::F14,
+    KeyCode::F15,
+    KeyCode::F16,
+    KeyCode::F17,
+    KeyCode::F18,
+    KeyCode::F19,
+    KeyCode::F20,
+    KeyCode::F21,
+    KeyCode::F22,
+    KeyCode::F23,
+    KeyCode::F24,
+    KeyCode::PrintScreen,
+    KeyCode::ScrollLock,
+    KeyCode::Pause,
+    KeyCode::Insert,
+    KeyCode::Home,
+    KeyCode::Delete,
+    KeyCode::End,
+    KeyCode::PageDown,
+    KeyCode::PageUp,
+    KeyCode::Left,
+    KeyCode::Up,
+    KeyCode::Right,
+    KeyCode::Down,
+    KeyCode::Backspace,
+    KeyCode::Enter,
+    KeyCode::Space,
+    KeyCode::Compose,
+    KeyCode::Caret,
+    KeyCode::NumLock,
+    KeyCode::Numpad0,
+    KeyCode::Numpad1,
+    KeyCode::Numpad2,
+    KeyCode::Numpad3,
+    KeyCode::Numpad4,
+    KeyCode::Numpad5,
+    KeyCode::Numpad6,
+    KeyCode::Numpad7,
+    KeyCode::Numpad8,
+    KeyCode::Numpad9,
+    KeyCode::NumpadAdd,
+    KeyCode::NumpadDivide,
+    KeyCode::NumpadDecimal,
+    KeyCode::NumpadComma,
+    KeyCode::NumpadEnter,
+    KeyCode::NumpadEquals,
+    KeyCode::NumpadMultiply,
+    KeyCode::NumpadSubtract,
+    KeyCode::AbntC1,
+    KeyCode::AbntC2,
+    KeyCode::Apostrophe,
+    KeyCode::Menu,
+    KeyCode::Asterisk,
+    KeyCode::At,
+    KeyCode::Ax,
+    KeyCode::BackSlash,
+    KeyCode::Calculator,
+    KeyCode::CapsLock,
+    KeyCode::Colon,
+    KeyCode::Comma,
+    KeyCode::Convert,
+    KeyCode::Equals,
+    KeyCode::GraveAccent,
+    KeyCode::Kana,
+    KeyCode::Kanji,
+    KeyCode::LeftAlt,
+    KeyCode::LeftBracket,
+    KeyCode::LeftCtrl,
+    KeyCode::LeftShift,
+    KeyCode::Meta,
+    KeyCode::Mail,
+    KeyCode::MediaSelect,
+    KeyCode::MediaStop,
+    KeyCode::Minus,
+    KeyCode::Mute,
+    KeyCode::MyComputer,
+    KeyCode::NavigateForward,
+    KeyCode::NavigateBackward,
+    KeyCode::NextTrack,
+    KeyCode::NoConvert,
+    KeyCode::IntlBackslash,
+    KeyCode::Period,
+    KeyCode::PlayPause,
+    KeyCode::Plus,
+    KeyCode::Power,
+    KeyCode::PrevTrack,
+    KeyCode::RightAlt,
+    KeyCode::RightBracket,
+    KeyCode::RightCtrl,
+    KeyCode::RightShift,
+    KeyCode::RightMeta,
+    KeyCode::Semicolon,
+    KeyCode::ForwardSlash,
+    KeyCode::Sleep,
+    KeyCode::Stop,
+    KeyCode::SysRq,
+    KeyCode::Tab,
+    KeyCode::Underline,
+    KeyCode::Unlabeled,
+    KeyCode::VolumeDown,
+    KeyCode::VolumeUp,
+    KeyCode::Wake,
+    KeyCode::WebBack,
+    KeyCode::WebFavorites,
+    KeyCode::WebForward,
+    KeyCode::WebHome,
+    KeyCode::WebRefresh,
+    KeyCode::WebSearch,
+    KeyCode::WebStop,
+    KeyCode::Yen,
+    KeyCode::Copy,
+    KeyCode::Paste,
+    KeyCode::Cut,
+];
+
+impl KeyCode {
+    /// Return every layout-aware key
+    pub fn all() -> &'static [KeyCode] {
+        return &KEY_CODES;
+    }
+
+    /// Return name of key code, as used in binding files
+    pub fn name(&self) -> &'static str {
+        return match self {
+            KeyCode::Num0 => "Num0",
+            KeyCode::Num1 => "Num1",
+            KeyCode::Num2 => "Num2",
+            KeyCode::Num3 => "Num3",
+            KeyCode::Num4 => "Num4",
+            KeyCode::Num5 => "Num5",
+            KeyCode::Num6 => "Num6",
+            KeyCode::Num7 => "Num7",
+            KeyCode::Num8 => "Num8",
+            KeyCode::Num9 => "Num9",
+            KeyCode::A => "A",
+            KeyCode::B => "B",
+            KeyCode::C => "C",
+            KeyCode::D => "D",
+            KeyCode::E => "E",
+            KeyCode::F => "F",
+            KeyCode::G => "G",
+            KeyCode::H => "H",
+            KeyCode::I => "I",
+            KeyCode::J => "J",
+            KeyCode::K => "K",
+            KeyCode::L => "L",
+            KeyCode::M => "M",
+            KeyCode::N => "N",
+            KeyCode::O => "O",
+            KeyCode::P => "P",
+            KeyCode::Q => "Q",
+            KeyCode::R => "R",
+            KeyCode::S => "S",
+            KeyCode::T => "T",
+            KeyCode::U => "U",
+            KeyCode::V => "V",
+            KeyCode::W => "W",
+            KeyCode::X => "X",
+            KeyCode::Y => "Y",
+            KeyCode::Z => "Z",
+            KeyCode::Esc => "Esc",
+            KeyCode::F1 => "F1",
+            KeyCode::F2 => "F2",
+            KeyCode::F3 => "F3",
+            KeyCode::F4 => "F4",
+            KeyCode::F5 => "F5",
+            KeyCode::F6 => "F6",
+            KeyCode::F7 => "F7",
+            KeyCode::F8 => "F8",
+            KeyCode::F9 => "F9",
+            KeyCode::F10 => "F10",
+            KeyCode::F11 => "F11",
+            KeyCode::F12 => "F12",
+            KeyCode::F13 => "F13",
+            KeyCode::F14 => "F14",
+            KeyCode::F15 => "F15",
+            KeyCode::F16 => "F16",
+            KeyCode::F17 => "F17",
+            KeyCode::F18 => "F18",
+            KeyCode::F19 => "F19",
+            KeyCode::F20 => "F20",
+            KeyCode::F21 => "F21",
+            KeyCode::F22 => "F22",
+            KeyCode::F23 => "F23",
+            KeyCode::F24 => "F24",
+            KeyCode::PrintScreen => "PrintScreen",
+            KeyCode::ScrollLock => "ScrollLock",
+            KeyCode::Pause => "Pause",
+            KeyCode::Insert => "Insert",
+            KeyCode::Home => "Home",
+            KeyCode::Delete => "Delete",
+            KeyCode::End => "End",
+            KeyCode::PageDown => "PageDown",
+            KeyCode::PageUp => "PageUp",
+            KeyCode::Left => "Left",
+            KeyCode::Up => "Up",
+            KeyCode::Right => "Right",
+            KeyCode::Down => "Down",
+            KeyCode::Backspace => "Backspace",
+            KeyCode::Enter => "Enter",
+            KeyCode::Space => "Space",
+            KeyCode::Compose => "Compose",
+            KeyCode::Caret => "Caret",
+            KeyCode::NumLock => "NumLock",
+            KeyCode::Numpad0 => "Numpad0",
+            KeyCode::Numpad1 => "Numpad1",
+            KeyCode::Numpad2 => "Numpad2",
+            KeyCode::Numpad3 => "Numpad3",
+            KeyCode::Numpad4 => "Numpad4",
+            KeyCode::Numpad5 => "Numpad5",
+            KeyCode::Numpad6 => "Numpad6",
+            KeyCode::Numpad7 => "Numpad7",
+            KeyCode::Numpad8 => "Numpad8",
+            KeyCode::Numpad9 => "Numpad9",
+            KeyCode::NumpadAdd => "NumpadAdd",
+            KeyCode::NumpadDivide => "NumpadDivide",
+            KeyCode::NumpadDecimal => "NumpadDecimal",
+            KeyCode::NumpadComma => "NumpadComma",
+            KeyCode::NumpadEnter => "NumpadEnter",
+            KeyCode::NumpadEquals => "NumpadEquals",
+            KeyCode::NumpadMultiply => "NumpadMultiply",
+            KeyCode::NumpadSubtract => "NumpadSubtract",
+            KeyCode::AbntC1 => "AbntC1",
+            KeyCode::AbntC2 => "AbntC2",
+            KeyCode::Apostrophe => "Apostrophe",
+            KeyCode::Menu => "Menu",
+            KeyCode::Asterisk => "Asterisk",
+            KeyCode::At => "At",
+            KeyCode::Ax => "Ax",
+            KeyCode::BackSlash => "BackSlash",
+            KeyCode::Calculator => "Calculator",
+            KeyCode::CapsLock => "CapsLock",
+            KeyCode::Colon => "Colon",
+            KeyCode::Comma => "Comma",
+            KeyCode::Convert => "Convert",
+            KeyCode::Equals => "Equals",
+            KeyCode::GraveAccent => "GraveAccent",
+            KeyCode::Kana => "Kana",
+            KeyCode::Kanji => "Kanji",
+            KeyCode::LeftAlt => "LeftAlt",
+            KeyCode::LeftBracket => "LeftBracket",
+            KeyCode::LeftCtrl => "LeftCtrl",
+            KeyCode::LeftShift => "LeftShift",
+            KeyCode::Meta => "Meta",
+            KeyCode::Mail => "Mail",
+            KeyCode::MediaSelect => "MediaSelect",
+            KeyCode::MediaStop => "MediaStop",
+            KeyCode::Minus => "Minus",
+            KeyCode::Mute => "Mute",
+            KeyCode::MyComputer => "MyComputer",
+            KeyCode::NavigateForward => "NavigateForward",
+            KeyCode::NavigateBackward => "NavigateBackward",
+            KeyCode::NextTrack => "NextTrack",
+            KeyCode::NoConvert => "NoConvert",
+            KeyCode::IntlBackslash => "IntlBackslash",
+            KeyCode::Period => "Period",
+            KeyCode::PlayPause => "PlayPause",
+            KeyCode::Plus => "Plus",
+            KeyCode::Power => "Power",
+            KeyCode::PrevTrack => "PrevTrack",
+            KeyCode::RightAlt => "RightAlt",
+            KeyCode::RightBracket => "RightBracket",
+            KeyCode::RightCtrl => "RightCtrl",
+            KeyCode::RightShift => "RightShift",
+            KeyCode::RightMeta => "RightMeta",
+            KeyCode::Semicolon => "Semicolon",
+            KeyCode::ForwardSlash => "ForwardSlash",
+            KeyCode::Sleep => "Sleep",
+            KeyCode::Stop => "Stop",
+            KeyCode::SysRq => "SysRq",
+            KeyCode::Tab => "Tab",
+            KeyCode::Underline => "Underline",
+            KeyCode::Unlabeled => "Unlabeled",
+            KeyCode::VolumeDown => "VolumeDown",
+            KeyCode::VolumeUp => "VolumeUp",
+            KeyCode::Wake => "Wake",
+            KeyCode::WebBack => "WebBack",
+            KeyCode::WebFavorites => "WebFavorites",
+            KeyCode::WebForward => "WebForward",
+            KeyCode::WebHome => "WebHome",
+            KeyCode::WebRefresh => "WebRefresh",
+            KeyCode::WebSearch => "WebSearch",
+            KeyCode::WebStop => "WebStop",
+            KeyCode::Yen => "Yen",
+            KeyCode::Copy => "Copy",
+            KeyCode::Paste => "Paste",
+            KeyCode::Cut => "Cut",
+        };
+    }
+    /// Return key code with a name
+    pub fn from_name(name: &str) -> Option<KeyCode> {
+        return KEY_CODES
+            .iter()
+            .find(|key_code| key_code.name() == name)
+            .copied();
+    }
+
+    /// Return name of key code to display to players, e.g. "Page Up" or "/"
+    pub fn display_name(&self) -> String {
+        return match self {
+            KeyCode::Apostrophe => String::from("'"),
+            KeyCode::Asterisk => String::from("*"),
+            KeyCode::At => String::from("@"),
+            KeyCode::BackSlash => String::from("\\"),
+            KeyCode::Colon => String::from(":"),
+            KeyCode::Comma => String::from(","),
+            KeyCode::Equals => String::from("="),
+            KeyCode::GraveAccent => String::from("`"),
+            KeyCode::LeftBracket => String::from("["),
+            KeyCode::RightBracket => String::from("]"),
+            KeyCode::Minus => String::from("-"),
+            KeyCode::Period => String::from("."),
+            KeyCode::Plus => String::from("+"),
+            KeyCode::Semicolon => String::from(";"),
+            KeyCode::ForwardSlash => String::from("/"),
+            KeyCode::Caret => String::from("^"),
+            KeyCode::Underline => String::from("_"),
+            KeyCode::Num0
+            | KeyCode::Num1
+            | KeyCode::Num2
+            | KeyCode::Num3
+            | KeyCode::Num4
+            | KeyCode::Num5
+            | KeyCode::Num6
+            | KeyCode::Num7
+            | KeyCode::Num8
+            | KeyCode::Num9 => String::from(&self.name()[3..]),
+            _ => split_words(self.name()),
+        };
+    }
+}
+
+/// Insert spaces between words and numbers of a name, e.g. "PageUp" becomes "Page Up"
+fn split_words(name: &str) -> String {
+    let mut words = String::new();
+    let mut previous: Option<char> = None;
+    for character in name.chars() {
+        if let Some(previous) = previous {
+            let is_new_word =
+                (character.is_uppercase() || character.is_ascii_digit()) && previous.is_lowercase();
+            if is_new_word {
+                words.push(' ');
+            }
+        }
+        words.push(character);
+        previous = Some(character);
+    }
+    return words;
+}
+
+/// Convert a glutin virtual key code to a key code
+pub(crate) fn from_virtual_key_code(virtual_key_code: VirtualKeyCode) -> KeyCode {
+    return match virtual_key_code {
+        VirtualKeyCode::Key0 => KeyCode::Num0,
+        VirtualKeyCode::Key1 => KeyCode::Num1,
+        VirtualKeyCode::Key2 => KeyCode::Num2,
+        VirtualKeyCode::Key3 => KeyCode::Num3,
+        VirtualKeyCode::Key4 => KeyCode::Num4,
+        VirtualKeyCode::Key5 => KeyCode::Num5,
+        VirtualKeyCode::Key6 => KeyCode::Num6,
+        VirtualKeyCode::Key7 => KeyCode::Num7,
+        VirtualKeyCode::Key8 => KeyCode::Num8,
+        VirtualKeyCode::Key9 => KeyCode::Num9,
+        VirtualKeyCode::A => KeyCode::A,
+        VirtualKeyCode::B => KeyCode::B,
+        VirtualKeyCode::C => KeyCode::C,
+        VirtualKeyCode::D => KeyCode::D,
+        VirtualKeyCode::E => KeyCode::E,
+        VirtualKeyCode::F => KeyCode::F,
+        VirtualKeyCode::G => KeyCode::G,
+        VirtualKeyCode::H => KeyCode::H,
+        VirtualKeyCode::I => KeyCode::I,
+        VirtualKeyCode::J => KeyCode::J,
+        VirtualKeyCode::K => KeyCode::K,
+        VirtualKeyCode::L => KeyCode::L,
+        VirtualKeyCode::M => KeyCode::M,
+        VirtualKeyCode::N => KeyCode::N,
+        VirtualKeyCode::O => KeyCode::O,
+        VirtualKeyCode::P => KeyCode::P,
+        VirtualKeyCode::Q => KeyCode::Q,
+        VirtualKeyCode::R => KeyCode::R,
+        VirtualKeyCode::S => KeyCode::S,
+        VirtualKeyCode::T => KeyCode::T,
+        VirtualKeyCode::U => KeyCode::U,
+        VirtualKeyCode::V => KeyCode::V,
+        VirtualKeyCode::W => KeyCode::W,
+        VirtualKeyCode::X => KeyCode::X,
+        VirtualKeyCode::Y => KeyCode::Y,
+        VirtualKeyCode::Z => KeyCode::Z,
+        VirtualKeyCode::Escape => KeyCode::Esc,
+        VirtualKeyCode::F1 => KeyCode::F1,
+        VirtualKeyCode::F2 => KeyCode::F2,
+        VirtualKeyCode::F3 => KeyCode::F3,
+        VirtualKeyCode::F4 => KeyCode::F4,
+        VirtualKeyCode::F5 => KeyCode::F5,
+        VirtualKeyCode::F6 => KeyCode::F6,
+        VirtualKeyCode::F7 => KeyCode::F7,
+        VirtualKeyCode::F8 => KeyCode::F8,
+        VirtualKeyCode::F9 => KeyCode::F9,
+        VirtualKeyCode::F10 => KeyCode::F10,
+        VirtualKeyCode::F11 => KeyCode::F11,
+        VirtualKeyCode::F12 => KeyCode::F12,
+        VirtualKeyCode::F13 => KeyCode::F13,
+        VirtualKeyCode::F14 => KeyCode::F14,
+        VirtualKeyCode::F15 => KeyCode::F15,
+        VirtualKeyCode::F16 => KeyCode::F16,
+        VirtualKeyCode::F17 => KeyCode::F17,
+        VirtualKeyCode::F18 => KeyCode::F18,
+        VirtualKeyCode::F19 => KeyCode::F19,
+        VirtualKeyCode::F20 => KeyCode::F20,
+        VirtualKeyCode::F21 => KeyCode::F21,
+        VirtualKeyCode::F22 => KeyCode::F22,
+        VirtualKeyCode::F23 => KeyCode::F23,
+        VirtualKeyCode::F24 => KeyCode::F24,
+        VirtualKeyCode::Snapshot => KeyCode::PrintScreen,
+        VirtualKeyCode::Scroll => KeyCode::ScrollLock,
+        VirtualKeyCode::Pause => KeyCode::Pause,
+        VirtualKeyCode::Insert => KeyCode::Insert,
+        VirtualKeyCode::Home => KeyCode::Home,
+        VirtualKeyCode::Delete => KeyCode::Delete,
+        VirtualKeyCode::End => KeyCode::End,
+        VirtualKeyCode::PageDown => KeyCode::PageDown,
+        VirtualKeyCode::PageUp => KeyCode::PageUp,
+        VirtualKeyCode::Left => KeyCode::Left,
+        VirtualKeyCode::Up => KeyCode::Up,
+        VirtualKeyCode::Right => KeyCode::Right,
+        VirtualKeyCode::Down => KeyCode::Down,
+        VirtualKeyCode::Back => KeyCode::Backspace,
+        VirtualKeyCode::Return => KeyCode::Enter,
+        VirtualKeyCode::Space => KeyCode::Space,
+        VirtualKeyCode::Compose => KeyCode::Compose,
+        VirtualKeyCode::Caret => KeyCode::Caret,
+        VirtualKeyCode::Numlock => KeyCode::NumLock,
+        VirtualKeyCode::Numpad0 => KeyCode::Numpad0,
+        VirtualKeyCode::Numpad1 => KeyCode::Numpad1,
+        VirtualKeyCode::Numpad2 => KeyCode::Numpad2,
+        VirtualKeyCode::Numpad3 => KeyCode::Numpad3,
+        VirtualKeyCode::Numpad4 => KeyCode::Numpad4,
+        VirtualKeyCode::Numpad5 => KeyCode::Numpad5,
+        VirtualKeyCode::Numpad6 => KeyCode::Numpad6,
+        VirtualKeyCode::Numpad7 => KeyCode::Numpad7,
+        VirtualKeyCode::Numpad8 => KeyCode::Numpad8,
+        VirtualKeyCode::Numpad9 => KeyCode::Numpad9,
+        VirtualKeyCode::NumpadAdd => KeyCode::NumpadAdd,
+        VirtualKeyCode::NumpadDivide => KeyCode::NumpadDivide,
+        VirtualKeyCode::NumpadDecimal => KeyCode::NumpadDecimal,
+        VirtualKeyCode::NumpadComma => KeyCode::NumpadComma,
+        VirtualKeyCode::NumpadEnter => KeyCode::NumpadEnter,
+        VirtualKeyCode::NumpadEquals => KeyCode::NumpadEquals,
+        VirtualKeyCode::NumpadMultiply => KeyCode::NumpadMultiply,
+        VirtualKeyCode::NumpadSubtract => KeyCode::NumpadSubtract,
+        VirtualKeyCode::AbntC1 => KeyCode::AbntC1,
+        VirtualKeyCode::AbntC2 => KeyCode::AbntC2,
+        VirtualKeyCode::Apostrophe => KeyCode::Apostrophe,
+        VirtualKeyCode::Apps => KeyCode::Menu,
+        VirtualKeyCode::Asterisk => KeyCode::Asterisk,
+        VirtualKeyCode::At => KeyCode::At,
+        VirtualKeyCode::Ax => KeyCode::Ax,
+        VirtualKeyCode::Backslash => KeyCode::BackSlash,
+        VirtualKeyCode::Calculator => KeyCode::Calculator,
+        VirtualKeyCode::Capital => KeyCode::CapsLock,
+        VirtualKeyCode::Colon => KeyCode::Colon,
+        VirtualKeyCode::Comma => KeyCode::Comma,
+        VirtualKeyCode::Convert => KeyCode::Convert,
+        VirtualKeyCode::Equals => KeyCode::Equals,
+        VirtualKeyCode::Grave => KeyCode::GraveAccent,
+        VirtualKeyCode::Kana => KeyCode::Kana,
+        VirtualKeyCode::Kanji => KeyCode::Kanji,
+        VirtualKeyCode::LAlt => KeyCode::LeftAlt,
+        VirtualKeyCode::LBracket => KeyCode::LeftBracket,
+        VirtualKeyCode::LControl => KeyCode::LeftCtrl,
+        VirtualKeyCode::LShift => KeyCode::LeftShift,
+        VirtualKeyCode::LWin => KeyCode::Meta,
+        VirtualKeyCode::Mail => KeyCode::Mail,
+        VirtualKeyCode::MediaSelect => KeyCode::MediaSelect,
+        VirtualKeyCode::MediaStop => KeyCode::MediaStop,
+        VirtualKeyCode::Minus => KeyCode::Minus,
+        VirtualKeyCode::Mute => KeyCode::Mute,
+        VirtualKeyCode::MyComputer => KeyCode::MyComputer,
+        VirtualKeyCode::NavigateForward => KeyCode::NavigateForward,
+        VirtualKeyCode::NavigateBackward => KeyCode::NavigateBackward,
+        VirtualKeyCode::NextTrack => KeyCode::NextTrack,
+        VirtualKeyCode::NoConvert => KeyCode::NoConvert,
+        VirtualKeyCode::OEM102 => KeyCode::IntlBackslash,
+        VirtualKeyCode::Period => KeyCode::Period,
+        VirtualKeyCode::PlayPause => KeyCode::PlayPause,
+        VirtualKeyCode::Plus => KeyCode::Plus,
+        VirtualKeyCode::Power => KeyCode::Power,
+        VirtualKeyCode::PrevTrack => KeyCode::PrevTrack,
+        VirtualKeyCode::RAlt => KeyCode::RightAlt,
+        VirtualKeyCode::RBracket => KeyCode::RightBracket,
+        VirtualKeyCode::RControl => KeyCode::RightCtrl,
+        VirtualKeyCode::RShift => KeyCode::RightShift,
+        VirtualKeyCode::RWin => KeyCode::RightMeta,
+        VirtualKeyCode::Semicolon => KeyCode::Semicolon,
+        VirtualKeyCode::Slash => KeyCode::ForwardSlash,
+        VirtualKeyCode::Sleep => KeyCode::Sleep,
+        VirtualKeyCode::Stop => KeyCode::Stop,
+        VirtualKeyCode::Sysrq => KeyCode::SysRq,
+        VirtualKeyCode::Tab => KeyCode::Tab,
+        VirtualKeyCode::Underline => KeyCode::Underline,
+        VirtualKeyCode::Unlabeled => KeyCode::Unlabeled,
+        VirtualKeyCode::VolumeDown => KeyCode::VolumeDown,
+        VirtualKeyCode::VolumeUp => KeyCode::VolumeUp,
+        VirtualKeyCode::Wake => KeyCode::Wake,
+        VirtualKeyCode::WebBack => KeyCode::WebBack,
+        VirtualKeyCode::WebFavorites => KeyCode::WebFavorites,
+        VirtualKeyCode::WebForward => KeyCode::WebForward,
+        VirtualKeyCode::WebHome => KeyCode::WebHome,
+        VirtualKeyCode::WebRefresh => KeyCode::WebRefresh,
+        VirtualKeyCode::WebSearch => KeyCode::WebSearch,
+        VirtualKeyCode::WebStop => KeyCode::WebStop,
+        VirtualKeyCode::Yen => KeyCode::Yen,
+        VirtualKeyCode::Copy => KeyCode::Copy,
+        VirtualKeyCode::Paste => KeyCode::Paste,
+        VirtualKeyCode::Cut => KeyCode::Cut,
+    };
+}
@@ -0,0 +1,1634 @@
+pub mod action;
+pub mod gamepad;
+#[cfg(feature = "gilrs")]
+pub mod gilrs_backend;
+pub mod key_code;
+pub mod recording;
+pub mod text;
+
+use crate::input::action::{ActionMap, InputSource};
+use crate::input::gamepad::{
+    GamepadAxis, GamepadBackend, GamepadEvent, GamepadId, GamepadState, GamepadStick,
+};
+use crate::input::key_code::KeyCode;
+use crate::input::recording::{InputEvent, InputRecording};
+use crate::input::text::TextInput;
+use crate::types::vector2::Vector2;
+
+use glutin::dpi::PhysicalPosition;
+use glutin::event::{ElementState, KeyboardInput, ModifiersState, MouseScrollDelta};
+
+use std::collections::HashMap;
+
+/// Input manager
+pub struct Input {
+    current_keys_down: Vec<u32>,
+    keys_pressed: Vec<u32>,
+    keys_released: Vec<u32>,
+    current_key_codes_down: Vec<KeyCode>,
+    key_codes_pressed: Vec<KeyCode>,
+    key_codes_released: Vec<KeyCode>,
+    key_codes_by_scancode: HashMap<u32, KeyCode>,
+    current_mouse_buttons_down: Vec<u32>,
+    mouse_buttons_pressed: Vec<u32>,
+    mouse_buttons_released: Vec<u32>,
+    mouse_position: Vector2,
+    mouse_delta: Vector2,
+    scroll_lines: Vector2,
+    scroll_pixels: Vector2,
+    is_mouse_entered: bool,
+
+    pub actions: ActionMap,
+    actions_down_last_frame: Vec<String>,
+    is_listening: bool,
+    captured_input: Option<InputSource>,
+
+    modifiers: Modifiers,
+    text_input: Vec<TextInput>,
+
+    gamepad_backend: Option<Box<dyn GamepadBackend>>,
+    gamepads: Vec<GamepadState>,
+    gamepad_events: Vec<GamepadEvent>,
+    /// Stick values below this distance from the center are ignored
+    pub stick_dead_zone: f32,
+    /// Trigger values below this are ignored
+    pub trigger_dead_zone: f32,
+
+    frame: u64,
+    recording: Option<InputRecording>,
+    recording_start_frame: u64,
+    playback: Option<InputRecording>,
+    playback_start_frame: u64,
+    playback_index: usize,
+    simulated_events: Vec<(u64, InputEvent)>,
+    is_frame_started: bool,
+}
+
+/// State of modifier keys
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    /// Windows key on Windows, Command key on macOS
+    pub logo: bool,
+}
+
+impl Copy for Modifiers {}
+
+impl Clone for Modifiers {
+    fn clone(&self) -> Modifiers {
+        *self
+    }
+}
+
+impl PartialEq for Modifiers {
+    fn eq(&self, other: &Modifiers) -> bool {
+        return self.shift == other.shift
+            && self.ctrl == other.ctrl
+            && self.alt == other.alt
+            && self.logo == other.logo;
+    }
+}
+
+impl Modifiers {
+    /// Default constructor to initialize Modifiers with no modifier held down
+    pub fn new() -> Self {
+        return Self {
+            shift: false,
+            ctrl: false,
+            alt: false,
+            logo: false,
+        };
+    }
+}
+
+/// Physical keyboard keys to check for input, identified by scancode so they stay in place across layouts
+pub enum Key {
+    Esc = 1,
+    Num1 = 2,
+    Num2 = 3,
+    Num3 = 4,
+    Num4 = 5,
+    Num5 = 6,
+    Num6 = 7,
+    Num7 = 8,
+    Num8 = 9,
+    Num9 = 10,
+    Num0 = 11,
+    Minus = 12,
+    Plus = 13,
+    Backspace = 14,
+    Tab = 15,
+    Q = 16,
+    W = 17,
+    E = 18,
+    R = 19,
+    T = 20,
+    Y = 21,
+    U = 22,
+    I = 23,
+    O = 24,
+    P = 25,
+    LeftBracket = 26,
+    RightBracket = 27,
+    Enter = 28,
+    LeftCtrl = 29,
+    A = 30,
+    S = 31,
+    D = 32,
+    F = 33,
+    G = 34,
+    H = 35,
+    J = 36,
+    K = 37,
+    L = 38,
+    Semicolon = 39,
+    Apostrophe = 40,
+    GraveAccent = 41,
+    LeftShift = 42,
+    BackSlash = 43,
+    Z = 44,
+    X = 45,
+    C = 46,
+    V = 47,
+    B = 48,
+    N = 49,
+    M = 50,
+    Comma = 51,
+    Period = 52,
+    ForwardSlash = 53,
+    RightShift = 54,
+    NumpadMultiply = 55,
+    LeftAlt = 56,
+    Space = 57,
+    CapsLock = 58,
+    F1 = 59,
+    F2 = 60,
+    F3 = 61,
+    F4 = 62,
+    F5 = 63,
+    F6 = 64,
+    F7 = 65,
+    F8 = 66,
+    F9 = 67,
+    F10 = 68,
+    Pause = 69,
+    ScrollLock = 70,
+    Numpad7 = 71,
+    Numpad8 = 72,
+    Numpad9 = 73,
+    NumpadSubtract = 74,
+    Numpad4 = 75,
+    Numpad5 = 76,
+    Numpad6 = 77,
+    NumpadAdd = 78,
+    Numpad1 = 79,
+    Numpad2 = 80,
+    Numpad3 = 81,
+    Numpad0 = 82,
+    NumpadDecimal = 83,
+    IntlBackslash = 86,
+    F11 = 87,
+    F12 = 88,
+    F13 = 100,
+    F14 = 101,
+    F15 = 102,
+    F16 = 103,
+    F17 = 104,
+    F18 = 105,
+    F19 = 106,
+    F20 = 107,
+    F21 = 108,
+    F22 = 109,
+    F23 = 110,
+    F24 = 118,
+    PrevTrack = 57360,
+    NextTrack = 57369,
+    NumpadEnter = 57372,
+    RightCtrl = 57373,
+    Mute = 57376,
+    Calculator = 57377,
+    PlayPause = 57378,
+    MediaStop = 57380,
+    VolumeDown = 57390,
+    VolumeUp = 57392,
+    WebHome = 57394,
+    NumpadDivide = 57397,
+    PrintScreen = 57399,
+    RightAlt = 57400,
+    NumLock = 57413,
+    Home = 57415,
+    Up = 57416,
+    PageUp = 57417,
+    Left = 57419,
+    Right = 57421,
+    End = 57423,
+    Down = 57424,
+    PageDown = 57425,
+    Insert = 57426,
+    Delete = 57427,
+    Meta = 57435,
+    RightMeta = 57436,
+    Menu = 57437,
+}
+
+impl Copy for Key {}
+
+impl Clone for Key {
+    fn clone(&self) -> Key {
+        *self
+    }
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Key) -> bool {
+        return *self as u32 == *other as u32;
+    }
+}
+
+// Every keyboard key, used to look keys up by name or scancode
+const KEYS: [Key; 126] = [
+    Key::Esc,
+    Key::Num1,
+    Key::Num2,
+    Key::Num3,
+    Key::Num4,
+    Key::Num5,
+    Key::Num6,
+    Key::Num7,
+    Key::Num8,
+    Key::Num9,
+    Key::Num0,
+    Key::Minus,
+    Key::Plus,
+    Key::Backspace,
+    Key::Tab,
+    Key::Q,
+    Key::W,
+    Key::E,
+    Key::R,
+    Key::T,
+    Key::Y,
+    Key::U,
+    Key::I,
+    Key::O,
+    Key::P,
+    Key::LeftBracket,
+    Key::RightBracket,
+    Key::Enter,
+    Key::LeftCtrl,
+    Key::A,
+    Key::S,
+    Key::D,
+    Key::F,
+    Key::G,
+    Key::H,
+    Key::J,
+    Key::K,
+    Key::L,
+    Key::Semicolon,
+    Key::Apostrophe,
+    Key::GraveAccent,
+    Key::LeftShift,
+    Key::BackSlash,
+    Key::Z,
+    Key::X,
+    Key::C,
+    Key::V,
+    Key::B,
+    Key::N,
+    Key::M,
+    Key::Comma,
+    Key::Period,
+    Key::ForwardSlash,
+    Key::RightShift,
+    Key::NumpadMultiply,
+    Key::LeftAlt,
+    Key::Space,
+    Key::CapsLock,
+    Key::F1,
+    Key::F2,
+    Key::F3,
+    Key::F4,
+    Key::F5,
+    Key::F6,
+    Key::F7,
+    Key::F8,
+    Key::F9,
+    Key::F10,
+    Key::Pause,
+    Key::ScrollLock,
+    Key::Numpad7,
+    Key::Numpad8,
+    Key::Numpad9,
+    Key::NumpadSubtract,
+    Key::Numpad4,
+    Key::Numpad5,
+    Key::Numpad6,
+    Key::NumpadAdd,
+    Key::Numpad1,
+    Key::Numpad2,
+    Key::Numpad3,
+    Key::Numpad0,
+    Key::NumpadDecimal,
+    Key::IntlBackslash,
+    Key::F11,
+    Key::F12,
+    Key::F13,
+    Key::F14,
+    Key::F15,
+    Key::F16,
+    Key::F17,
+    Key::F18,
+    Key::F19,
+    Key::F20,
+    Key::F21,
+    Key::F22,
+    Key::F23,
+    Key::F24,
+    Key::PrevTrack,
+    Key::NextTrack,
+    Key::NumpadEnter,
+    Key::RightCtrl,
+    Key::Mute,
+    Key::Calculator,
+    Key::PlayPause,
+    Key::MediaStop,
+    Key::VolumeDown,
+    Key::VolumeUp,
+    Key::WebHome,
+    Key::NumpadDivide,
+    Key::PrintScreen,
+    Key::RightAlt,
+    Key::NumLock,
+    Key::Home,
+    Key::Up,
+    Key::PageUp,
+    Key::Left,
+    Key::Right,
+    Key::End,
+    Key::Down,
+    Key::PageDown,
+    Key::Insert,
+    Key::Delete,
+    Key::Meta,
+    Key::RightMeta,
+    Key::Menu,
+];
+
+impl Key {
+    /// Return every keyboard key
+    pub fn all() -> &'static [Key] {
+        return &KEYS;
+    }
+
+    /// Return name of key, as used in binding files
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Key::Esc => "Esc",
+            Key::Num1 => "Num1",
+            Key::Num2 => "Num2",
+            Key::Num3 => "Num3",
+            Key::Num4 => "Num4",
+            Key::Num5 => "Num5",
+            Key::Num6 => "Num6",
+            Key::Num7 => "Num7",
+            Key::Num8 => "Num8",
+            Key::Num9 => "Num9",
+            Key::Num0 => "Num0",
+            Key::Minus => "Minus",
+            Key::Plus => "Plus",
+            Key::Backspace => "Backspace",
+            Key::Tab => "Tab",
+            Key::Q => "Q",
+            Key::W => "W",
+            Key::E => "E",
+            Key::R => "R",
+            Key::T => "T",
+            Key::Y => "Y",
+            Key::U => "U",
+            Key::I => "I",
+            Key::O => "O",
+            Key::P => "P",
+            Key::LeftBracket => "LeftBracket",
+            Key::RightBracket => "RightBracket",
+            Key::Enter => "Enter",
+            Key::LeftCtrl => "LeftCtrl",
+            Key::A => "A",
+            Key::S => "S",
+            Key::D => "D",
+            Key::F => "F",
+            Key::G => "G",
+            Key::H => "H",
+            Key::J => "J",
+            Key::K => "K",
+            Key::L => "L",
+            Key::Semicolon => "Semicolon",
+            Key::Apostrophe => "Apostrophe",
+            Key::GraveAccent => "GraveAccent",
+            Key::LeftShift => "LeftShift",
+            Key::BackSlash => "BackSlash",
+            Key::Z => "Z",
+            Key::X => "X",
+            Key::C => "C",
+            Key::V => "V",
+            Key::B => "B",
+            Key::N => "N",
+            Key::M => "M",
+            Key::Comma => "Comma",
+            Key::Period => "Period",
+            Key::ForwardSlash => "ForwardSlash",
+            Key::RightShift => "RightShift",
+            Key::NumpadMultiply => "NumpadMultiply",
+            Key::LeftAlt => "LeftAlt",
+            Key::Space => "Space",
+            Key::CapsLock => "CapsLock",
+            Key::F1 => "F1",
+            Key::F2 => "F2",
+            Key::F3 => "F3",
+            Key::F4 => "F4",
+            Key::F5 => "F5",
+            Key::F6 => "F6",
+            Key::F7 => "F7",
+            Key::F8 => "F8",
+            Key::F9 => "F9",
+            Key::F10 => "F10",
+            Key::Pause => "Pause",
+            Key::ScrollLock => "ScrollLock",
+            Key::Numpad7 => "Numpad7",
+            Key::Numpad8 => "Numpad8",
+            Key::Numpad9 => "Numpad9",
+            Key::NumpadSubtract => "NumpadSubtract",
+            Key::Numpad4 => "Numpad4",
+            Key::Numpad5 => "Numpad5",
+            Key::Numpad6 => "Numpad6",
+            Key::NumpadAdd => "NumpadAdd",
+            Key::Numpad1 => "Numpad1",
+            Key::Numpad2 => "Numpad2",
+            Key::Numpad3 => "Numpad3",
+            Key::Numpad0 => "Numpad0",
+            Key::NumpadDecimal => "NumpadDecimal",
+            Key::IntlBackslash => "IntlBackslash",
+            Key::F11 => "F11",
+            Key::F12 => "F12",
+            Key::F13 => "F13",
+            Key::F14 => "F14",
+            Key::F15 => "F15",
+            Key::F16 => "F16",
+            Key::F17 => "F17",
+            Key::F18 => "F18",
+            Key::F19 => "F19",
+            Key::F20 => "F20",
+            Key::F21 => "F21",
+            Key::F22 => "F22",
+            Key::F23 => "F23",
+            Key::F24 => "F24",
+            Key::PrevTrack => "PrevTrack",
+            Key::NextTrack => "NextTrack",
+            Key::NumpadEnter => "NumpadEnter",
+            Key::RightCtrl => "RightCtrl",
+            Key::Mute => "Mute",
+            Key::Calculator => "Calculator",
+            Key::PlayPause => "PlayPause",
+            Key::MediaStop => "MediaStop",
+            Key::VolumeDown => "VolumeDown",
+            Key::VolumeUp => "VolumeUp",
+            Key::WebHome => "WebHome",
+            Key::NumpadDivide => "NumpadDivide",
+            Key::PrintScreen => "PrintScreen",
+            Key::RightAlt => "RightAlt",
+            Key::NumLock => "NumLock",
+            Key::Home => "Home",
+            Key::Up => "Up",
+            Key::PageUp => "PageUp",
+            Key::Left => "Left",
+            Key::Right => "Right",
+            Key::End => "End",
+            Key::Down => "Down",
+            Key::PageDown => "PageDown",
+            Key::Insert => "Insert",
+            Key::Delete => "Delete",
+            Key::Meta => "Meta",
+            Key::RightMeta => "RightMeta",
+            Key::Menu => "Menu",
+        };
+    }
+    /// Return key with a name
+    pub fn from_name(name: &str) -> Option<Key> {
+        return KEYS.iter().find(|key| key.name() == name).copied();
+    }
+    /// Return key with a scancode
+    pub fn from_scancode(scancode: u32) -> Option<Key> {
+        return KEYS.iter().find(|key| **key as u32 == scancode).copied();
+    }
+
+    /// Return layout-aware key produced by this physical key in the US QWERTY layout
+    pub fn default_key_code(&self) -> KeyCode {
+        return match self {
+            Key::Plus => KeyCode::Equals,
+            _ => KeyCode::from_name(self.name()).unwrap_or(KeyCode::Unlabeled),
+        };
+    }
+}
+
+/// Different mouse buttons
+pub enum MouseButton {
+    Left = 1,
+    Right = 2,
+    Middle = 3,
+    Back = 4,
+    Forward = 5,
+}
+
+impl Copy for MouseButton {}
+
+impl Clone for MouseButton {
+    fn clone(&self) -> MouseButton {
+        *self
+    }
+}
+
+impl PartialEq for MouseButton {
+    fn eq(&self, other: &MouseButton) -> bool {
+        return *self as u32 == *other as u32;
+    }
+}
+
+// Every mouse button, used to look mouse buttons up by name
+const MOUSE_BUTTONS: [MouseButton; 5] = [
+    MouseButton::Left,
+    MouseButton::Right,
+    MouseButton::Middle,
+    MouseButton::Back,
+    MouseButton::Forward,
+];
+
+impl MouseButton {
+    /// Return every mouse button
+    pub fn all() -> &'static [MouseButton] {
+        return &MOUSE_BUTTONS;
+    }
+
+    /// Return name of mouse button, as used in binding files
+    pub fn name(&self) -> &'static str {
+        return match self {
+            MouseButton::Left => "Left",
+            MouseButton::Right => "Right",
+            MouseButton::Middle => "Middle",
+            MouseButton::Back => "Back",
+            MouseButton::Forward => "Forward",
+        };
+    }
+    /// Return mouse button with a name
+    pub fn from_name(name: &str) -> Option<MouseButton> {
+        return MOUSE_BUTTONS
+            .iter()
+            .find(|mouse_button| mouse_button.name() == name)
+            .copied();
+    }
+}
+
+/// Different gamepad buttons, face buttons are named by position (South is A on Xbox, Cross on PlayStation)
+pub enum GamepadButton {
+    South = 1,
+    East = 2,
+    West = 3,
+    North = 4,
+    LeftBumper = 5,
+    RightBumper = 6,
+    Select = 7,
+    Start = 8,
+    Mode = 9,
+    LeftStick = 10,
+    RightStick = 11,
+    DPadUp = 12,
+    DPadDown = 13,
+    DPadLeft = 14,
+    DPadRight = 15,
+}
+
+impl Copy for GamepadButton {}
+
+impl Clone for GamepadButton {
+    fn clone(&self) -> GamepadButton {
+        *self
+    }
+}
+
+impl PartialEq for GamepadButton {
+    fn eq(&self, other: &GamepadButton) -> bool {
+        return *self as u32 == *other as u32;
+    }
+}
+
+// Every gamepad button, used to look gamepad buttons up by name
+const GAMEPAD_BUTTONS: [GamepadButton; 15] = [
+    GamepadButton::South,
+    GamepadButton::East,
+    GamepadButton::West,
+    GamepadButton::North,
+    GamepadButton::LeftBumper,
+    GamepadButton::RightBumper,
+    GamepadButton::Select,
+    GamepadButton::Start,
+    GamepadButton::Mode,
+    GamepadButton::LeftStick,
+    GamepadButton::RightStick,
+    GamepadButton::DPadUp,
+    GamepadButton::DPadDown,
+    GamepadButton::DPadLeft,
+    GamepadButton::DPadRight,
+];
+
+impl GamepadButton {
+    /// Return every gamepad button
+    pub fn all() -> &'static [GamepadButton] {
+        return &GAMEPAD_BUTTONS;
+    }
+
+    /// Return name of gamepad button, as used in binding files
+    pub fn name(&self) -> &'static str {
+        return match self {
+            GamepadButton::South => "South",
+            GamepadButton::East => "East",
+            GamepadButton::West => "West",
+            GamepadButton::North => "North",
+            GamepadButton::LeftBumper => "LeftBumper",
+            GamepadButton::RightBumper => "RightBumper",
+            GamepadButton::Select => "Select",
+            GamepadButton::Start => "Start",
+            GamepadButton::Mode => "Mode",
+            GamepadButton::LeftStick => "LeftStick",
+            GamepadButton::RightStick => "RightStick",
+            GamepadButton::DPadUp => "DPadUp",
+            GamepadButton::DPadDown => "DPadDown",
+            GamepadButton::DPadLeft => "DPadLeft",
+            GamepadButton::DPadRight => "DPadRight",
+        };
+    }
+    /// Return gamepad button with a name
+    pub fn from_name(name: &str) -> Option<GamepadButton> {
+        return GAMEPAD_BUTTONS
+            .iter()
+            .find(|button| button.name() == name)
+            .copied();
+    }
+}
+
+// Default values for gamepad axes
+const DEFAULT_STICK_DEAD_ZONE: f32 = 0.15;
+const DEFAULT_TRIGGER_DEAD_ZONE: f32 = 0.05;
+// Axis value above which an axis bound to an action counts as held down
+const AXIS_PRESS_THRESHOLD: f32 = 0.5;
+
+impl Input {
+    /// Default constructor to initialize Input
+    pub fn new() -> Self {
+        return Self {
+            current_keys_down: Vec::new(),
+            keys_pressed: Vec::new(),
+            keys_released: Vec::new(),
+            current_key_codes_down: Vec::new(),
+            key_codes_pressed: Vec::new(),
+            key_codes_released: Vec::new(),
+            key_codes_by_scancode: HashMap::new(),
+            current_mouse_buttons_down: Vec::new(),
+            mouse_buttons_pressed: Vec::new(),
+            mouse_buttons_released: Vec::new(),
+            mouse_position: Vector2::new(),
+            mouse_delta: Vector2::zero(),
+            scroll_lines: Vector2::zero(),
+            scroll_pixels: Vector2::zero(),
+            is_mouse_entered: false,
+
+            actions: Self::default_actions(),
+            actions_down_last_frame: Vec::new(),
+            is_listening: false,
+            captured_input: None,
+
+            modifiers: Modifiers::new(),
+            text_input: Vec::new(),
+
+            gamepad_backend: None,
+            gamepads: Vec::new(),
+            gamepad_events: Vec::new(),
+            stick_dead_zone: DEFAULT_STICK_DEAD_ZONE,
+            trigger_dead_zone: DEFAULT_TRIGGER_DEAD_ZONE,
+
+            frame: 0,
+            recording: None,
+            recording_start_frame: 0,
+            playback: None,
+            playback_start_frame: 0,
+            playback_index: 0,
+            simulated_events: Vec::new(),
+            is_frame_started: false,
+        };
+    }
+
+    /// Return action map with the default horizontal (AD, Left Right arrows and left stick)
+    /// and vertical (WS, Up Down arrows and left stick) axes
+    fn default_actions() -> ActionMap {
+        let mut actions = ActionMap::new();
+        actions.bind_axis(
+            "horizontal",
+            InputSource::Key(Key::A),
+            InputSource::Key(Key::D),
+        );
+        actions.bind_axis(
+            "horizontal",
+            InputSource::Key(Key::Left),
+            InputSource::Key(Key::Right),
+        );
+        actions.bind_axis(
+            "vertical",
+            InputSource::Key(Key::S),
+            InputSource::Key(Key::W),
+        );
+        actions.bind_axis(
+            "vertical",
+            InputSource::Key(Key::Down),
+            InputSource::Key(Key::Up),
+        );
+        actions.bind_axis(
+            "horizontal",
+            InputSource::GamepadAxisNegative(GamepadAxis::LeftStickX),
+            InputSource::GamepadAxisPositive(GamepadAxis::LeftStickX),
+        );
+        actions.bind_axis(
+            "vertical",
+            InputSource::GamepadAxisNegative(GamepadAxis::LeftStickY),
+            InputSource::GamepadAxisPositive(GamepadAxis::LeftStickY),
+        );
+        return actions;
+    }
+
+    /// Return whether a key is being held down
+    pub fn is_key_down(&mut self, key: Key) -> bool {
+        return self.current_keys_down.contains(&(key as u32));
+    }
+    /// Return whether a key is NOT being held down
+    pub fn is_key_up(&mut self, key: Key) -> bool {
+        return !self.current_keys_down.contains(&(key as u32));
+    }
+
+    /// Return true for the frame a key is pressed, key repeats are not counted as presses
+    pub fn on_key_down(&mut self, key: Key) -> bool {
+        return self.keys_pressed.contains(&(key as u32));
+    }
+    /// Return true for the frame a key is released
+    pub fn on_key_up(&mut self, key: Key) -> bool {
+        return self.keys_released.contains(&(key as u32));
+    }
+
+    /// Return whether a layout-aware key is being held down
+    pub fn is_key_code_down(&self, key_code: KeyCode) -> bool {
+        return self.current_key_codes_down.contains(&key_code);
+    }
+    /// Return whether a layout-aware key is NOT being held down
+    pub fn is_key_code_up(&self, key_code: KeyCode) -> bool {
+        return !self.current_key_codes_down.contains(&key_code);
+    }
+    /// Return true for the frame a layout-aware key is pressed
+    pub fn on_key_code_down(&self, key_code: KeyCode) -> bool {
+        return self.key_codes_pressed.contains(&key_code);
+    }
+    /// Return true for the frame a layout-aware key is released
+    pub fn on_key_code_up(&self, key_code: KeyCode) -> bool {
+        return self.key_codes_released.contains(&key_code);
+    }
+
+    /// Return layout-aware key produced by a physical key in the current keyboard layout.
+    /// Layout is learned from key events, keys not pressed yet are assumed to be in the US QWERTY layout
+    pub fn key_code(&self, key: Key) -> KeyCode {
+        return match self.key_codes_by_scancode.get(&(key as u32)) {
+            Some(key_code) => *key_code,
+            None => key.default_key_code(),
+        };
+    }
+    /// Return name of a physical key in the current keyboard layout, for on-screen prompts.
+    /// The windowing library cannot query the layout, so until a key has been pressed once its US QWERTY name
+    /// is returned, e.g. Key::Q is named "Q" instead of "A" on AZERTY until Q is pressed
+    pub fn key_display_name(&self, key: Key) -> String {
+        return self.key_code(key).display_name();
+    }
+
+    /// Return value of horizontal input axis, bound to AD, Left Right arrows and left stick by default
+    pub fn get_axis_horizontal(&mut self) -> f32 {
+        return self.axis("horizontal");
+    }
+    /// Return value of vertical input axis, bound to WS, Up Down arrows and left stick by default
+    pub fn get_axis_vertical(&mut self) -> f32 {
+        return self.axis("vertical");
+    }
+
+    /// Return whether any input bound to an action is being held down
+    pub fn is_action_down(&self, action: &str) -> bool {
+        return self
+            .actions
+            .action_bindings(action)
+            .iter()
+            .any(|source| self.is_source_down(*source));
+    }
+    /// Return whether no input bound to an action is being held down
+    pub fn is_action_up(&self, action: &str) -> bool {
+        return !self.is_action_down(action);
+    }
+
+    /// Return true for the frame an action goes down, even if it is released within the same frame
+    pub fn on_action_down(&self, action: &str) -> bool {
+        return !self.was_action_down(action)
+            && self
+                .actions
+                .action_bindings(action)
+                .iter()
+                .any(|source| self.is_source_pressed(*source));
+    }
+    /// Return true for the frame an action goes up after being down
+    pub fn on_action_up(&self, action: &str) -> bool {
+        if self.is_action_down(action) {
+            return false;
+        }
+        return self.was_action_down(action)
+            || self
+                .actions
+                .action_bindings(action)
+                .iter()
+                .any(|source| self.is_source_released(*source));
+    }
+
+    /// Return analog value of an action between 0 and 1, the largest value of its inputs
+    pub fn action_value(&self, action: &str) -> f32 {
+        return self
+            .actions
+            .action_bindings(action)
+            .iter()
+            .map(|source| self.source_value(*source))
+            .fold(0., f32::max);
+    }
+
+    /// Return value of an axis between -1 and 1
+    pub fn axis(&self, axis: &str) -> f32 {
+        let value: f32 = self
+            .actions
+            .axis_bindings(axis)
+            .iter()
+            .map(|binding| {
+                self.source_value(binding.positive) - self.source_value(binding.negative)
+            })
+            .sum();
+        return value.clamp(-1.0, 1.0);
+    }
+
+    /// Return whether an input is being held down
+    pub fn is_source_down(&self, source: InputSource) -> bool {
+        return match source {
+            InputSource::Key(key) => self.current_keys_down.contains(&(key as u32)),
+            InputSource::KeyCode(key_code) => self.current_key_codes_down.contains(&key_code),
+            InputSource::MouseButton(mouse_button) => self
+                .current_mouse_buttons_down
+                .contains(&(mouse_button as u32)),
+            InputSource::GamepadButton(button) => self
+                .gamepads
+                .iter()
+                .any(|gamepad| gamepad.buttons_down.contains(&button)),
+            InputSource::GamepadAxisPositive(_) | InputSource::GamepadAxisNegative(_) => {
+                self.source_value(source) > AXIS_PRESS_THRESHOLD
+            }
+        };
+    }
+    /// Return whether an input was pressed this frame
+    pub fn is_source_pressed(&self, source: InputSource) -> bool {
+        return match source {
+            InputSource::Key(key) => self.keys_pressed.contains(&(key as u32)),
+            InputSource::KeyCode(key_code) => self.key_codes_pressed.contains(&key_code),
+            InputSource::MouseButton(mouse_button) => {
+                self.mouse_buttons_pressed.contains(&(mouse_button as u32))
+            }
+            InputSource::GamepadButton(button) => self
+                .gamepads
+                .iter()
+                .any(|gamepad| gamepad.buttons_pressed.contains(&button)),
+            InputSource::GamepadAxisPositive(axis) => self.gamepads.iter().any(|gamepad| {
+                self.axis_value(gamepad, axis, false) <= AXIS_PRESS_THRESHOLD
+                    && self.axis_value(gamepad, axis, true) > AXIS_PRESS_THRESHOLD
+            }),
+            InputSource::GamepadAxisNegative(axis) => self.gamepads.iter().any(|gamepad| {
+                -self.axis_value(gamepad, axis, false) <= AXIS_PRESS_THRESHOLD
+                    && -self.axis_value(gamepad, axis, true) > AXIS_PRESS_THRESHOLD
+            }),
+        };
+    }
+    /// Return whether an input was released this frame
+    pub fn is_source_released(&self, source: InputSource) -> bool {
+        return match source {
+            InputSource::Key(key) => self.keys_released.contains(&(key as u32)),
+            InputSource::KeyCode(key_code) => self.key_codes_released.contains(&key_code),
+            InputSource::MouseButton(mouse_button) => {
+                self.mouse_buttons_released.contains(&(mouse_button as u32))
+            }
+            InputSource::GamepadButton(button) => self
+                .gamepads
+                .iter()
+                .any(|gamepad| gamepad.buttons_released.contains(&button)),
+            InputSource::GamepadAxisPositive(axis) => self.gamepads.iter().any(|gamepad| {
+                self.axis_value(gamepad, axis, false) > AXIS_PRESS_THRESHOLD
+                    && self.axis_value(gamepad, axis, true) <= AXIS_PRESS_THRESHOLD
+            }),
+            InputSource::GamepadAxisNegative(axis) => self.gamepads.iter().any(|gamepad| {
+                -self.axis_value(gamepad, axis, false) > AXIS_PRESS_THRESHOLD
+                    && -self.axis_value(gamepad, axis, true) <= AXIS_PRESS_THRESHOLD
+            }),
+        };
+    }
+    /// Return analog value of an input between 0 and 1, digital inputs are either 0 or 1.
+    /// Gamepad inputs use the gamepad that gives the largest value
+    pub fn source_value(&self, source: InputSource) -> f32 {
+        return match source {
+            InputSource::GamepadAxisPositive(axis) => self
+                .gamepads
+                .iter()
+                .map(|gamepad| self.axis_value(gamepad, axis, true))
+                .fold(0., f32::max),
+            InputSource::GamepadAxisNegative(axis) => self
+                .gamepads
+                .iter()
+                .map(|gamepad| -self.axis_value(gamepad, axis, true))
+                .fold(0., f32::max),
+            _ => self.is_source_down(source) as i32 as f32,
+        };
+    }
+
+    /// Set backend that gamepads are read from, replacing any connected gamepads
+    pub fn set_gamepad_backend<B: GamepadBackend + 'static>(&mut self, backend: B) {
+        self.gamepad_backend = Some(Box::new(backend));
+        self.gamepads.clear();
+    }
+    /// Return whether a backend has been set to read gamepads from
+    pub fn has_gamepad_backend(&self) -> bool {
+        return self.gamepad_backend.is_some();
+    }
+
+    /// Return ids of connected gamepads in connection order
+    pub fn gamepads(&self) -> Vec<GamepadId> {
+        return self.gamepads.iter().map(|gamepad| gamepad.id).collect();
+    }
+    /// Return whether a gamepad is connected
+    pub fn is_gamepad_connected(&self, id: GamepadId) -> bool {
+        return self.gamepad(id).is_some();
+    }
+    /// Return name of a connected gamepad, if the backend knows it
+    pub fn gamepad_name(&self, id: GamepadId) -> Option<String> {
+        return self.gamepad_backend.as_ref()?.name(id);
+    }
+    /// Return gamepad events received this frame, e.g. to handle connection and disconnection
+    pub fn gamepad_events(&self) -> &[GamepadEvent] {
+        return &self.gamepad_events;
+    }
+
+    /// Return whether a gamepad button is being held down
+    pub fn is_gamepad_button_down(&self, id: GamepadId, button: GamepadButton) -> bool {
+        return match self.gamepad(id) {
+            Some(gamepad) => gamepad.buttons_down.contains(&button),
+            None => false,
+        };
+    }
+    /// Return whether a gamepad button is NOT being held down
+    pub fn is_gamepad_button_up(&self, id: GamepadId, button: GamepadButton) -> bool {
+        return !self.is_gamepad_button_down(id, button);
+    }
+    /// Return true for the frame a gamepad button is pressed
+    pub fn on_gamepad_button_down(&self, id: GamepadId, button: GamepadButton) -> bool {
+        return match self.gamepad(id) {
+            Some(gamepad) => gamepad.buttons_pressed.contains(&button),
+            None => false,
+        };
+    }
+    /// Return true for the frame a gamepad button is released
+    pub fn on_gamepad_button_up(&self, id: GamepadId, button: GamepadButton) -> bool {
+        return match self.gamepad(id) {
+            Some(gamepad) => gamepad.buttons_released.contains(&button),
+            None => false,
+        };
+    }
+
+    /// Return value of a gamepad axis with dead zone applied.
+    /// Use gamepad_stick for sticks, which applies the dead zone to both axes together
+    pub fn gamepad_axis(&self, id: GamepadId, axis: GamepadAxis) -> f32 {
+        return match self.gamepad(id) {
+            Some(gamepad) => self.axis_value(gamepad, axis, true),
+            None => 0.,
+        };
+    }
+    /// Return position of a gamepad stick with a radial dead zone applied, up and right are positive
+    pub fn gamepad_stick(&self, id: GamepadId, stick: GamepadStick) -> Vector2 {
+        let gamepad = match self.gamepad(id) {
+            Some(gamepad) => gamepad,
+            None => return Vector2::zero(),
+        };
+        let (x, y) = match stick {
+            GamepadStick::Left => (GamepadAxis::LeftStickX, GamepadAxis::LeftStickY),
+            GamepadStick::Right => (GamepadAxis::RightStickX, GamepadAxis::RightStickY),
+        };
+
+        let mut position = Vector2 {
+            x: gamepad.axes[x as usize],
+            y: gamepad.axes[y as usize],
+        };
+        let length = position.length();
+        if length <= self.stick_dead_zone {
+            return Vector2::zero();
+        }
+        return position.normalized() * gamepad::apply_dead_zone(length, self.stick_dead_zone);
+    }
+
+    /// Rumble a gamepad with strong and weak motor intensities between 0 and 1 for a duration in seconds.
+    /// Return whether the gamepad supports rumble
+    pub fn rumble(&mut self, id: GamepadId, strong: f32, weak: f32, duration: f32) -> bool {
+        return match self.gamepad_backend.as_mut() {
+            Some(backend) => backend.rumble(
+                id,
+                strong.clamp(0., 1.),
+                weak.clamp(0., 1.),
+                duration.max(0.),
+            ),
+            None => false,
+        };
+    }
+
+    /// Return state of a connected gamepad
+    fn gamepad(&self, id: GamepadId) -> Option<&GamepadState> {
+        return self.gamepads.iter().find(|gamepad| gamepad.id == id);
+    }
+    /// Return value of a gamepad axis with dead zone applied, this frame or last frame
+    fn axis_value(&self, gamepad: &GamepadState, axis: GamepadAxis, is_current: bool) -> f32 {
+        let value = if is_current {
+            gamepad.axes[axis as usize]
+        } else {
+            gamepad.previous_axes[axis as usize]
+        };
+        let dead_zone = if axis.is_trigger() {
+            self.trigger_dead_zone
+        } else {
+            self.stick_dead_zone
+        };
+        return gamepad::apply_dead_zone(value, dead_zone);
+    }
+
+    /// Return modifier keys being held down
+    pub fn modifiers(&self) -> Modifiers {
+        return self.modifiers;
+    }
+
+    /// Return text input events received this frame, in order.
+    /// Text committed with an input method editor (IME) arrives as characters,
+    /// text still being composed is not reported by the windowing library
+    pub fn text_input(&self) -> &[TextInput] {
+        return &self.text_input;
+    }
+    /// Return text committed this frame, without editing keys
+    pub fn text(&self) -> String {
+        return self
+            .text_input
+            .iter()
+            .filter_map(|input| match input {
+                TextInput::Character(character) => Some(*character),
+                TextInput::Edit(_, _) => None,
+            })
+            .collect();
+    }
+    /// Apply text input received this frame to a single-line text and a cursor position in characters
+    pub fn apply_text_input(&self, text: &mut String, cursor: &mut usize) {
+        text::apply_text_input(&self.text_input, text, cursor);
+    }
+
+    /// Start listening for the next key, mouse button or gamepad button pressed, e.g. to rebind a control.
+    /// The pressed input is returned once by captured_input
+    pub fn listen_for_input(&mut self) {
+        self.is_listening = true;
+        self.captured_input = None;
+    }
+    /// Stop listening for the next input without capturing anything
+    pub fn cancel_listening(&mut self) {
+        self.is_listening = false;
+        self.captured_input = None;
+    }
+    /// Return whether Input is listening for the next input
+    pub fn is_listening(&self) -> bool {
+        return self.is_listening;
+    }
+    /// Return the input pressed since listen_for_input was called, once
+    pub fn captured_input(&mut self) -> Option<InputSource> {
+        return self.captured_input.take();
+    }
+
+    /// Capture an input if listening for one
+    fn capture(&mut self, source: Option<InputSource>) {
+        if self.is_listening && source.is_some() {
+            self.is_listening = false;
+            self.captured_input = source;
+        }
+    }
+
+    /// Return whether an action was down at the end of last frame
+    fn was_action_down(&self, action: &str) -> bool {
+        return self
+            .actions_down_last_frame
+            .iter()
+            .any(|down| down == action);
+    }
+
+    /// Return number of frames input has been updated for
+    pub fn frame(&self) -> u64 {
+        return self.frame;
+    }
+
+    /// Start recording keyboard, text, modifier and mouse events from the window from the next frame.
+    /// Gamepads are not recorded
+    /// Start recording in init to record from the first frame
+    pub fn start_recording(&mut self) {
+        self.recording = Some(InputRecording::new());
+        self.recording_start_frame = self.frame + self.is_frame_started as u64;
+    }
+    /// Stop recording and return what was recorded, None if input was not being recorded
+    pub fn stop_recording(&mut self) -> Option<InputRecording> {
+        return self.recording.take();
+    }
+    /// Return whether input is being recorded
+    pub fn is_recording(&self) -> bool {
+        return self.recording.is_some();
+    }
+
+    /// Replay a recording from the next frame, ignoring window input until it ends.
+    /// Play in init and run headless for recording.frames() frames to reproduce a session frame-for-frame,
+    /// headless frames then run with the frame times they were recorded with
+    pub fn play_recording(&mut self, recording: InputRecording) {
+        self.playback = Some(recording);
+        self.playback_start_frame = self.frame + self.is_frame_started as u64;
+        self.playback_index = 0;
+    }
+    /// Stop replaying a recording
+    pub fn stop_playback(&mut self) {
+        self.playback = None;
+    }
+    /// Return whether a recording is being replayed
+    pub fn is_playing(&self) -> bool {
+        return self.playback.is_some();
+    }
+    /// Return frame time the next replayed frame was recorded with, None if no recording is being replayed
+    pub fn playback_frame_time(&self) -> Option<f32> {
+        let frame = self.frame + self.is_frame_started as u64 - self.playback_start_frame;
+        return self.playback.as_ref()?.frame_time(frame);
+    }
+
+    /// Simulate pressing a key, the key is held down from the next frame until simulate_key_up
+    pub fn simulate_key_down(&mut self, key: Key) {
+        self.simulate(
+            0,
+            InputEvent::KeyPressed(key as u32, Some(self.key_code(key))),
+        );
+    }
+    /// Simulate releasing a key on the next frame
+    pub fn simulate_key_up(&mut self, key: Key) {
+        self.simulate(0, InputEvent::KeyReleased(key as u32));
+    }
+    /// Simulate tapping a key, held down for the next frame only
+    pub fn simulate_key_press(&mut self, key: Key) {
+        self.simulate_key_hold(key, 1);
+    }
+    /// Simulate holding a key down for a number of frames from the next frame
+    pub fn simulate_key_hold(&mut self, key: Key, frames: u64) {
+        self.simulate_key_down(key);
+        self.simulate(frames.max(1), InputEvent::KeyReleased(key as u32));
+    }
+
+    /// Simulate pressing a mouse button, the button is held down from the next frame until simulate_mouse_button_up
+    pub fn simulate_mouse_button_down(&mut self, mouse_button: MouseButton) {
+        self.simulate(0, InputEvent::MouseButtonPressed(mouse_button));
+    }
+    /// Simulate releasing a mouse button on the next frame
+    pub fn simulate_mouse_button_up(&mut self, mouse_button: MouseButton) {
+        self.simulate(0, InputEvent::MouseButtonReleased(mouse_button));
+    }
+    /// Simulate clicking a mouse button, held down for the next frame only
+    pub fn simulate_mouse_click(&mut self, mouse_button: MouseButton) {
+        self.simulate_mouse_button_hold(mouse_button, 1);
+    }
+    /// Simulate holding a mouse button down for a number of frames from the next frame
+    pub fn simulate_mouse_button_hold(&mut self, mouse_button: MouseButton, frames: u64) {
+        self.simulate_mouse_button_down(mouse_button);
+        self.simulate(frames.max(1), InputEvent::MouseButtonReleased(mouse_button));
+    }
+    /// Simulate moving mouse cursor to a position in physical pixels on the next frame
+    pub fn simulate_mouse_move(&mut self, position: Vector2) {
+        self.simulate(0, InputEvent::MousePosition(position));
+    }
+
+    /// Cancel simulated input that has not happened yet, e.g. scheduled key releases
+    pub fn clear_simulated_input(&mut self) {
+        self.simulated_events.clear();
+    }
+    /// Return whether there is simulated input that has not happened yet
+    pub fn has_simulated_input(&self) -> bool {
+        return !self.simulated_events.is_empty();
+    }
+
+    /// Schedule a simulated event a number of frames after the next frame
+    fn simulate(&mut self, frames_after_next: u64, event: InputEvent) {
+        // Input simulated during a frame happens on the next frame, like input from the window
+        let next_frame = self.frame + self.is_frame_started as u64;
+        self.simulated_events
+            .push((next_frame + frames_after_next, event));
+    }
+
+    /// Read gamepads, apply replayed and simulated input and record frame time for this frame.
+    /// Only meant to be called internally within sidekick, at the start of every frame
+    pub fn begin_frame(&mut self, frame_time: f32) {
+        self.is_frame_started = true;
+        self.update_gamepads();
+        if let Some(recording) = self.recording.as_mut() {
+            recording.set_frame_time(self.frame - self.recording_start_frame, frame_time);
+        }
+
+        // Simulated input goes through the same path as input from the window, in the order it was scheduled
+        let frame = self.frame;
+        let (due, pending) = std::mem::take(&mut self.simulated_events)
+            .into_iter()
+            .partition(|(event_frame, _)| *event_frame <= frame);
+        self.simulated_events = pending;
+        for (_, event) in due {
+            self.receive_input_event(event);
+        }
+
+        let playback = match self.playback.take() {
+            Some(playback) => playback,
+            None => return,
+        };
+        let frame = self.frame - self.playback_start_frame;
+        while let Some((event_frame, event)) = playback.events().get(self.playback_index).copied() {
+            if event_frame > frame {
+                break;
+            }
+            self.apply_input_event(event);
+            self.playback_index += 1;
+        }
+
+        // Keep playing until the last recorded frame
+        if frame + 1 < playback.frames() {
+            self.playback = Some(playback);
+        }
+    }
+
+    /// Clear inputs pressed and released this frame and remember which actions are down.
+    /// Only meant to be called internally within sidekick, at the end of every frame
+    pub fn end_frame(&mut self) {
+        let actions_down: Vec<String> = self
+            .actions
+            .actions()
+            .into_iter()
+            .filter(|action| self.is_action_down(action))
+            .map(String::from)
+            .collect();
+        self.actions_down_last_frame = actions_down;
+
+        self.keys_pressed.clear();
+        self.keys_released.clear();
+        self.key_codes_pressed.clear();
+        self.key_codes_released.clear();
+        self.mouse_buttons_pressed.clear();
+        self.mouse_buttons_released.clear();
+        self.text_input.clear();
+        self.gamepad_events.clear();
+        for gamepad in self.gamepads.iter_mut() {
+            gamepad.buttons_pressed.clear();
+            gamepad.buttons_released.clear();
+            gamepad.previous_axes = gamepad.axes;
+        }
+        self.mouse_delta = Vector2::zero();
+        self.scroll_lines = Vector2::zero();
+        self.scroll_pixels = Vector2::zero();
+
+        self.frame += 1;
+        self.is_frame_started = false;
+        if let Some(recording) = self.recording.as_mut() {
+            recording.set_frames(self.frame - self.recording_start_frame);
+        }
+    }
+
+    /// Return whether a mouse button is being held down
+    pub fn is_mouse_button_down(&mut self, mouse_button: MouseButton) -> bool {
+        return self
+            .current_mouse_buttons_down
+            .contains(&(mouse_button as u32));
+    }
+    /// Return whether a mouse button is NOT being held down
+    pub fn is_mouse_button_up(&mut self, mouse_button: MouseButton) -> bool {
+        return !self
+            .current_mouse_buttons_down
+            .contains(&(mouse_button as u32));
+    }
+
+    /// Return true for the frame a mouse button is pressed
+    pub fn on_mouse_button_down(&mut self, mouse_button: MouseButton) -> bool {
+        return self.mouse_buttons_pressed.contains(&(mouse_button as u32));
+    }
+    /// Return true for the frame a mouse button is released
+    pub fn on_mouse_button_up(&mut self, mouse_button: MouseButton) -> bool {
+        return self.mouse_buttons_released.contains(&(mouse_button as u32));
+    }
+
+    /// Return position of mouse cursor
+    pub fn mouse_position(&mut self) -> Vector2 {
+        return self.mouse_position;
+    }
+
+    /// Return raw mouse movement this frame, not affected by cursor acceleration or screen edges.
+    /// Use for FPS-style aiming together with App::set_mouse_confined
+    pub fn mouse_delta(&self) -> Vector2 {
+        return self.mouse_delta;
+    }
+
+    /// Return mouse wheel scroll this frame in lines, reported by most mouse wheels
+    pub fn scroll_lines(&self) -> Vector2 {
+        return self.scroll_lines;
+    }
+    /// Return mouse wheel scroll this frame in pixels, reported by touchpads and precise wheels
+    pub fn scroll_pixels(&self) -> Vector2 {
+        return self.scroll_pixels;
+    }
+
+    /// Return whether mouse cursor is in game window
+    pub fn is_mouse_entered(&mut self) -> bool {
+        return self.is_mouse_entered;
+    }
+
+    /// Update keyboard input. Only meant to be called internally within sidekick
+    pub fn update_keyboard_input(&mut self, input: KeyboardInput) {
+        if input.state == ElementState::Pressed {
+            let key_code = input.virtual_keycode.map(key_code::from_virtual_key_code);
+            self.receive_input_event(InputEvent::KeyPressed(input.scancode, key_code));
+        } else {
+            self.receive_input_event(InputEvent::KeyReleased(input.scancode));
+        };
+    }
+
+    /// Update mouse button input. Only meant to be called internally within sidekick
+    pub fn update_mouse_button_input(
+        &mut self,
+        state: ElementState,
+        button: glutin::event::MouseButton,
+    ) {
+        let mouse_button = match button {
+            glutin::event::MouseButton::Left => MouseButton::Left,
+            glutin::event::MouseButton::Right => MouseButton::Right,
+            glutin::event::MouseButton::Middle => MouseButton::Middle,
+            glutin::event::MouseButton::Other(button) => match extra_mouse_button(button) {
+                Some(mouse_button) => mouse_button,
+                None => return,
+            },
+        };
+
+        if state == ElementState::Pressed {
+            self.receive_input_event(InputEvent::MouseButtonPressed(mouse_button));
+        } else {
+            self.receive_input_event(InputEvent::MouseButtonReleased(mouse_button));
+        };
+    }
+
+    /// Record an input event from the window and apply it, unless a recording is being replayed
+    fn receive_input_event(&mut self, event: InputEvent) {
+        if self.is_playing() {
+            return;
+        }
+        // Recording started during a frame only records from the next frame
+        if let Some(recording) = self.recording.as_mut() {
+            if let Some(frame) = self.frame.checked_sub(self.recording_start_frame) {
+                recording.push(frame, event);
+            }
+        }
+        self.apply_input_event(event);
+    }
+
+    /// Apply an input event to input state
+    fn apply_input_event(&mut self, event: InputEvent) {
+        match event {
+            InputEvent::KeyPressed(scancode, key_code) => {
+                // Editing keys are reported on every key repeat
+                if let Some(edit_key) = key_code.and_then(text::edit_key) {
+                    self.text_input
+                        .push(TextInput::Edit(edit_key, self.modifiers));
+                }
+                self.press_key(scancode, key_code);
+            }
+            InputEvent::KeyReleased(scancode) => self.release_key(scancode),
+            InputEvent::MouseButtonPressed(mouse_button) => self.press_mouse_button(mouse_button),
+            InputEvent::MouseButtonReleased(mouse_button) => {
+                self.release_mouse_button(mouse_button)
+            }
+            InputEvent::MousePosition(position) => self.mouse_position = position,
+            InputEvent::MouseMotion(delta) => self.mouse_delta += delta,
+            InputEvent::MouseWheelLines(delta) => self.scroll_lines += delta,
+            InputEvent::MouseWheelPixels(delta) => self.scroll_pixels += delta,
+            InputEvent::Character(character) => {
+                self.text_input.push(TextInput::Character(character))
+            }
+            InputEvent::ModifiersChanged(modifiers) => self.modifiers = modifiers,
+        }
+    }
+
+    /// Record a key press and the layout-aware key it produces, repeated presses of a held key are ignored
+    fn press_key(&mut self, scancode: u32, key_code: Option<KeyCode>) {
+        if self.current_keys_down.contains(&scancode) {
+            return;
+        }
+        self.capture(match Key::from_scancode(scancode) {
+            Some(key) => Some(InputSource::Key(key)),
+            None => key_code.map(InputSource::KeyCode),
+        });
+        self.current_keys_down.push(scancode);
+        if !self.keys_pressed.contains(&scancode) {
+            self.keys_pressed.push(scancode);
+        }
+
+        if let Some(key_code) = key_code {
+            self.key_codes_by_scancode.insert(scancode, key_code);
+            if !self.current_key_codes_down.contains(&key_code) {
+                self.current_key_codes_down.push(key_code);
+            }
+            if !self.key_codes_pressed.contains(&key_code) {
+                self.key_codes_pressed.push(key_code);
+            }
+        }
+    }
+    /// Record a key release, releasing the layout-aware key it produced when pressed
+    fn release_key(&mut self, scancode: u32) {
+        if !self.current_keys_down.contains(&scancode) {
+            return;
+        }
+        self.current_keys_down.retain(|code| *code != scancode);
+        if !self.keys_released.contains(&scancode) {
+            self.keys_released.push(scancode);
+        }
+
+        if let Some(key_code) = self.key_codes_by_scancode.get(&scancode).copied() {
+            // Another held physical key can produce the same key code
+            let is_still_down = self
+                .current_keys_down
+                .iter()
+                .any(|code| self.key_codes_by_scancode.get(code).copied() == Some(key_code));
+            if !is_still_down {
+                self.current_key_codes_down.retain(|down| *down != key_code);
+                if !self.key_codes_released.contains(&key_code) {
+                    self.key_codes_released.push(key_code);
+                }
+            }
+        }
+    }
+
+    /// Record a mouse button press
+    fn press_mouse_button(&mut self, mouse_button: MouseButton) {
+        let button = mouse_button as u32;
+        if self.current_mouse_buttons_down.contains(&button) {
+            return;
+        }
+        self.capture(Some(InputSource::MouseButton(mouse_button)));
+        self.current_mouse_buttons_down.push(button);
+        if !self.mouse_buttons_pressed.contains(&button) {
+            self.mouse_buttons_pressed.push(button);
+        }
+    }
+    /// Record a mouse button release
+    fn release_mouse_button(&mut self, mouse_button: MouseButton) {
+        let button = mouse_button as u32;
+        if !self.current_mouse_buttons_down.contains(&button) {
+            return;
+        }
+        self.current_mouse_buttons_down
+            .retain(|code| *code != button);
+        if !self.mouse_buttons_released.contains(&button) {
+            self.mouse_buttons_released.push(button);
+        }
+    }
+
+    /// Update text input with a received character. Only meant to be called internally within sidekick
+    pub fn update_text_input(&mut self, character: char) {
+        // Control characters are reported as editing keys instead
+        if !character.is_control() {
+            self.receive_input_event(InputEvent::Character(character));
+        }
+    }
+
+    /// Update modifier keys input. Only meant to be called internally within sidekick
+    pub fn update_modifiers_input(&mut self, modifiers: ModifiersState) {
+        self.receive_input_event(InputEvent::ModifiersChanged(Modifiers {
+            shift: modifiers.shift(),
+            ctrl: modifiers.ctrl(),
+            alt: modifiers.alt(),
+            logo: modifiers.logo(),
+        }));
+    }
+
+    /// Update mouse position input. Only meant to be called internally within sidekick
+    pub fn update_mouse_position_input(&mut self, position: PhysicalPosition<f64>) {
+        self.receive_input_event(InputEvent::MousePosition(Vector2 {
+            x: position.x as f32,
+            y: position.y as f32,
+        }));
+    }
+
+    /// Poll gamepad backend for events
+    fn update_gamepads(&mut self) {
+        let events = match self.gamepad_backend.as_mut() {
+            Some(backend) => backend.poll_events(),
+            None => return,
+        };
+        for event in events {
+            self.update_gamepad_input(event);
+        }
+    }
+
+    /// Update gamepad input with a single event
+    fn update_gamepad_input(&mut self, event: GamepadEvent) {
+        match event {
+            GamepadEvent::Connected(id) => {
+                if self.gamepad(id).is_none() {
+                    self.gamepads.push(GamepadState::new(id));
+                }
+            }
+            GamepadEvent::Disconnected(id) => self.gamepads.retain(|gamepad| gamepad.id != id),
+            GamepadEvent::ButtonPressed(id, button) => {
+                let is_pressed = match self.gamepads.iter_mut().find(|gamepad| gamepad.id == id) {
+                    Some(gamepad) if !gamepad.buttons_down.contains(&button) => {
+                        gamepad.buttons_down.push(button);
+                        gamepad.buttons_pressed.push(button);
+                        true
+                    }
+                    _ => false,
+                };
+                if is_pressed {
+                    self.capture(Some(InputSource::GamepadButton(button)));
+                }
+            }
+            GamepadEvent::ButtonReleased(id, button) => {
+                if let Some(gamepad) = self.gamepads.iter_mut().find(|gamepad| gamepad.id == id) {
+                    if gamepad.buttons_down.contains(&button) {
+                        gamepad.buttons_down.retain(|down| *down != button);
+                        gamepad.buttons_released.push(button);
+                    }
+                }
+            }
+            GamepadEvent::AxisChanged(id, axis, value) => {
+                if let Some(gamepad) = self.gamepads.iter_mut().find(|gamepad| gamepad.id == id) {
+                    let range = if axis.is_trigger() { 0. } else { -1. };
+                    gamepad.axes[axis as usize] = value.clamp(range, 1.);
+                }
+            }
+        }
+        self.gamepad_events.push(event);
+    }
+
+    /// Update raw mouse motion input. Only meant to be called internally within sidekick
+    pub fn update_mouse_motion_input(&mut self, delta: (f64, f64)) {
+        self.receive_input_event(InputEvent::MouseMotion(Vector2 {
+            x: delta.0 as f32,
+            y: delta.1 as f32,
+        }));
+    }
+
+    /// Update mouse wheel input. Only meant to be called internally within sidekick
+    pub fn update_mouse_wheel_input(&mut self, delta: MouseScrollDelta) {
+        let event = match delta {
+            MouseScrollDelta::LineDelta(x, y) => InputEvent::MouseWheelLines(Vector2 { x, y }),
+            MouseScrollDelta::PixelDelta(position) => InputEvent::MouseWheelPixels(Vector2 {
+                x: position.x as f32,
+                y: position.y as f32,
+            }),
+        };
+        self.receive_input_event(event);
+    }
+
+    /// Update mouse entered/exit input. Only meant to be called internally within sidekick
+    pub fn update_mouse_entered_input(&mut self, is_mouse_entered: bool) {
+        self.is_mouse_entered = is_mouse_entered;
+    }
+}
+
+/// Return back or forward mouse button from a platform-specific extra button number
+fn extra_mouse_button(button: u16) -> Option<MouseButton> {
+    let (back, forward): (&[u16], &[u16]) = if cfg!(target_os = "windows") {
+        (&[1], &[2])
+    } else if cfg!(target_os = "macos") {
+        (&[3], &[4])
+    } else {
+        // X11 button numbers and Wayland evdev codes
+        (&[8, 275], &[9, 276])
+    };
+
+    if back.contains(&button) {
+        return Some(MouseButton::Back);
+    }
+    if forward.contains(&button) {
+        return Some(MouseButton::Forward);
+    }
+    return None;
+}
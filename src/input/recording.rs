@@ -0,0 +1,596 @@
+use crate::input::key_code::KeyCode;
+use crate::input::{Modifiers, MouseButton};
+use crate::types::vector2::Vector2;
+
+use std::convert::TryFrom;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// Recording files start with a magic number followed by the format version
+const MAGIC: &[u8; 4] = b"SKIR";
+const VERSION: u8 = 2;
+
+// Tags of events in recording files
+const TAG_KEY_PRESSED: u8 = 0;
+const TAG_KEY_RELEASED: u8 = 1;
+const TAG_MOUSE_BUTTON_PRESSED: u8 = 2;
+const TAG_MOUSE_BUTTON_RELEASED: u8 = 3;
+const TAG_MOUSE_POSITION: u8 = 4;
+const TAG_MOUSE_MOTION: u8 = 5;
+const TAG_MOUSE_WHEEL_LINES: u8 = 6;
+const TAG_MOUSE_WHEEL_PIXELS: u8 = 7;
+const TAG_CHARACTER: u8 = 8;
+const TAG_MODIFIERS_CHANGED: u8 = 9;
+
+// Bits of modifier keys in recording files
+const MODIFIER_SHIFT: u8 = 1;
+const MODIFIER_CTRL: u8 = 2;
+const MODIFIER_ALT: u8 = 4;
+const MODIFIER_LOGO: u8 = 8;
+
+/// A raw input event as received from the window, before it is applied to Input
+pub enum InputEvent {
+    /// A physical key is pressed, repeated while the key is held down
+    KeyPressed(u32, Option<KeyCode>),
+    /// A physical key is released
+    KeyReleased(u32),
+    MouseButtonPressed(MouseButton),
+    MouseButtonReleased(MouseButton),
+    /// Mouse cursor moved to a position in physical pixels
+    MousePosition(Vector2),
+    /// Raw mouse motion, not limited by the window or screen edges
+    MouseMotion(Vector2),
+    /// Mouse wheel scrolled by lines
+    MouseWheelLines(Vector2),
+    /// Mouse wheel scrolled by pixels, e.g. on touchpads
+    MouseWheelPixels(Vector2),
+    /// A character is typed, control characters are not sent
+    Character(char),
+    /// Modifier keys held down changed
+    ModifiersChanged(Modifiers),
+}
+
+impl Copy for InputEvent {}
+
+impl Clone for InputEvent {
+    fn clone(&self) -> InputEvent {
+        *self
+    }
+}
+
+/// Input events recorded with the frame they were received on, to replay a session frame-for-frame
+pub struct InputRecording {
+    events: Vec<(u64, InputEvent)>,
+    frames: u64,
+    frame_times: Vec<f32>,
+}
+
+impl Clone for InputRecording {
+    fn clone(&self) -> InputRecording {
+        return InputRecording {
+            events: self.events.clone(),
+            frames: self.frames,
+            frame_times: self.frame_times.clone(),
+        };
+    }
+}
+
+impl InputRecording {
+    /// Default constructor to initialize an empty recording
+    pub fn new() -> Self {
+        return Self {
+            events: Vec::new(),
+            frames: 0,
+            frame_times: Vec::new(),
+        };
+    }
+
+    /// Return recorded events with the frame they were received on, counted from the start of the recording
+    pub fn events(&self) -> &[(u64, InputEvent)] {
+        return &self.events;
+    }
+    /// Return number of frames recorded, including frames without input
+    pub fn frames(&self) -> u64 {
+        return self.frames;
+    }
+    /// Return frame time a frame was recorded with, None if it was not recorded
+    pub fn frame_time(&self, frame: u64) -> Option<f32> {
+        return self.frame_times.get(frame as usize).copied();
+    }
+
+    /// Add an event received on a frame
+    pub(crate) fn push(&mut self, frame: u64, event: InputEvent) {
+        self.events.push((frame, event));
+        self.frames = self.frames.max(frame + 1);
+    }
+    /// Set number of frames recorded, frames without input still count
+    pub(crate) fn set_frames(&mut self, frames: u64) {
+        self.frames = self.frames.max(frames);
+    }
+
+    /// Set frame time a frame was recorded with, earlier frames without a frame time get the same one
+    pub(crate) fn set_frame_time(&mut self, frame: u64, frame_time: f32) {
+        let frame = frame as usize;
+        if self.frame_times.len() <= frame {
+            self.frame_times.resize(frame + 1, frame_time);
+        }
+        self.frame_times[frame] = frame_time;
+    }
+
+    /// Encode recording in a compact binary format
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(16 + self.frame_times.len() * 4 + self.events.len() * 4);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        write_varint(&mut bytes, self.frames);
+        write_varint(&mut bytes, self.frame_times.len() as u64);
+        for frame_time in self.frame_times.iter() {
+            bytes.extend_from_slice(&frame_time.to_le_bytes());
+        }
+        write_varint(&mut bytes, self.events.len() as u64);
+
+        // Frames are stored as the difference to the previous event, which is usually 0 or small
+        let mut previous_frame = 0;
+        for (frame, event) in self.events.iter() {
+            write_varint(&mut bytes, frame - previous_frame);
+            previous_frame = *frame;
+
+            match *event {
+                InputEvent::KeyPressed(scancode, key_code) => {
+                    bytes.push(TAG_KEY_PRESSED);
+                    write_varint(&mut bytes, scancode as u64);
+                    // Key codes are stored by name so recordings survive new key codes, empty means no key code
+                    let name = key_code.map_or("", |key_code| key_code.name());
+                    write_varint(&mut bytes, name.len() as u64);
+                    bytes.extend_from_slice(name.as_bytes());
+                }
+                InputEvent::KeyReleased(scancode) => {
+                    bytes.push(TAG_KEY_RELEASED);
+                    write_varint(&mut bytes, scancode as u64);
+                }
+                InputEvent::MouseButtonPressed(mouse_button) => {
+                    bytes.push(TAG_MOUSE_BUTTON_PRESSED);
+                    bytes.push(mouse_button as u8);
+                }
+                InputEvent::MouseButtonReleased(mouse_button) => {
+                    bytes.push(TAG_MOUSE_BUTTON_RELEASED);
+                    bytes.push(mouse_button as u8);
+                }
+                InputEvent::MousePosition(position) => {
+                    bytes.push(TAG_MOUSE_POSITION);
+                    write_vector2(&mut bytes, position);
+                }
+                InputEvent::MouseMotion(delta) => {
+                    bytes.push(TAG_MOUSE_MOTION);
+                    write_vector2(&mut bytes, delta);
+                }
+                InputEvent::MouseWheelLines(delta) => {
+                    bytes.push(TAG_MOUSE_WHEEL_LINES);
+                    write_vector2(&mut bytes, delta);
+                }
+                InputEvent::MouseWheelPixels(delta) => {
+                    bytes.push(TAG_MOUSE_WHEEL_PIXELS);
+                    write_vector2(&mut bytes, delta);
+                }
+                InputEvent::Character(character) => {
+                    bytes.push(TAG_CHARACTER);
+                    write_varint(&mut bytes, character as u64);
+                }
+                InputEvent::ModifiersChanged(modifiers) => {
+                    bytes.push(TAG_MODIFIERS_CHANGED);
+                    bytes.push(
+                        (modifiers.shift as u8 * MODIFIER_SHIFT)
+                            | (modifiers.ctrl as u8 * MODIFIER_CTRL)
+                            | (modifiers.alt as u8 * MODIFIER_ALT)
+                            | (modifiers.logo as u8 * MODIFIER_LOGO),
+                    );
+                }
+            }
+        }
+        return bytes;
+    }
+
+    /// Decode recording from bytes written by to_bytes
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<InputRecording> {
+        let mut reader = Reader { bytes, position: 0 };
+        if reader.read_bytes(4)? != MAGIC {
+            return Err(invalid_data("not an input recording"));
+        }
+        let version = reader.read_u8()?;
+        if version != VERSION {
+            return Err(invalid_data(format!(
+                "unsupported input recording version {}",
+                version
+            )));
+        }
+
+        let frames = reader.read_varint()?;
+        let mut recording = InputRecording::new();
+        let frame_time_count = reader.read_varint()?;
+        for frame in 0..frame_time_count {
+            recording.set_frame_time(frame, reader.read_f32()?);
+        }
+        let count = reader.read_varint()?;
+        let mut frame = 0;
+        for _ in 0..count {
+            frame += reader.read_varint()?;
+            let event = match reader.read_u8()? {
+                TAG_KEY_PRESSED => {
+                    let scancode = reader.read_varint()? as u32;
+                    let key_code = reader.read_key_code()?;
+                    InputEvent::KeyPressed(scancode, key_code)
+                }
+                TAG_KEY_RELEASED => InputEvent::KeyReleased(reader.read_varint()? as u32),
+                TAG_MOUSE_BUTTON_PRESSED => {
+                    InputEvent::MouseButtonPressed(reader.read_mouse_button()?)
+                }
+                TAG_MOUSE_BUTTON_RELEASED => {
+                    InputEvent::MouseButtonReleased(reader.read_mouse_button()?)
+                }
+                TAG_MOUSE_POSITION => InputEvent::MousePosition(reader.read_vector2()?),
+                TAG_MOUSE_MOTION => InputEvent::MouseMotion(reader.read_vector2()?),
+                TAG_MOUSE_WHEEL_LINES => InputEvent::MouseWheelLines(reader.read_vector2()?),
+                TAG_MOUSE_WHEEL_PIXELS => InputEvent::MouseWheelPixels(reader.read_vector2()?),
+                TAG_CHARACTER => {
+                    let code = reader.read_varint()?;
+                    let character = u32::try_from(code)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| invalid_data(format!("invalid character {}", code)))?;
+                    InputEvent::Character(character)
+                }
+                TAG_MODIFIERS_CHANGED => {
+                    let bits = reader.read_u8()?;
+                    InputEvent::ModifiersChanged(Modifiers {
+                        shift: bits & MODIFIER_SHIFT != 0,
+                        ctrl: bits & MODIFIER_CTRL != 0,
+                        alt: bits & MODIFIER_ALT != 0,
+                        logo: bits & MODIFIER_LOGO != 0,
+                    })
+                }
+                tag => return Err(invalid_data(format!("unknown input event {}", tag))),
+            };
+            recording.push(frame, event);
+        }
+        recording.set_frames(frames);
+        return Ok(recording);
+    }
+
+    /// Save recording to a file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        if let Some(parent) = path.as_ref().parent() {
+            fs::create_dir_all(parent)?;
+        }
+        return fs::write(path, self.to_bytes());
+    }
+    /// Load recording from a file
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<InputRecording> {
+        return InputRecording::from_bytes(&fs::read(path)?);
+    }
+}
+
+/// Reads values from recording bytes
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// Read a number of bytes
+    fn read_bytes(&mut self, count: usize) -> io::Result<&'a [u8]> {
+        let end = self.position + count;
+        if end > self.bytes.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "input recording ended early",
+            ));
+        }
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+        return Ok(bytes);
+    }
+
+    /// Read a single byte
+    fn read_u8(&mut self) -> io::Result<u8> {
+        return Ok(self.read_bytes(1)?[0]);
+    }
+
+    /// Read a little-endian f32
+    fn read_f32(&mut self) -> io::Result<f32> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.read_bytes(4)?);
+        return Ok(f32::from_le_bytes(bytes));
+    }
+
+    /// Read a vector of two little-endian f32
+    fn read_vector2(&mut self) -> io::Result<Vector2> {
+        return Ok(Vector2 {
+            x: self.read_f32()?,
+            y: self.read_f32()?,
+        });
+    }
+
+    /// Read an unsigned LEB128 variable-length integer
+    fn read_varint(&mut self) -> io::Result<u64> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_u8()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        return Err(invalid_data("integer too long"));
+    }
+
+    /// Read a key code stored by name, None if it was stored without a key code
+    fn read_key_code(&mut self) -> io::Result<Option<KeyCode>> {
+        let length = self.read_varint()? as usize;
+        if length == 0 {
+            return Ok(None);
+        }
+        let name = std::str::from_utf8(self.read_bytes(length)?)
+            .map_err(|_| invalid_data("key code name is not UTF-8"))?;
+        return KeyCode::from_name(name)
+            .map(Some)
+            .ok_or_else(|| invalid_data(format!("unknown key code {}", name)));
+    }
+
+    /// Read a mouse button
+    fn read_mouse_button(&mut self) -> io::Result<MouseButton> {
+        let button = self.read_u8()?;
+        return MouseButton::all()
+            .iter()
+            .find(|mouse_button| **mouse_button as u8 == button)
+            .copied()
+            .ok_or_else(|| invalid_data(format!("unknown mouse button {}", button)));
+    }
+}
+
+/// Write an unsigned LEB128 variable-length integer
+fn write_varint(bytes: &mut Vec<u8>, value: u64) {
+    let mut value = value;
+    while value >= 0x80 {
+        bytes.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+/// Write a vector as two little-endian f32
+fn write_vector2(bytes: &mut Vec<u8>, vector: Vector2) {
+    bytes.extend_from_slice(&vector.x.to_le_bytes());
+    bytes.extend_from_slice(&vector.y.to_le_bytes());
+}
+
+/// Return an error for malformed recordings
+fn invalid_data<E: ToString>(err: E) -> io::Error {
+    return io::Error::new(io::ErrorKind::InvalidData, err.to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::App;
+    use crate::input::text::TextInput;
+    use crate::input::{Input, Key};
+
+    use glutin::dpi::PhysicalPosition;
+    use glutin::event::{ModifiersState, MouseScrollDelta};
+
+    /// Send window input that is not sent as keys, as received between frames
+    fn send_window_input(
+        input: &mut Input,
+        character: char,
+        modifiers: ModifiersState,
+        scale: f64,
+    ) {
+        input.update_text_input(character);
+        input.update_modifiers_input(modifiers);
+        input.update_mouse_motion_input((scale, -scale));
+        input.update_mouse_wheel_input(MouseScrollDelta::LineDelta(0., scale as f32));
+        input.update_mouse_wheel_input(MouseScrollDelta::PixelDelta(PhysicalPosition::new(
+            scale, 0.,
+        )));
+        input.update_mouse_position_input(PhysicalPosition::new(scale, scale));
+    }
+
+    /// Return text, modifiers and mouse input of the current frame
+    fn window_state(
+        input: &mut Input,
+    ) -> (Vec<char>, Modifiers, Vector2, Vector2, Vector2, Vector2) {
+        let characters = input
+            .text_input()
+            .iter()
+            .filter_map(|text| match text {
+                TextInput::Character(character) => Some(*character),
+                _ => None,
+            })
+            .collect();
+        return (
+            characters,
+            input.modifiers(),
+            input.mouse_delta(),
+            input.scroll_lines(),
+            input.scroll_pixels(),
+            input.mouse_position(),
+        );
+    }
+
+    /// Return whether two events are the same
+    fn is_same_event(a: &InputEvent, b: &InputEvent) -> bool {
+        return match (*a, *b) {
+            (InputEvent::KeyPressed(a, a_code), InputEvent::KeyPressed(b, b_code)) => {
+                a == b && a_code == b_code
+            }
+            (InputEvent::KeyReleased(a), InputEvent::KeyReleased(b)) => a == b,
+            (InputEvent::MouseButtonPressed(a), InputEvent::MouseButtonPressed(b)) => a == b,
+            (InputEvent::MouseButtonReleased(a), InputEvent::MouseButtonReleased(b)) => a == b,
+            (InputEvent::MousePosition(a), InputEvent::MousePosition(b)) => a == b,
+            (InputEvent::MouseMotion(a), InputEvent::MouseMotion(b)) => a == b,
+            (InputEvent::MouseWheelLines(a), InputEvent::MouseWheelLines(b)) => a == b,
+            (InputEvent::MouseWheelPixels(a), InputEvent::MouseWheelPixels(b)) => a == b,
+            (InputEvent::Character(a), InputEvent::Character(b)) => a == b,
+            (InputEvent::ModifiersChanged(a), InputEvent::ModifiersChanged(b)) => a == b,
+            _ => false,
+        };
+    }
+
+    #[test]
+    fn every_event_survives_bytes() {
+        let mut recording = InputRecording::new();
+        recording.push(0, InputEvent::KeyPressed(30, Some(KeyCode::LeftShift)));
+        recording.push(0, InputEvent::KeyPressed(31, None));
+        recording.push(2, InputEvent::KeyReleased(30));
+        recording.push(2, InputEvent::MouseButtonPressed(MouseButton::Right));
+        recording.push(3, InputEvent::MouseButtonReleased(MouseButton::Right));
+        recording.push(3, InputEvent::MousePosition(Vector2 { x: 1.5, y: -2. }));
+        recording.push(3, InputEvent::MouseMotion(Vector2 { x: 3., y: 4. }));
+        recording.push(4, InputEvent::MouseWheelLines(Vector2 { x: 0., y: -1. }));
+        recording.push(4, InputEvent::MouseWheelPixels(Vector2 { x: 12., y: 0. }));
+        recording.push(5, InputEvent::Character('é'));
+        recording.push(
+            5,
+            InputEvent::ModifiersChanged(Modifiers {
+                shift: true,
+                ctrl: false,
+                alt: true,
+                logo: false,
+            }),
+        );
+        recording.set_frames(8);
+        recording.set_frame_time(1, 0.02);
+
+        let decoded = InputRecording::from_bytes(&recording.to_bytes()).unwrap();
+        assert_eq!(decoded.frames(), 8);
+        assert_eq!(decoded.frame_time(0), Some(0.02));
+        assert_eq!(decoded.frame_time(1), Some(0.02));
+        assert_eq!(decoded.frame_time(2), None);
+        assert_eq!(decoded.events().len(), recording.events().len());
+        for ((frame, event), (decoded_frame, decoded_event)) in
+            recording.events().iter().zip(decoded.events().iter())
+        {
+            assert_eq!(frame, decoded_frame);
+            assert!(is_same_event(event, decoded_event));
+        }
+    }
+
+    #[test]
+    fn malformed_bytes_are_rejected() {
+        let bytes = InputRecording::new().to_bytes();
+        assert!(InputRecording::from_bytes(b"SKIP").is_err());
+        assert!(InputRecording::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[4] = VERSION + 1;
+        assert!(InputRecording::from_bytes(&wrong_version).is_err());
+
+        let mut unknown_event = bytes;
+        unknown_event.truncate(unknown_event.len() - 1);
+        unknown_event.extend_from_slice(&[1, 0, 255]);
+        assert!(InputRecording::from_bytes(&unknown_event).is_err());
+    }
+
+    #[test]
+    fn window_input_is_replayed_instead_of_live_input() {
+        let mut input = Input::new();
+        input.start_recording();
+        send_window_input(&mut input, 'a', ModifiersState::CTRL, 2.);
+        input.begin_frame(0.);
+        let recorded_state = window_state(&mut input);
+        input.end_frame();
+        let recording = input.stop_recording().unwrap();
+
+        // Live input received while replaying is ignored
+        let mut input = Input::new();
+        input.play_recording(InputRecording::from_bytes(&recording.to_bytes()).unwrap());
+        send_window_input(&mut input, 'b', ModifiersState::ALT, 5.);
+        input.begin_frame(0.);
+        let replayed_state = window_state(&mut input);
+
+        assert_eq!(recorded_state.0, vec!['a']);
+        assert!(recorded_state.1.ctrl && !recorded_state.1.alt);
+        assert!(recorded_state.2 == Vector2 { x: 2., y: -2. });
+        assert!(recorded_state.3 == Vector2 { x: 0., y: 2. });
+        assert!(recorded_state.4 == Vector2 { x: 2., y: 0. });
+        assert!(recorded_state.5 == Vector2 { x: 2., y: 2. });
+        assert_eq!(replayed_state.0, recorded_state.0);
+        assert!(replayed_state.1 == recorded_state.1);
+        assert!(replayed_state.2 == recorded_state.2);
+        assert!(replayed_state.3 == recorded_state.3);
+        assert!(replayed_state.4 == recorded_state.4);
+        assert!(replayed_state.5 == recorded_state.5);
+    }
+
+    /// Return whether Space is down, pressed and released this frame
+    fn space_state(app: &mut App) -> (bool, bool, bool) {
+        return (
+            app.input.is_key_down(Key::Space),
+            app.input.on_key_down(Key::Space),
+            app.input.on_key_up(Key::Space),
+        );
+    }
+
+    #[test]
+    fn recording_started_mid_frame_replays_frame_for_frame() {
+        // Start recording from an update, Space is pressed on the first recorded frame and released on the second
+        let mut recording = None;
+        let mut recorded_states = Vec::new();
+        App::new().run_headless(
+            |_app: &mut App| {},
+            |app: &mut App| match app.input.frame() {
+                2 => {
+                    app.input.start_recording();
+                    app.input.simulate_key_down(Key::Space);
+                }
+                3 => {
+                    app.input.simulate_key_up(Key::Space);
+                    recorded_states.push(space_state(app));
+                }
+                4..=5 => recorded_states.push(space_state(app)),
+                6 => recording = app.input.stop_recording(),
+                _ => {}
+            },
+            7,
+        );
+        let recording = recording.unwrap();
+        assert_eq!(recording.frames(), 3);
+        assert!(matches!(
+            recording.events()[0],
+            (0, InputEvent::KeyPressed(..))
+        ));
+        assert!(matches!(
+            recording.events()[1],
+            (1, InputEvent::KeyReleased(..))
+        ));
+
+        // Start replaying from an update, after a round trip through bytes
+        let mut replay = Some(InputRecording::from_bytes(&recording.to_bytes()).unwrap());
+        let mut replayed_states = Vec::new();
+        let mut is_playing = Vec::new();
+        App::new().run_headless(
+            |_app: &mut App| {},
+            |app: &mut App| {
+                let frame = app.input.frame();
+                if frame == 1 {
+                    app.input.play_recording(replay.take().unwrap());
+                } else if (2..=4).contains(&frame) {
+                    replayed_states.push(space_state(app));
+                    is_playing.push(app.input.is_playing());
+                }
+            },
+            6,
+        );
+
+        let expected = vec![
+            (true, true, false),
+            (false, false, true),
+            (false, false, false),
+        ];
+        assert_eq!(recorded_states, expected);
+        assert_eq!(replayed_states, expected);
+        // Playback ends once its last frame has started
+        assert_eq!(is_playing, vec![true, true, false]);
+    }
+}
@@ -0,0 +1,146 @@
+use crate::input::key_code::KeyCode;
+use crate::input::Modifiers;
+
+/// Keys used to edit text, reported with key repeats so holding Backspace keeps deleting
+pub enum EditKey {
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    Enter,
+    Tab,
+    Esc,
+}
+
+impl Copy for EditKey {}
+
+impl Clone for EditKey {
+    fn clone(&self) -> EditKey {
+        *self
+    }
+}
+
+impl PartialEq for EditKey {
+    fn eq(&self, other: &EditKey) -> bool {
+        return *self as u32 == *other as u32;
+    }
+}
+
+/// A single text input event, in the order it was received
+pub enum TextInput {
+    /// A committed character, including characters committed by an IME
+    Character(char),
+    /// An editing key pressed with modifiers held down
+    Edit(EditKey, Modifiers),
+}
+
+impl Copy for TextInput {}
+
+impl Clone for TextInput {
+    fn clone(&self) -> TextInput {
+        *self
+    }
+}
+
+/// Apply text input events to a single-line text and a cursor position in characters.
+/// Supports Backspace, Delete, Left, Right, Home and End, with Ctrl (or Alt on macOS) moving by words
+pub fn apply_text_input(inputs: &[TextInput], text: &mut String, cursor: &mut usize) {
+    let mut characters: Vec<char> = text.chars().collect();
+    *cursor = (*cursor).min(characters.len());
+
+    for input in inputs.iter() {
+        match *input {
+            TextInput::Character(character) => {
+                characters.insert(*cursor, character);
+                *cursor += 1;
+            }
+            TextInput::Edit(key, modifiers) => {
+                let by_word = modifiers.ctrl || modifiers.alt;
+                match key {
+                    EditKey::Backspace => {
+                        let start = if by_word {
+                            previous_word(&characters, *cursor)
+                        } else {
+                            cursor.saturating_sub(1)
+                        };
+                        characters.drain(start..*cursor);
+                        *cursor = start;
+                    }
+                    EditKey::Delete => {
+                        let end = if by_word {
+                            next_word(&characters, *cursor)
+                        } else {
+                            (*cursor + 1).min(characters.len())
+                        };
+                        characters.drain(*cursor..end);
+                    }
+                    EditKey::Left => {
+                        *cursor = if by_word {
+                            previous_word(&characters, *cursor)
+                        } else {
+                            cursor.saturating_sub(1)
+                        };
+                    }
+                    EditKey::Right => {
+                        *cursor = if by_word {
+                            next_word(&characters, *cursor)
+                        } else {
+                            (*cursor + 1).min(characters.len())
+                        };
+                    }
+                    EditKey::Home => *cursor = 0,
+                    EditKey::End => *cursor = characters.len(),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    *text = characters.into_iter().collect();
+}
+
+/// Return position of the start of the word before a position
+fn previous_word(characters: &[char], position: usize) -> usize {
+    let mut position = position;
+    while position > 0 && characters[position - 1].is_whitespace() {
+        position -= 1;
+    }
+    while position > 0 && !characters[position - 1].is_whitespace() {
+        position -= 1;
+    }
+    return position;
+}
+
+/// Return position of the end of the word after a position
+fn next_word(characters: &[char], position: usize) -> usize {
+    let mut position = position;
+    while position < characters.len() && characters[position].is_whitespace() {
+        position += 1;
+    }
+    while position < characters.len() && !characters[position].is_whitespace() {
+        position += 1;
+    }
+    return position;
+}
+
+/// Return editing key produced by a key code
+pub(crate) fn edit_key(key_code: KeyCode) -> Option<EditKey> {
+    return match key_code {
+        KeyCode::Backspace => Some(EditKey::Backspace),
+        KeyCode::Delete => Some(EditKey::Delete),
+        KeyCode::Left => Some(EditKey::Left),
+        KeyCode::Right => Some(EditKey::Right),
+        KeyCode::Up => Some(EditKey::Up),
+        KeyCode::Down => Some(EditKey::Down),
+        KeyCode::Home => Some(EditKey::Home),
+        KeyCode::End => Some(EditKey::End),
+        KeyCode::Enter | KeyCode::NumpadEnter => Some(EditKey::Enter),
+        KeyCode::Tab => Some(EditKey::Tab),
+        KeyCode::Esc => Some(EditKey::Esc),
+        _ => None,
+    };
+}
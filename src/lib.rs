@@ -1,8 +1,11 @@
 pub mod app;
 pub mod components;
 pub mod debug;
+pub mod ecs;
 pub mod entities;
 pub mod input;
+pub mod rendering;
+pub mod scene;
 pub mod test;
 pub mod time;
 pub mod types;
@@ -0,0 +1,720 @@
+use crate::entities::game_object::GameObject;
+use crate::entities::game_view::GameView;
+use crate::entities::registry::ObjectId;
+use crate::rendering::layers::{LayerRegistry, RenderLayer};
+use crate::rendering::resolution::ScreenTransform;
+use crate::rendering::static_geometry::{StaticBatch, StaticGeometry};
+use crate::rendering::viewport;
+use crate::types::vector2::Vector2;
+
+use rgx::core::*;
+use rgx::kit::shape2d::{Fill, Shape, Vertex};
+use rgx::kit::ZDepth;
+use rgx::math::Matrix4;
+
+use std::ops::Range;
+
+// Most copies of a shape drawn by a game view on a repeating layer, in case the repeat distance is tiny
+const MAX_REPEATS: i64 = 1024;
+
+/// A shape submitted for rendering, in world coordinates unless it is in screen space
+pub struct DrawItem {
+    pub shape: Shape,
+    pub owner: Option<ObjectId>,
+    /// Render layer shape is drawn on, which sets its draw order, parallax and repetition
+    pub layer: RenderLayer,
+    /// Smallest and largest world positions the shape covers, to skip it when out of view. None to always draw it
+    pub bounds: Option<(Vector2, Vector2)>,
+    /// Whether shape is drawn in screen pixels, unaffected by the game view
+    pub is_screen_space: bool,
+}
+
+impl Clone for DrawItem {
+    fn clone(&self) -> DrawItem {
+        return DrawItem {
+            shape: self.shape.clone(),
+            owner: self.owner,
+            layer: self.layer,
+            bounds: self.bounds,
+            is_screen_space: self.is_screen_space,
+        };
+    }
+}
+
+/// List of shapes to render on current frame.
+/// The list is cleared at the start of every frame and objects submit their shapes again when drawn,
+/// so objects can be drawn, skipped or dropped without affecting each other
+pub struct DrawList {
+    items: Vec<DrawItem>,
+}
+
+impl DrawList {
+    /// Default constructor to initialize an empty DrawList
+    pub fn new() -> Self {
+        return Self { items: Vec::new() };
+    }
+
+    /// Submit a shape in world coordinates to be rendered on current frame
+    pub fn submit(&mut self, shape: Shape) {
+        self.submit_item(DrawItem {
+            shape,
+            owner: None,
+            layer: RenderLayer::new(),
+            bounds: None,
+            is_screen_space: false,
+        });
+    }
+    /// Submit a shape drawn by a game object, it is removed if its owner is destroyed this frame
+    pub fn submit_owned(&mut self, shape: Shape, owner: Option<ObjectId>) {
+        self.submit_item(DrawItem {
+            shape,
+            owner,
+            layer: RenderLayer::new(),
+            bounds: None,
+            is_screen_space: false,
+        });
+    }
+    /// Submit a shape drawn by a game object on a render layer.
+    /// The shape is skipped when its transform is out of view, margin is how far it reaches outside, e.g. its stroke size
+    pub fn submit_object(
+        &mut self,
+        shape: Shape,
+        game_object: &GameObject,
+        layer: RenderLayer,
+        margin: f32,
+    ) {
+        let (min, max) = game_object.transform.bounds();
+        self.submit_item(DrawItem {
+            shape,
+            owner: game_object.id(),
+            layer,
+            bounds: Some((min - margin, max + margin)),
+            is_screen_space: false,
+        });
+    }
+    /// Submit a shape in screen pixels that is not affected by the game view, e.g. for overlays.
+    /// Screen pixels are virtual pixels if App has a virtual resolution
+    pub fn submit_screen(&mut self, shape: Shape) {
+        self.submit_item(DrawItem {
+            shape,
+            owner: None,
+            layer: RenderLayer::new(),
+            bounds: None,
+            is_screen_space: true,
+        });
+    }
+    /// Submit a draw item
+    pub fn submit_item(&mut self, item: DrawItem) {
+        self.items.push(item);
+    }
+
+    /// Remove every shape drawn by one of the given owners
+    pub fn remove_owned_by(&mut self, owners: &[ObjectId]) {
+        if owners.is_empty() {
+            return;
+        }
+        self.items.retain(|item| match item.owner {
+            Some(owner) => !owners.contains(&owner),
+            None => true,
+        });
+    }
+
+    /// Remove every submitted shape, is called at the start of every frame
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// Return number of submitted shapes
+    pub fn len(&self) -> usize {
+        return self.items.len();
+    }
+    /// Return whether no shape has been submitted
+    pub fn is_empty(&self) -> bool {
+        return self.items.is_empty();
+    }
+
+    /// Return submitted items in submission order
+    pub fn items(&self) -> &[DrawItem] {
+        return &self.items;
+    }
+    /// Return an iterator over submitted shapes in submission order
+    pub fn shapes(&self) -> impl Iterator<Item = &Shape> {
+        return self.items.iter().map(|item| &item.shape);
+    }
+
+    /// Return triangles of submitted shapes and static geometry in screen pixels, drawn by each game view in order.
+    /// Every game view clears its viewport to its color and draws in front of the game views before it,
+    /// shapes in screen space are drawn last in front of every game view, placed in the window by screen.
+    /// Within a game view, layers are drawn back to front by sort order and shapes out of view are skipped
+    pub fn vertices(
+        &self,
+        game_views: &[&GameView],
+        screen: ScreenTransform,
+        layers: &LayerRegistry,
+        static_geometry: &StaticGeometry,
+    ) -> Vec<Vertex> {
+        return self.collect_vertices(game_views, screen, layers, static_geometry, None);
+    }
+    /// Return triangles like vertices, leaving out game view backgrounds and static geometry drawn from GPU buffers
+    /// in a window of a size, which are drawn in their own GPU passes. Only meant to be called internally within sidekick
+    pub(crate) fn vertices_for_gpu(
+        &self,
+        game_views: &[&GameView],
+        screen: ScreenTransform,
+        layers: &LayerRegistry,
+        static_geometry: &StaticGeometry,
+        window_size: Vector2,
+    ) -> Vec<Vertex> {
+        return self.collect_vertices(
+            game_views,
+            screen,
+            layers,
+            static_geometry,
+            Some(window_size),
+        );
+    }
+
+    /// Return triangles of every game view and screen space shape,
+    /// leaving out backgrounds and static geometry drawn from GPU buffers if there is a window
+    fn collect_vertices(
+        &self,
+        game_views: &[&GameView],
+        screen: ScreenTransform,
+        layers: &LayerRegistry,
+        static_geometry: &StaticGeometry,
+        gpu_window_size: Option<Vector2>,
+    ) -> Vec<Vertex> {
+        let mut vertices = Vec::with_capacity(self.items.len() * 6 * game_views.len());
+        // Layers can be referred to by items before they are added to the registry
+        let layer_slots = self
+            .items
+            .iter()
+            .map(|item| item.layer.index() as usize + 1)
+            .max()
+            .unwrap_or(0)
+            .max(layers.len());
+        // Items are submitted again every frame, so they are grouped by layer and culled by their bounds
+        let mut layer_items: Vec<Vec<usize>> = vec![Vec::new(); layer_slots];
+        for (item_index, item) in self.items.iter().enumerate() {
+            if !item.is_screen_space {
+                layer_items[item.layer.index() as usize].push(item_index);
+            }
+        }
+        let ranks = layers.ranks();
+        // Items are only tessellated once, by the first game view that sees them
+        let mut tessellated: Vec<Option<Vec<Vertex>>> = vec![None; self.items.len()];
+
+        for (view_index, game_view) in game_views.iter().enumerate() {
+            let min = game_view.viewport_origin();
+            let max = min + game_view.viewport_size();
+            if gpu_window_size.is_none() {
+                vertices.append(&mut background(game_view, view_index, game_views.len()));
+            }
+
+            let mut triangles = Vec::new();
+            for (layer_index, items) in layer_items.iter().enumerate() {
+                let layer = RenderLayer::from_index(layer_index as u32);
+                if !game_view.is_layer_visible(layer) {
+                    continue;
+                }
+                let layer_view = LayerView::new(
+                    game_view,
+                    view_index,
+                    game_views.len(),
+                    layers,
+                    &ranks,
+                    layer,
+                );
+                let (visible_min, visible_max) = layer_view.visible;
+
+                // Static geometry is drawn behind shapes on the same layer and depth
+                let is_static_on_gpu = gpu_window_size.is_some_and(|window_size| {
+                    is_static_on_gpu(game_view, layers, layer, window_size)
+                });
+                if !is_static_on_gpu {
+                    let shapes = static_geometry.shapes_in_view(
+                        layer,
+                        visible_min,
+                        visible_max,
+                        layer_view.is_repeating(),
+                    );
+                    for shape in shapes {
+                        layer_view.push(&mut triangles, &shape.vertices, shape.bounds, game_view);
+                    }
+                }
+
+                for item_index in items.iter().copied() {
+                    let item = &self.items[item_index];
+                    // Items without bounds and items on repeating layers are always drawn
+                    let is_in_view = match item.bounds {
+                        Some((item_min, item_max)) if !layer_view.is_repeating() => {
+                            item_min.x <= visible_max.x
+                                && item_max.x >= visible_min.x
+                                && item_min.y <= visible_max.y
+                                && item_max.y >= visible_min.y
+                        }
+                        _ => true,
+                    };
+                    if !is_in_view {
+                        continue;
+                    }
+                    let shape = tessellated[item_index].get_or_insert_with(|| {
+                        item.shape
+                            .triangulate()
+                            .into_iter()
+                            .map(bake_rotation)
+                            .collect()
+                    });
+                    layer_view.push(&mut triangles, shape, vertex_bounds(shape), game_view);
+                }
+            }
+            viewport::clip_triangles(&triangles, min, max, &mut vertices);
+        }
+
+        let mut triangles = Vec::new();
+        for item in self.items.iter().filter(|item| item.is_screen_space) {
+            for vertex in item.shape.triangulate() {
+                let mut vertex = bake_rotation(vertex);
+                let position = screen.to_window(Vector2 {
+                    x: vertex.position.x,
+                    y: vertex.position.y,
+                });
+                vertex.position.x = position.x;
+                vertex.position.y = position.y;
+                vertex.position.z = depth(
+                    game_views.len(),
+                    game_views.len(),
+                    0.,
+                    vertex.position.z,
+                    1.,
+                );
+                triangles.push(vertex);
+            }
+        }
+        // Screen space shapes do not spill onto the bars around a virtual resolution
+        let (min, max) = screen.window_rect();
+        viewport::clip_triangles(&triangles, min, max, &mut vertices);
+        return vertices;
+    }
+}
+
+/// A pass of a frame drawn on the GPU
+pub(crate) enum GpuPass {
+    /// Backgrounds of every game view
+    Backgrounds,
+    /// Ranges of vertices of a static batch, with a transform from world coordinates to window pixels and depth
+    Static {
+        batch: usize,
+        transform: Matrix4<f32>,
+        ranges: Vec<Range<u32>>,
+    },
+    /// Triangles of shapes and static geometry drawn on the CPU
+    Shapes,
+}
+
+/// Return passes to draw a frame on the GPU in order, with static batches of static geometry.
+/// Backgrounds come first, since static geometry on the backmost layer has the same depth as them
+pub(crate) fn gpu_passes(
+    game_views: &[&GameView],
+    layers: &LayerRegistry,
+    batches: &[StaticBatch],
+    window_size: Vector2,
+) -> Vec<GpuPass> {
+    let mut passes = vec![GpuPass::Backgrounds];
+    for (view_index, game_view) in game_views.iter().enumerate() {
+        for (batch_index, batch) in batches.iter().enumerate() {
+            if !is_static_on_gpu(game_view, layers, batch.layer, window_size) {
+                continue;
+            }
+            let (visible_min, visible_max) = game_view.visible_bounds(layers.parallax(batch.layer));
+            let ranges = batch.visible_ranges(visible_min, visible_max);
+            if ranges.is_empty() {
+                continue;
+            }
+            passes.push(GpuPass::Static {
+                batch: batch_index,
+                transform: static_transform(
+                    game_view,
+                    view_index,
+                    game_views.len(),
+                    layers,
+                    batch.layer,
+                ),
+                ranges,
+            });
+        }
+    }
+    passes.push(GpuPass::Shapes);
+    return passes;
+}
+
+/// Return triangles of the backgrounds of game views, each clearing its viewport to its color.
+/// Only meant to be called internally within sidekick
+pub(crate) fn backgrounds(game_views: &[&GameView]) -> Vec<Vertex> {
+    return game_views
+        .iter()
+        .enumerate()
+        .flat_map(|(view_index, game_view)| background(game_view, view_index, game_views.len()))
+        .collect();
+}
+
+/// Return triangles of the background of a game view, at the back of its range of depths
+fn background(game_view: &GameView, view_index: usize, view_count: usize) -> Vec<Vertex> {
+    let min = game_view.viewport_origin();
+    let max = min + game_view.viewport_size();
+    let color = game_view.color;
+    return Shape::rect([min.x, min.y], [max.x, max.y])
+        .stroke(0., Rgba::TRANSPARENT)
+        .fill(Fill::solid(Rgba::new(color.r, color.g, color.b, color.a)))
+        .zdepth(ZDepth::from(depth(view_index, view_count, 0., 0., 1.)))
+        .triangulate();
+}
+
+/// Return whether static geometry on a layer is drawn from GPU buffers by a game view instead of on the CPU.
+/// The GPU does not clip to viewports, so only game views covering the whole window qualify,
+/// and repeating layers are always drawn on the CPU
+fn is_static_on_gpu(
+    game_view: &GameView,
+    layers: &LayerRegistry,
+    layer: RenderLayer,
+    window_size: Vector2,
+) -> bool {
+    let min = game_view.viewport_origin();
+    let max = min + game_view.viewport_size();
+    return game_view.is_layer_visible(layer)
+        && !is_repeating(layers, layer)
+        && min.x <= 0.
+        && min.y <= 0.
+        && max.x >= window_size.x
+        && max.y >= window_size.y;
+}
+
+/// Return matrix that transforms static geometry on a layer from world coordinates to window pixels and depth,
+/// the same way vertices are transformed on the CPU
+fn static_transform(
+    game_view: &GameView,
+    view_index: usize,
+    view_count: usize,
+    layers: &LayerRegistry,
+    layer: RenderLayer,
+) -> Matrix4<f32> {
+    let layer_view = LayerView::new(
+        game_view,
+        view_index,
+        view_count,
+        layers,
+        &layers.ranks(),
+        layer,
+    );
+    let parallax = layer_view.parallax;
+    let origin = game_view.world_to_screen_with_parallax(Vector2::zero(), parallax);
+    let x_axis = game_view.world_to_screen_with_parallax(Vector2::unit_x(), parallax) - origin;
+    let y_axis = game_view.world_to_screen_with_parallax(Vector2::unit_y(), parallax) - origin;
+    return Matrix4::new(
+        x_axis.x,
+        x_axis.y,
+        0.,
+        0.,
+        y_axis.x,
+        y_axis.y,
+        0.,
+        0.,
+        0.,
+        0.,
+        layer_view.depth_scale,
+        0.,
+        origin.x,
+        origin.y,
+        layer_view.depth_offset,
+        1.,
+    );
+}
+
+/// Return depth of a shape drawn by a game view on a layer with a rank among a number of layers.
+/// Each game view gets its own range of depths so it is drawn in front of earlier game views,
+/// which is split again between layers. Screen space shapes use a range after the last game view
+fn depth(view_index: usize, view_count: usize, rank: f32, z: f32, layer_count: f32) -> f32 {
+    let bands = view_count as f32 + 1.;
+    return (view_index as f32 + (rank + z) / layer_count) / bands;
+}
+
+/// Return whether a layer repeats on any axis
+fn is_repeating(layers: &LayerRegistry, layer: RenderLayer) -> bool {
+    let repeat = layers.repeat(layer);
+    return repeat.x > 0. || repeat.y > 0.;
+}
+
+/// How a layer is drawn by a game view
+struct LayerView {
+    parallax: Vector2,
+    repeat: Vector2,
+    /// Smallest and largest world positions of the layer on screen
+    visible: (Vector2, Vector2),
+    /// Depth of shapes is scaled and offset into the range of the layer
+    depth_scale: f32,
+    depth_offset: f32,
+}
+
+impl LayerView {
+    /// Default constructor to initialize how a game view draws a layer, with the ranks of every layer
+    fn new(
+        game_view: &GameView,
+        view_index: usize,
+        view_count: usize,
+        layers: &LayerRegistry,
+        ranks: &[u32],
+        layer: RenderLayer,
+    ) -> Self {
+        let parallax = layers.parallax(layer);
+        let rank = ranks
+            .get(layer.index() as usize)
+            .map_or(0., |rank| *rank as f32);
+        let layer_count = ranks.len().max(1) as f32;
+        let depth_offset = depth(view_index, view_count, rank, 0., layer_count);
+        return Self {
+            parallax,
+            repeat: layers.repeat(layer),
+            visible: game_view.visible_bounds(parallax),
+            depth_scale: depth(view_index, view_count, rank, 1., layer_count) - depth_offset,
+            depth_offset,
+        };
+    }
+
+    /// Return whether layer repeats on any axis
+    fn is_repeating(&self) -> bool {
+        return self.repeat.x > 0. || self.repeat.y > 0.;
+    }
+
+    /// Transform triangles of a shape in world coordinates to screen pixels of a game view,
+    /// repeating them across the screen if the layer repeats
+    fn push(
+        &self,
+        triangles: &mut Vec<Vertex>,
+        shape: &[Vertex],
+        bounds: (Vector2, Vector2),
+        game_view: &GameView,
+    ) {
+        for offset in repeat_offsets(bounds, self.repeat, self.visible) {
+            for vertex in shape.iter() {
+                let mut vertex = *vertex;
+                let position = game_view.world_to_screen_with_parallax(
+                    Vector2 {
+                        x: vertex.position.x + offset.x,
+                        y: vertex.position.y + offset.y,
+                    },
+                    self.parallax,
+                );
+                vertex.position.x = position.x;
+                vertex.position.y = position.y;
+                vertex.position.z = vertex.position.z * self.depth_scale + self.depth_offset;
+                triangles.push(vertex);
+            }
+        }
+    }
+}
+
+/// Return offsets to draw a shape with bounds at so it repeats every repeat distance across the visible area.
+/// Axes with a repeat distance of 0 are not repeated
+fn repeat_offsets(
+    bounds: (Vector2, Vector2),
+    repeat: Vector2,
+    visible: (Vector2, Vector2),
+) -> Vec<Vector2> {
+    if repeat.x <= 0. && repeat.y <= 0. {
+        return vec![Vector2::zero()];
+    }
+    let (min, max) = bounds;
+
+    let steps_x = repeat_steps(
+        repeat.x,
+        min.x,
+        max.x,
+        visible.0.x,
+        visible.1.x,
+        MAX_REPEATS,
+    );
+    // Limit copies on both axes together, so repeating on both axes stays within MAX_REPEATS
+    let max_steps_y = (MAX_REPEATS / (steps_x.end - steps_x.start).max(1)).max(1);
+    let steps_y = repeat_steps(
+        repeat.y,
+        min.y,
+        max.y,
+        visible.0.y,
+        visible.1.y,
+        max_steps_y,
+    );
+
+    let mut offsets = Vec::new();
+    for x in steps_x {
+        for y in steps_y.clone() {
+            offsets.push(Vector2 {
+                x: x as f32 * repeat.x.max(0.),
+                y: y as f32 * repeat.y.max(0.),
+            });
+        }
+    }
+    return offsets;
+}
+
+/// Return steps of a repeat distance that move a shape from min to max into a visible range, 0 only without repeat.
+/// With more than max_steps, only max_steps around the middle of the visible range are returned
+fn repeat_steps(
+    repeat: f32,
+    min: f32,
+    max: f32,
+    visible_min: f32,
+    visible_max: f32,
+    max_steps: i64,
+) -> Range<i64> {
+    if repeat <= 0. {
+        return 0..1;
+    }
+
+    // Steps are clamped before they are converted, so tiny repeat distances and huge positions do not overflow
+    let limit = (i64::MAX / 4) as f64;
+    let first = ((visible_min as f64 - max as f64) / repeat as f64)
+        .ceil()
+        .clamp(-limit, limit);
+    let last = ((visible_max as f64 - min as f64) / repeat as f64)
+        .floor()
+        .clamp(-limit, limit);
+    if first.is_nan() || last.is_nan() || first > last {
+        return 0..0;
+    }
+    if last - first < max_steps as f64 {
+        return first as i64..last as i64 + 1;
+    }
+    let start = ((first + last) / 2.).floor() as i64 - max_steps / 2;
+    return start..start + max_steps;
+}
+
+/// Return smallest and largest positions of vertices
+pub(crate) fn vertex_bounds(vertices: &[Vertex]) -> (Vector2, Vector2) {
+    let mut min = Vector2 {
+        x: f32::MAX,
+        y: f32::MAX,
+    };
+    let mut max = Vector2 {
+        x: f32::MIN,
+        y: f32::MIN,
+    };
+    for vertex in vertices.iter() {
+        min.x = min.x.min(vertex.position.x);
+        min.y = min.y.min(vertex.position.y);
+        max.x = max.x.max(vertex.position.x);
+        max.y = max.y.max(vertex.position.y);
+    }
+    return (min, max);
+}
+
+/// Return vertex with its rotation around its center applied to its position, instead of in the vertex shader
+pub(crate) fn bake_rotation(vertex: Vertex) -> Vertex {
+    let mut vertex = vertex;
+    let (sin, cos) = vertex.angle.sin_cos();
+    let x = vertex.position.x - vertex.center.x;
+    let y = vertex.position.y - vertex.center.y;
+    vertex.position.x = x * cos + y * sin + vertex.center.x;
+    vertex.position.y = -x * sin + y * cos + vertex.center.y;
+    vertex.angle = 0.;
+    return vertex;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Return bounds from smallest to largest position
+    fn area(min: (f32, f32), max: (f32, f32)) -> (Vector2, Vector2) {
+        return (
+            Vector2 { x: min.0, y: min.1 },
+            Vector2 { x: max.0, y: max.1 },
+        );
+    }
+
+    #[test]
+    fn repeat_offsets_without_repeat() {
+        let offsets = repeat_offsets(
+            area((0., 0.), (10., 10.)),
+            Vector2::zero(),
+            area((-100., -100.), (100., 100.)),
+        );
+        assert!(offsets == vec![Vector2::zero()]);
+    }
+
+    #[test]
+    fn repeat_offsets_on_single_axis() {
+        let offsets = repeat_offsets(
+            area((0., 0.), (10., 10.)),
+            Vector2 { x: 100., y: 0. },
+            area((-250., -50.), (250., 50.)),
+        );
+        let xs: Vec<f32> = offsets.iter().map(|offset| offset.x).collect();
+        assert_eq!(xs, vec![-200., -100., 0., 100., 200.]);
+        assert!(offsets.iter().all(|offset| offset.y == 0.));
+    }
+
+    #[test]
+    fn repeat_offsets_on_both_axes() {
+        let offsets = repeat_offsets(
+            area((0., 0.), (10., 10.)),
+            Vector2 { x: 100., y: 50. },
+            area((-150., -60.), (150., 60.)),
+        );
+        // 3 columns of 3 rows, each copy reaching into the visible area
+        assert_eq!(offsets.len(), 9);
+        assert!(offsets.contains(&Vector2 { x: -100., y: -50. }));
+        assert!(offsets.contains(&Vector2 { x: 100., y: 50. }));
+        assert!(!offsets.contains(&Vector2 { x: 200., y: 0. }));
+    }
+
+    #[test]
+    fn repeat_offsets_out_of_view() {
+        // A copy is needed every 1000 units, none of which reach into a visible area 10 units wide
+        let offsets = repeat_offsets(
+            area((0., 0.), (10., 10.)),
+            Vector2 { x: 1000., y: 0. },
+            area((400., 0.), (410., 10.)),
+        );
+        assert!(offsets.is_empty());
+    }
+
+    #[test]
+    fn repeat_offsets_capped_around_visible_area() {
+        let visible = area((400., -10.), (600., 10.));
+        let offsets = repeat_offsets(
+            area((0., 0.), (1., 1.)),
+            Vector2 { x: 0.01, y: 0. },
+            visible,
+        );
+        assert_eq!(offsets.len(), MAX_REPEATS as usize);
+        assert!(offsets
+            .iter()
+            .all(|offset| offset.x > 490. && offset.x < 510.));
+
+        // Copies on both axes are limited together
+        let offsets = repeat_offsets(
+            area((0., 0.), (1., 1.)),
+            Vector2 { x: 0.01, y: 0.01 },
+            visible,
+        );
+        assert!(offsets.len() <= MAX_REPEATS as usize);
+        assert!(!offsets.is_empty());
+    }
+
+    #[test]
+    fn repeat_offsets_do_not_overflow() {
+        let tiny = repeat_offsets(
+            area((0., 0.), (1., 1.)),
+            Vector2 { x: 1e-30, y: 1e-30 },
+            area((-100., -100.), (100., 100.)),
+        );
+        assert!(!tiny.is_empty() && tiny.len() <= MAX_REPEATS as usize);
+
+        let huge = repeat_offsets(
+            area((-3e38, -3e38), (3e38, 3e38)),
+            Vector2 { x: 1e-3, y: 0. },
+            area((-3e38, -1.), (3e38, 1.)),
+        );
+        assert_eq!(huge.len(), MAX_REPEATS as usize);
+    }
+}
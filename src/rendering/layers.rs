@@ -0,0 +1,168 @@
+use crate::types::vector2::Vector2;
+
+use std::fmt;
+
+/// Handle of a render layer registered with the App's LayerRegistry
+pub struct RenderLayer {
+    index: u32,
+}
+
+impl fmt::Debug for RenderLayer {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "RenderLayer({})", self.index)
+    }
+}
+
+impl Copy for RenderLayer {}
+
+impl Clone for RenderLayer {
+    fn clone(&self) -> RenderLayer {
+        *self
+    }
+}
+
+impl PartialEq for RenderLayer {
+    fn eq(&self, other: &RenderLayer) -> bool {
+        return self.index == other.index;
+    }
+}
+
+impl Eq for RenderLayer {}
+
+impl RenderLayer {
+    /// Default constructor to initialize a handle of the default layer every registry starts with
+    pub fn new() -> Self {
+        return Self { index: 0 };
+    }
+
+    /// Return index of layer in its registry, in the order layers were added
+    pub fn index(&self) -> u32 {
+        return self.index;
+    }
+    /// Return handle of a layer with an index. Only meant to be called internally within sidekick
+    pub(crate) fn from_index(index: u32) -> Self {
+        return Self { index };
+    }
+}
+
+/// How shapes on a render layer are drawn
+pub struct LayerSettings {
+    pub name: String,
+    /// Layers with a higher sort order are drawn in front, layers with the same sort order in the order they were added
+    pub sort_order: i32,
+    /// How much the layer moves with the game view on each axis, 1 follows the game view and 0 stays in place.
+    /// Values between 0 and 1 make backgrounds look further away
+    pub parallax: Vector2,
+    /// Distance in world units after which the layer repeats endlessly on each axis, 0 to not repeat
+    pub repeat: Vector2,
+}
+
+impl Clone for LayerSettings {
+    fn clone(&self) -> LayerSettings {
+        return LayerSettings {
+            name: self.name.clone(),
+            sort_order: self.sort_order,
+            parallax: self.parallax,
+            repeat: self.repeat,
+        };
+    }
+}
+
+impl LayerSettings {
+    /// Default constructor to initialize a layer that follows the game view and does not repeat
+    pub fn new(name: &str, sort_order: i32) -> Self {
+        return Self {
+            name: String::from(name),
+            sort_order,
+            parallax: Vector2::identity(),
+            repeat: Vector2::zero(),
+        };
+    }
+}
+
+/// Named render layers shapes are drawn on, sorted by their sort order
+pub struct LayerRegistry {
+    layers: Vec<LayerSettings>,
+}
+
+impl LayerRegistry {
+    /// Default constructor to initialize a registry with only the default layer
+    pub fn new() -> Self {
+        return Self {
+            layers: vec![LayerSettings::new("Default", 0)],
+        };
+    }
+
+    /// Add a layer with a name and sort order, a layer with the same name is reused and gets the new sort order
+    pub fn add(&mut self, name: &str, sort_order: i32) -> RenderLayer {
+        if let Some(layer) = self.get(name) {
+            self.layers[layer.index as usize].sort_order = sort_order;
+            return layer;
+        }
+        self.layers.push(LayerSettings::new(name, sort_order));
+        return RenderLayer {
+            index: self.layers.len() as u32 - 1,
+        };
+    }
+
+    /// Return layer with a name if it exists
+    pub fn get(&self, name: &str) -> Option<RenderLayer> {
+        return self
+            .layers
+            .iter()
+            .position(|layer| layer.name == name)
+            .map(|index| RenderLayer {
+                index: index as u32,
+            });
+    }
+
+    /// Return settings of a layer if it exists
+    pub fn settings(&self, layer: RenderLayer) -> Option<&LayerSettings> {
+        return self.layers.get(layer.index as usize);
+    }
+    /// Return mutable settings of a layer if it exists
+    pub fn settings_mut(&mut self, layer: RenderLayer) -> Option<&mut LayerSettings> {
+        return self.layers.get_mut(layer.index as usize);
+    }
+
+    /// Return parallax factor of a layer, layers that do not exist follow the game view
+    pub fn parallax(&self, layer: RenderLayer) -> Vector2 {
+        return self
+            .settings(layer)
+            .map_or(Vector2::identity(), |settings| settings.parallax);
+    }
+    /// Return repeat distance of a layer, layers that do not exist do not repeat
+    pub fn repeat(&self, layer: RenderLayer) -> Vector2 {
+        return self
+            .settings(layer)
+            .map_or(Vector2::zero(), |settings| settings.repeat);
+    }
+
+    /// Return number of layers, including the default layer
+    pub fn len(&self) -> usize {
+        return self.layers.len();
+    }
+    /// Return whether registry has no layers, which never happens as the default layer is always there
+    pub fn is_empty(&self) -> bool {
+        return self.layers.is_empty();
+    }
+
+    /// Return every layer in drawing order, back to front
+    pub fn sorted(&self) -> Vec<RenderLayer> {
+        let mut layers: Vec<RenderLayer> = (0..self.layers.len() as u32)
+            .map(|index| RenderLayer { index })
+            .collect();
+        // Stable sort keeps layers with the same sort order in the order they were added
+        layers.sort_by_key(|layer| self.layers[layer.index as usize].sort_order);
+        return layers;
+    }
+
+    /// Return position of each layer in drawing order, indexed by layer index
+    pub(crate) fn ranks(&self) -> Vec<u32> {
+        let mut ranks = vec![0; self.layers.len()];
+        for (rank, layer) in self.sorted().iter().enumerate() {
+            ranks[layer.index as usize] = rank as u32;
+        }
+        return ranks;
+    }
+}
@@ -0,0 +1,7 @@
+pub mod draw_list;
+pub mod layers;
+pub mod rasterizer;
+pub mod resolution;
+pub mod spatial_index;
+pub mod static_geometry;
+pub mod viewport;
@@ -0,0 +1,316 @@
+use crate::types::color::Color;
+use crate::types::image::Image;
+
+use rgx::kit::shape2d::{Shape, Vertex};
+
+/// A 2D point used while rasterizing triangles
+struct Point {
+    x: f32,
+    y: f32,
+}
+
+/// Software renderer that draws shapes into an in-memory RGBA image on the CPU.
+/// Shapes are tessellated the same way as on the GPU so output matches the windowed renderer
+pub struct Rasterizer {
+    width: u32,
+    height: u32,
+}
+
+impl Rasterizer {
+    /// Default constructor to initialize Rasterizer with a target image size
+    pub fn new(width: u32, height: u32) -> Self {
+        return Self { width, height };
+    }
+
+    /// Return width of rendered images
+    pub fn width(&self) -> u32 {
+        return self.width;
+    }
+    /// Return height of rendered images
+    pub fn height(&self) -> u32 {
+        return self.height;
+    }
+
+    /// Render shapes on top of a clear color and return the resulting image
+    pub fn render<'a, S>(&self, shapes: S, clear_color: Color) -> Image
+    where
+        S: IntoIterator<Item = &'a Shape>,
+    {
+        let vertices: Vec<Vertex> = shapes
+            .into_iter()
+            .flat_map(|shape| shape.triangulate())
+            .collect();
+        return self.render_vertices(&vertices, clear_color);
+    }
+
+    /// Render triangles on top of a clear color and return the resulting image
+    pub fn render_vertices(&self, vertices: &[Vertex], clear_color: Color) -> Image {
+        let mut image = Image::new(self.width, self.height);
+        let mut depth = vec![1.0f32; (self.width * self.height) as usize];
+
+        // Clear with background color
+        let clear = color_to_bytes(clear_color);
+        for pixel in image.pixels_mut().chunks_mut(4) {
+            pixel.copy_from_slice(&clear);
+        }
+
+        for triangle in vertices.chunks_exact(3) {
+            self.draw_triangle(&mut image, &mut depth, triangle);
+        }
+
+        return image;
+    }
+
+    /// Fill a single triangle, applying vertex rotation, depth testing and alpha blending like the GPU pipeline
+    fn draw_triangle(&self, image: &mut Image, depth: &mut [f32], triangle: &[Vertex]) {
+        let mut points: Vec<Point> = triangle.iter().map(rotated_position).collect();
+
+        // Depth is flipped by the orthographic projection so higher z-depth ends up in front
+        let z = -triangle[0].position.z;
+        let color = triangle[0].color;
+
+        // Make winding consistent so that inside tests have a single sign
+        let area = edge(&points[0], &points[1], &points[2]);
+        if area.is_nan() || area == 0. {
+            return;
+        }
+        if area < 0. {
+            points.swap(1, 2);
+        }
+
+        // Bounding box of triangle clipped to image
+        let min_x = points
+            .iter()
+            .map(|p| p.x)
+            .fold(f32::MAX, f32::min)
+            .floor()
+            .max(0.) as u32;
+        let min_y = points
+            .iter()
+            .map(|p| p.y)
+            .fold(f32::MAX, f32::min)
+            .floor()
+            .max(0.) as u32;
+        let max_x = points
+            .iter()
+            .map(|p| p.x)
+            .fold(f32::MIN, f32::max)
+            .ceil()
+            .max(0.) as u32;
+        let max_y = points
+            .iter()
+            .map(|p| p.y)
+            .fold(f32::MIN, f32::max)
+            .ceil()
+            .max(0.) as u32;
+        let max_x = max_x.min(self.width);
+        let max_y = max_y.min(self.height);
+
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                // Sample at pixel center
+                let p = Point {
+                    x: x as f32 + 0.5,
+                    y: y as f32 + 0.5,
+                };
+                if !is_covered(&points[1], &points[2], &p)
+                    || !is_covered(&points[2], &points[0], &p)
+                    || !is_covered(&points[0], &points[1], &p)
+                {
+                    continue;
+                }
+
+                let index = (y * self.width + x) as usize;
+                if z > depth[index] {
+                    continue;
+                }
+                depth[index] = z;
+
+                let pixel = &mut image.pixels_mut()[index * 4..index * 4 + 4];
+                blend(pixel, [color.r, color.g, color.b, color.a]);
+            }
+        }
+    }
+}
+
+/// Return vertex position after applying its rotation around its center, as the vertex shader does
+fn rotated_position(vertex: &Vertex) -> Point {
+    let (sin, cos) = vertex.angle.sin_cos();
+    let x = vertex.position.x - vertex.center.x;
+    let y = vertex.position.y - vertex.center.y;
+    return Point {
+        x: x * cos + y * sin + vertex.center.x,
+        y: -x * sin + y * cos + vertex.center.y,
+    };
+}
+
+/// Return twice the signed area of triangle (a, b, p)
+fn edge(a: &Point, b: &Point, p: &Point) -> f32 {
+    return (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x);
+}
+
+/// Return whether a point is on the inner side of edge (a, b).
+/// Points exactly on an edge only count for top and left edges so shared edges are not drawn twice
+fn is_covered(a: &Point, b: &Point, p: &Point) -> bool {
+    let w = edge(a, b, p);
+    if w != 0. {
+        return w > 0.;
+    }
+    let is_top = a.y == b.y && b.x < a.x;
+    let is_left = b.y < a.y;
+    return is_top || is_left;
+}
+
+/// Blend a source color over a destination pixel using source alpha
+fn blend(pixel: &mut [u8], source: [u8; 4]) {
+    let alpha = source[3] as f32 / 255.;
+    for i in 0..4 {
+        let value = source[i] as f32 * alpha + pixel[i] as f32 * (1. - alpha);
+        pixel[i] = value.round().clamp(0., 255.) as u8;
+    }
+}
+
+/// Convert a color to RGBA bytes
+fn color_to_bytes(color: Color) -> [u8; 4] {
+    let mut color = color;
+    let color = color.normalized();
+    return [
+        (color.r * 255.).round() as u8,
+        (color.g * 255.).round() as u8,
+        (color.b * 255.).round() as u8,
+        (color.a * 255.).round() as u8,
+    ];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rgx::kit::shape2d::vertex;
+    use rgx::kit::Rgba8;
+    use rgx::math::Point2;
+
+    const RED: Rgba8 = Rgba8 {
+        r: 255,
+        g: 0,
+        b: 0,
+        a: 255,
+    };
+    const GREEN: Rgba8 = Rgba8 {
+        r: 0,
+        g: 255,
+        b: 0,
+        a: 255,
+    };
+
+    /// Return two triangles covering a rectangle, split along its (x1, y2) - (x2, y1) diagonal
+    fn quad(x1: f32, y1: f32, x2: f32, y2: f32, z: f32, angle: f32, color: Rgba8) -> Vec<Vertex> {
+        let center = Point2::new((x1 + x2) / 2., (y1 + y2) / 2.);
+        return vec![
+            vertex(x1, y1, z, angle, center, color),
+            vertex(x2, y1, z, angle, center, color),
+            vertex(x1, y2, z, angle, center, color),
+            vertex(x2, y1, z, angle, center, color),
+            vertex(x2, y2, z, angle, center, color),
+            vertex(x1, y2, z, angle, center, color),
+        ];
+    }
+
+    /// Return RGBA of a pixel
+    fn rgba(image: &Image, x: u32, y: u32) -> [u32; 4] {
+        let pixel = image.pixel(x, y).unwrap();
+        return [pixel.r, pixel.g, pixel.b, pixel.a];
+    }
+
+    #[test]
+    fn shared_edges_are_filled_once() {
+        // Half transparent quads blend twice wherever a pixel is filled twice.
+        // Their shared edge at x = 4.5 and their diagonals go through pixel centers
+        let color = Rgba8 {
+            r: 255,
+            g: 255,
+            b: 255,
+            a: 128,
+        };
+        let mut vertices = quad(0., 0., 4.5, 4., 0., 0., color);
+        vertices.extend(quad(4.5, 0., 8., 4., 0., 0., color));
+        let image = Rasterizer::new(10, 6).render_vertices(&vertices, Color::black());
+
+        let filled = rgba(&image, 0, 0);
+        assert_eq!(filled, [128, 128, 128, 191]);
+        for y in 0..6 {
+            for x in 0..10 {
+                let expected = if x < 8 && y < 4 {
+                    filled
+                } else {
+                    [0, 0, 0, 255]
+                };
+                assert_eq!(rgba(&image, x, y), expected, "pixel ({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn higher_z_depth_stays_in_front() {
+        let rasterizer = Rasterizer::new(4, 4);
+
+        // Vertex z is the z-depth, red has the higher z-depth
+        let mut vertices = quad(0., 0., 4., 4., 0.5, 0., RED);
+        vertices.extend(quad(0., 0., 4., 4., 0., 0., GREEN));
+        let image = rasterizer.render_vertices(&vertices, Color::black());
+        assert_eq!(rgba(&image, 1, 1), [255, 0, 0, 255]);
+
+        // Equal depths draw in submission order
+        let mut vertices = quad(0., 0., 4., 4., 0., 0., RED);
+        vertices.extend(quad(0., 0., 4., 4., 0., 0., GREEN));
+        let image = rasterizer.render_vertices(&vertices, Color::black());
+        assert_eq!(rgba(&image, 1, 1), [0, 255, 0, 255]);
+
+        // Shapes behind the far plane are clipped
+        let image = rasterizer.render_vertices(&quad(0., 0., 4., 4., -2., 0., RED), Color::black());
+        assert_eq!(rgba(&image, 1, 1), [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn alpha_blends_over_destination() {
+        let color = Rgba8 {
+            r: 255,
+            g: 0,
+            b: 0,
+            a: 51,
+        };
+        let image = Rasterizer::new(2, 2)
+            .render_vertices(&quad(0., 0., 2., 2., 0., 0., color), Color::blue());
+        // 20% red over opaque blue
+        assert_eq!(rgba(&image, 0, 0), [51, 0, 204, 214]);
+
+        let transparent = Rgba8 { a: 0, ..color };
+        let image = Rasterizer::new(2, 2)
+            .render_vertices(&quad(0., 0., 2., 2., 0., 0., transparent), Color::blue());
+        assert_eq!(rgba(&image, 0, 0), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn rotation_matches_vertex_shader() {
+        // Shader rotation is mat2(c, -s, s, c) in column-major order, applied around the center
+        for &angle in [0.3f32, 1., std::f32::consts::FRAC_PI_2, 4.].iter() {
+            let (s, c) = angle.sin_cos();
+            let columns = [[c, -s], [s, c]];
+            let (x, y) = (3f32, -2f32);
+            let center = Point2::new(1., 1.);
+            let expected_x = columns[0][0] * (x - 1.) + columns[1][0] * (y - 1.) + 1.;
+            let expected_y = columns[0][1] * (x - 1.) + columns[1][1] * (y - 1.) + 1.;
+
+            let point = rotated_position(&vertex(x, y, 0., angle, center, RED));
+            assert!((point.x - expected_x).abs() < 1e-5);
+            assert!((point.y - expected_y).abs() < 1e-5);
+        }
+
+        // A horizontal bar rotated a quarter turn around its center becomes vertical
+        let bar = quad(3., 7., 13., 9., 0., std::f32::consts::FRAC_PI_2, RED);
+        let image = Rasterizer::new(16, 16).render_vertices(&bar, Color::black());
+        assert_eq!(rgba(&image, 7, 4), [255, 0, 0, 255]);
+        assert_eq!(rgba(&image, 8, 11), [255, 0, 0, 255]);
+        assert_eq!(rgba(&image, 4, 7), [0, 0, 0, 255]);
+        assert_eq!(rgba(&image, 11, 8), [0, 0, 0, 255]);
+    }
+}
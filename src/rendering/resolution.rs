@@ -0,0 +1,256 @@
+use crate::types::color::Color;
+use crate::types::vector2::Vector2;
+
+/// How a virtual resolution is scaled to fit the window
+pub enum ScalingPolicy {
+    /// Scale each axis to fill the window, distorting the aspect ratio
+    Stretch,
+    /// Scale uniformly as much as fits, adding bars on the sides (pillarbox) or top and bottom (letterbox)
+    Fit,
+    /// Scale uniformly by the largest whole number that fits, adding bars around, for crisp pixel art
+    PixelPerfect,
+    /// Scale uniformly as much as fits and show more of the world along the longer axis instead of bars
+    Expand,
+}
+
+impl Copy for ScalingPolicy {}
+
+impl Clone for ScalingPolicy {
+    fn clone(&self) -> ScalingPolicy {
+        *self
+    }
+}
+
+impl PartialEq for ScalingPolicy {
+    fn eq(&self, other: &ScalingPolicy) -> bool {
+        return *self as u8 == *other as u8;
+    }
+}
+
+/// Fixed design resolution games are laid out in, independent of the window size
+pub struct VirtualResolution {
+    /// Design size in virtual pixels
+    pub size: Vector2,
+    pub policy: ScalingPolicy,
+    /// Color of the bars around the scaled screen
+    pub bar_color: Color,
+}
+
+impl Copy for VirtualResolution {}
+
+impl Clone for VirtualResolution {
+    fn clone(&self) -> VirtualResolution {
+        *self
+    }
+}
+
+impl VirtualResolution {
+    /// Default constructor to initialize a virtual resolution with black bars
+    pub fn new(width: u32, height: u32, policy: ScalingPolicy) -> Self {
+        return Self {
+            size: Vector2 {
+                x: width as f32,
+                y: height as f32,
+            },
+            policy,
+            bar_color: Color::black(),
+        };
+    }
+
+    /// Return how virtual pixels are placed in a window of a size in physical pixels
+    pub fn screen_transform(&self, window_size: Vector2) -> ScreenTransform {
+        let size = Vector2 {
+            x: self.size.x.max(1.),
+            y: self.size.y.max(1.),
+        };
+        let fit_scale = (window_size.x / size.x)
+            .min(window_size.y / size.y)
+            .max(f32::EPSILON);
+
+        let (size, scale) = match self.policy {
+            ScalingPolicy::Stretch => (
+                size,
+                Vector2 {
+                    x: window_size.x / size.x,
+                    y: window_size.y / size.y,
+                },
+            ),
+            ScalingPolicy::Fit => (size, Vector2::identity() * fit_scale),
+            ScalingPolicy::PixelPerfect => (size, Vector2::identity() * fit_scale.floor().max(1.)),
+            ScalingPolicy::Expand => (window_size / fit_scale, Vector2::identity() * fit_scale),
+        };
+        // Center scaled screen in the window, on whole pixels so pixel art is not blurred
+        let offset = (window_size - size * scale) / 2.;
+        return ScreenTransform {
+            size,
+            scale,
+            offset: Vector2 {
+                x: offset.x.round(),
+                y: offset.y.round(),
+            },
+        };
+    }
+}
+
+/// Placement of the screen in the window, mapping virtual pixels to physical window pixels
+pub struct ScreenTransform {
+    /// Size of the screen in virtual pixels
+    pub size: Vector2,
+    /// Physical pixels per virtual pixel on each axis
+    pub scale: Vector2,
+    /// Top left corner of the screen in the window, in physical pixels
+    pub offset: Vector2,
+}
+
+impl Copy for ScreenTransform {}
+
+impl Clone for ScreenTransform {
+    fn clone(&self) -> ScreenTransform {
+        *self
+    }
+}
+
+impl ScreenTransform {
+    /// Return a transform that maps a window of a size one to one, without a virtual resolution
+    pub fn identity(window_size: Vector2) -> Self {
+        return Self {
+            size: window_size,
+            scale: Vector2::identity(),
+            offset: Vector2::zero(),
+        };
+    }
+
+    /// Convert a position in virtual pixels to physical window pixels
+    pub fn to_window(&self, position: Vector2) -> Vector2 {
+        return Vector2 {
+            x: position.x * self.scale.x + self.offset.x,
+            y: position.y * self.scale.y + self.offset.y,
+        };
+    }
+    /// Convert a position in physical window pixels to virtual pixels
+    pub fn to_virtual(&self, position: Vector2) -> Vector2 {
+        return Vector2 {
+            x: (position.x - self.offset.x) / self.scale.x.max(f32::EPSILON),
+            y: (position.y - self.offset.y) / self.scale.y.max(f32::EPSILON),
+        };
+    }
+
+    /// Return top left and bottom right corners of the screen in the window, in physical pixels
+    pub fn window_rect(&self) -> (Vector2, Vector2) {
+        return (self.to_window(Vector2::zero()), self.to_window(self.size));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICIES: [ScalingPolicy; 4] = [
+        ScalingPolicy::Stretch,
+        ScalingPolicy::Fit,
+        ScalingPolicy::PixelPerfect,
+        ScalingPolicy::Expand,
+    ];
+
+    fn vector(x: f32, y: f32) -> Vector2 {
+        return Vector2 { x, y };
+    }
+
+    fn assert_near(a: Vector2, b: Vector2) {
+        assert!(
+            (a.x - b.x).abs() < 1e-3 && (a.y - b.y).abs() < 1e-3,
+            "{} != {}",
+            a,
+            b
+        );
+    }
+
+    /// Return size, scale and offset of a 320x180 virtual resolution in a window
+    fn screen(policy: ScalingPolicy, width: f32, height: f32) -> (Vector2, Vector2, Vector2) {
+        let screen =
+            VirtualResolution::new(320, 180, policy).screen_transform(vector(width, height));
+        return (screen.size, screen.scale, screen.offset);
+    }
+
+    #[test]
+    fn stretch_fills_window() {
+        let (size, scale, offset) = screen(ScalingPolicy::Stretch, 800., 600.);
+        assert_near(size, vector(320., 180.));
+        assert_near(scale, vector(2.5, 600. / 180.));
+        assert_near(offset, Vector2::zero());
+    }
+
+    #[test]
+    fn fit_adds_letterbox_and_pillarbox_bars() {
+        // Window taller than the virtual resolution gets bars at the top and bottom
+        let (size, scale, offset) = screen(ScalingPolicy::Fit, 800., 600.);
+        assert_near(size, vector(320., 180.));
+        assert_near(scale, vector(2.5, 2.5));
+        assert_near(offset, vector(0., 75.));
+
+        // Window wider than the virtual resolution gets bars on the sides
+        let (_, scale, offset) = screen(ScalingPolicy::Fit, 1000., 450.);
+        assert_near(scale, vector(2.5, 2.5));
+        assert_near(offset, vector(100., 0.));
+    }
+
+    #[test]
+    fn pixel_perfect_scales_by_whole_numbers() {
+        let (size, scale, offset) = screen(ScalingPolicy::PixelPerfect, 800., 600.);
+        assert_near(size, vector(320., 180.));
+        assert_near(scale, vector(2., 2.));
+        assert_near(offset, vector(80., 120.));
+
+        // Bars are rounded to whole pixels
+        let (_, scale, offset) = screen(ScalingPolicy::PixelPerfect, 961., 541.);
+        assert_near(scale, vector(3., 3.));
+        assert_near(offset, vector(1., 1.));
+
+        // Windows smaller than the virtual resolution are never scaled below 1
+        let (_, scale, offset) = screen(ScalingPolicy::PixelPerfect, 200., 100.);
+        assert_near(scale, vector(1., 1.));
+        assert_near(offset, vector(-60., -40.));
+    }
+
+    #[test]
+    fn expand_shows_more_of_the_longer_axis() {
+        let (size, scale, offset) = screen(ScalingPolicy::Expand, 800., 600.);
+        assert_near(size, vector(320., 240.));
+        assert_near(scale, vector(2.5, 2.5));
+        assert_near(offset, Vector2::zero());
+
+        let (size, _, _) = screen(ScalingPolicy::Expand, 1000., 450.);
+        assert_near(size, vector(400., 180.));
+    }
+
+    #[test]
+    fn window_and_virtual_positions_round_trip() {
+        for policy in POLICIES.iter() {
+            for &(width, height) in [(800., 600.), (1000., 450.), (333., 777.)].iter() {
+                let screen = VirtualResolution::new(320, 180, *policy)
+                    .screen_transform(vector(width, height));
+                for &(x, y) in [(0., 0.), (320., 180.), (17.5, -3.), (400., 90.)].iter() {
+                    let position = vector(x, y);
+                    assert_near(screen.to_virtual(screen.to_window(position)), position);
+                }
+
+                // Screen covers the window or is centered in it with bars, offset to whole pixels
+                let (min, max) = screen.window_rect();
+                let far_bar = vector(width, height) - max;
+                assert_near(min, screen.offset);
+                assert!((far_bar.x - min.x).abs() <= 1. && (far_bar.y - min.y).abs() <= 1.);
+                assert!(min.x == min.x.round() && min.y == min.y.round());
+                if *policy != ScalingPolicy::PixelPerfect {
+                    assert!(min.x.abs() < 1e-3 || min.y.abs() < 1e-3);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn identity_maps_window_one_to_one() {
+        let screen = ScreenTransform::identity(vector(640., 480.));
+        assert_near(screen.to_window(vector(12., 34.)), vector(12., 34.));
+        assert_near(screen.window_rect().1, vector(640., 480.));
+    }
+}
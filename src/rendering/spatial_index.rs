@@ -0,0 +1,222 @@
+use crate::types::vector2::Vector2;
+
+use std::collections::HashMap;
+
+// Entries covering more cells than this are kept in a list checked by every query instead
+const MAX_CELLS_PER_ENTRY: i64 = 64;
+// Queries covering more cells than this check every entry instead of visiting cells
+const MAX_CELLS_PER_QUERY: i64 = 4096;
+
+/// Uniform grid of world space cells to find entries overlapping an area without checking every entry
+pub struct SpatialIndex {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    large: Vec<usize>,
+    bounds: HashMap<usize, (Vector2, Vector2)>,
+}
+
+impl SpatialIndex {
+    /// Default constructor to initialize an empty index with a cell size in world units
+    pub fn new(cell_size: f32) -> Self {
+        return Self {
+            cell_size: cell_size.max(f32::EPSILON),
+            cells: HashMap::new(),
+            large: Vec::new(),
+            bounds: HashMap::new(),
+        };
+    }
+
+    /// Add an entry with an id and its smallest and largest world positions, replacing an entry with the same id.
+    /// Infinite bounds are allowed for entries that should always be found
+    pub fn insert(&mut self, id: usize, min: Vector2, max: Vector2) {
+        self.remove(id);
+        match self.cell_range(min, max, MAX_CELLS_PER_ENTRY) {
+            Some((first, last)) => {
+                for x in first.0..=last.0 {
+                    for y in first.1..=last.1 {
+                        self.cells.entry((x, y)).or_default().push(id);
+                    }
+                }
+            }
+            None => self.large.push(id),
+        }
+        self.bounds.insert(id, (min, max));
+    }
+
+    /// Remove an entry, return whether it was in the index
+    pub fn remove(&mut self, id: usize) -> bool {
+        let (min, max) = match self.bounds.remove(&id) {
+            Some(bounds) => bounds,
+            None => return false,
+        };
+        match self.cell_range(min, max, MAX_CELLS_PER_ENTRY) {
+            Some((first, last)) => {
+                for x in first.0..=last.0 {
+                    for y in first.1..=last.1 {
+                        if let Some(cell) = self.cells.get_mut(&(x, y)) {
+                            cell.retain(|entry| *entry != id);
+                            if cell.is_empty() {
+                                self.cells.remove(&(x, y));
+                            }
+                        }
+                    }
+                }
+            }
+            None => self.large.retain(|entry| *entry != id),
+        }
+        return true;
+    }
+
+    /// Remove every entry
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.large.clear();
+        self.bounds.clear();
+    }
+
+    /// Return number of entries
+    pub fn len(&self) -> usize {
+        return self.bounds.len();
+    }
+    /// Return whether index has no entries
+    pub fn is_empty(&self) -> bool {
+        return self.bounds.is_empty();
+    }
+
+    /// Return smallest and largest world positions of an entry
+    pub fn bounds(&self, id: usize) -> Option<(Vector2, Vector2)> {
+        return self.bounds.get(&id).copied();
+    }
+
+    /// Return ids of entries overlapping an area, in ascending order
+    pub fn query(&self, min: Vector2, max: Vector2) -> Vec<usize> {
+        let mut found: Vec<usize> = match self.cell_range(min, max, MAX_CELLS_PER_QUERY) {
+            Some((first, last)) => {
+                let mut found = self.large.clone();
+                for x in first.0..=last.0 {
+                    for y in first.1..=last.1 {
+                        if let Some(cell) = self.cells.get(&(x, y)) {
+                            found.extend_from_slice(cell);
+                        }
+                    }
+                }
+                found
+            }
+            // Large areas are faster to check entry by entry
+            None => self.bounds.keys().copied().collect(),
+        };
+        found.sort_unstable();
+        found.dedup();
+        found.retain(|id| {
+            let (entry_min, entry_max) = self.bounds[id];
+            entry_min.x <= max.x
+                && entry_max.x >= min.x
+                && entry_min.y <= max.y
+                && entry_max.y >= min.y
+        });
+        return found;
+    }
+
+    /// Return first and last cell covered by an area, None if the area covers more cells than a limit
+    fn cell_range(
+        &self,
+        min: Vector2,
+        max: Vector2,
+        limit: i64,
+    ) -> Option<((i32, i32), (i32, i32))> {
+        let cell = |value: f32| (value / self.cell_size).floor() as f64;
+        let first = (cell(min.x), cell(min.y));
+        let last = (cell(max.x), cell(max.y));
+        let is_finite = [first.0, first.1, last.0, last.1]
+            .iter()
+            .all(|value| value.is_finite() && value.abs() < i32::MAX as f64);
+        if !is_finite || last.0 < first.0 || last.1 < first.1 {
+            return None;
+        }
+
+        let count = (last.0 - first.0 + 1.) * (last.1 - first.1 + 1.);
+        if count > limit as f64 {
+            return None;
+        }
+        return Some((
+            (first.0 as i32, first.1 as i32),
+            (last.0 as i32, last.1 as i32),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Return a position
+    fn at(x: f32, y: f32) -> Vector2 {
+        return Vector2 { x, y };
+    }
+
+    #[test]
+    fn insert_and_query() {
+        let mut index = SpatialIndex::new(10.);
+        index.insert(0, at(0., 0.), at(5., 5.));
+        index.insert(1, at(20., 20.), at(25., 25.));
+        index.insert(2, at(-15., 0.), at(-12., 30.));
+
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.query(at(-1., -1.), at(1., 1.)), vec![0]);
+        assert_eq!(index.query(at(4., 4.), at(21., 21.)), vec![0, 1]);
+        assert_eq!(index.query(at(-20., 10.), at(30., 22.)), vec![1, 2]);
+        // Areas in the same cells but not overlapping any entry find nothing
+        assert!(index.query(at(6., 6.), at(9., 9.)).is_empty());
+        // Touching edges overlap
+        assert_eq!(index.query(at(5., 5.), at(6., 6.)), vec![0]);
+    }
+
+    #[test]
+    fn insert_replaces_and_remove() {
+        let mut index = SpatialIndex::new(10.);
+        index.insert(0, at(0., 0.), at(5., 5.));
+        index.insert(0, at(100., 100.), at(105., 105.));
+        assert_eq!(index.len(), 1);
+        assert!(index.query(at(0., 0.), at(5., 5.)).is_empty());
+        assert_eq!(index.query(at(100., 100.), at(101., 101.)), vec![0]);
+        assert_eq!(index.bounds(0).map(|bounds| bounds.0.x), Some(100.));
+
+        assert!(index.remove(0));
+        assert!(!index.remove(0));
+        assert!(index.is_empty());
+        assert!(index.cells.is_empty());
+        assert!(index.query(at(100., 100.), at(101., 101.)).is_empty());
+    }
+
+    #[test]
+    fn large_entries_are_listed_separately() {
+        let mut index = SpatialIndex::new(10.);
+        // 100 by 100 cells is more than an entry may cover
+        index.insert(0, at(0., 0.), at(1000., 1000.));
+        index.insert(1, at(0., 0.), at(1., 1.));
+        let infinity = at(f32::INFINITY, f32::INFINITY);
+        index.insert(2, -infinity, infinity);
+        assert_eq!(index.large, vec![0, 2]);
+
+        assert_eq!(index.query(at(500., 500.), at(501., 501.)), vec![0, 2]);
+        assert_eq!(index.query(at(-50., -50.), at(-40., -40.)), vec![2]);
+
+        index.remove(0);
+        assert_eq!(index.large, vec![2]);
+        index.clear();
+        assert!(index.is_empty() && index.large.is_empty() && index.cells.is_empty());
+    }
+
+    #[test]
+    fn large_queries_check_every_entry() {
+        let mut index = SpatialIndex::new(1.);
+        index.insert(0, at(0., 0.), at(1., 1.));
+        index.insert(1, at(500., 500.), at(501., 501.));
+        index.insert(2, at(-500., 0.), at(-499., 1.));
+
+        // 1000 by 1000 cells is more than a query visits
+        assert_eq!(index.query(at(-1., -1.), at(999., 999.)), vec![0, 1]);
+        let infinity = at(f32::INFINITY, f32::INFINITY);
+        assert_eq!(index.query(-infinity, infinity), vec![0, 1, 2]);
+    }
+}
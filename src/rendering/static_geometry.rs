@@ -0,0 +1,276 @@
+use crate::rendering::draw_list;
+use crate::rendering::layers::RenderLayer;
+use crate::rendering::spatial_index::SpatialIndex;
+use crate::types::vector2::Vector2;
+
+use rgx::kit::shape2d::{Shape, Vertex};
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range;
+
+// Size in world units of the cells static shapes are found in
+const CELL_SIZE: f32 = 256.;
+// Size in world units of the chunks static shapes are grouped in on the GPU, drawn only when in view
+const CHUNK_SIZE: f32 = 1024.;
+
+/// Handle of a shape added to StaticGeometry.
+/// A handle stays invalid after its shape is removed, even if the slot is reused by a new shape
+pub struct StaticShapeId {
+    index: u32,
+    generation: u32,
+}
+
+impl fmt::Debug for StaticShapeId {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "StaticShapeId({}v{})",
+            self.index, self.generation
+        )
+    }
+}
+
+impl Copy for StaticShapeId {}
+
+impl Clone for StaticShapeId {
+    fn clone(&self) -> StaticShapeId {
+        *self
+    }
+}
+
+impl PartialEq for StaticShapeId {
+    fn eq(&self, other: &StaticShapeId) -> bool {
+        return self.index == other.index && self.generation == other.generation;
+    }
+}
+
+impl Eq for StaticShapeId {}
+
+/// A static shape, tessellated in world coordinates
+pub(crate) struct StaticShape {
+    pub layer: RenderLayer,
+    pub vertices: Vec<Vertex>,
+    pub bounds: (Vector2, Vector2),
+}
+
+/// Static shapes of a layer in one buffer, ordered by chunk so chunks in view can be drawn as ranges
+pub(crate) struct StaticBatch {
+    pub layer: RenderLayer,
+    pub vertices: Vec<Vertex>,
+    chunks: Vec<(Range<u32>, (Vector2, Vector2))>,
+}
+
+impl StaticBatch {
+    /// Return vertex ranges of chunks overlapping an area, joining neighbouring ranges
+    pub fn visible_ranges(&self, min: Vector2, max: Vector2) -> Vec<Range<u32>> {
+        let mut ranges: Vec<Range<u32>> = Vec::new();
+        for (range, (chunk_min, chunk_max)) in self.chunks.iter() {
+            if chunk_min.x > max.x
+                || chunk_max.x < min.x
+                || chunk_min.y > max.y
+                || chunk_max.y < min.y
+            {
+                continue;
+            }
+            match ranges.last_mut() {
+                Some(last) if last.end == range.start => last.end = range.end,
+                _ => ranges.push(range.clone()),
+            }
+        }
+        return ranges;
+    }
+}
+
+/// Shapes that rarely change, such as level geometry.
+/// Shapes are tessellated once when added instead of every frame, only the ones in view are drawn,
+/// and they are kept in GPU buffers when a game view covers the whole window
+pub struct StaticGeometry {
+    shapes: Vec<Option<StaticShape>>,
+    generations: Vec<u32>,
+    free: Vec<u32>,
+    index: SpatialIndex,
+    version: u64,
+}
+
+impl StaticGeometry {
+    /// Default constructor to initialize empty static geometry
+    pub fn new() -> Self {
+        return Self {
+            shapes: Vec::new(),
+            generations: Vec::new(),
+            free: Vec::new(),
+            index: SpatialIndex::new(CELL_SIZE),
+            version: 0,
+        };
+    }
+
+    /// Add a shape in world coordinates on a render layer, it is drawn every frame until removed
+    pub fn add(&mut self, shape: Shape, layer: RenderLayer) -> StaticShapeId {
+        let vertices: Vec<Vertex> = shape
+            .triangulate()
+            .into_iter()
+            .map(draw_list::bake_rotation)
+            .collect();
+        let bounds = draw_list::vertex_bounds(&vertices);
+        let static_shape = StaticShape {
+            layer,
+            vertices,
+            bounds,
+        };
+
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.shapes[index as usize] = Some(static_shape);
+                index
+            }
+            None => {
+                self.shapes.push(Some(static_shape));
+                self.generations.push(0);
+                self.shapes.len() as u32 - 1
+            }
+        };
+        self.index.insert(index as usize, bounds.0, bounds.1);
+        self.version += 1;
+        return StaticShapeId {
+            index,
+            generation: self.generations[index as usize],
+        };
+    }
+
+    /// Remove a shape, return whether it was there
+    pub fn remove(&mut self, id: StaticShapeId) -> bool {
+        if !self.contains(id) {
+            return false;
+        }
+        let index = id.index as usize;
+        self.shapes[index] = None;
+        self.generations[index] += 1;
+        self.free.push(id.index);
+        self.index.remove(index);
+        self.version += 1;
+        return true;
+    }
+
+    /// Remove every shape
+    pub fn clear(&mut self) {
+        for index in 0..self.shapes.len() {
+            if self.shapes[index].take().is_some() {
+                self.generations[index] += 1;
+                self.free.push(index as u32);
+            }
+        }
+        self.index.clear();
+        self.version += 1;
+    }
+
+    /// Return whether a shape has been added and not removed
+    pub fn contains(&self, id: StaticShapeId) -> bool {
+        let index = id.index as usize;
+        return index < self.shapes.len()
+            && self.generations[index] == id.generation
+            && self.shapes[index].is_some();
+    }
+
+    /// Return number of shapes
+    pub fn len(&self) -> usize {
+        return self.index.len();
+    }
+    /// Return whether there are no shapes
+    pub fn is_empty(&self) -> bool {
+        return self.index.is_empty();
+    }
+
+    /// Return smallest and largest world positions of a shape
+    pub fn bounds(&self, id: StaticShapeId) -> Option<(Vector2, Vector2)> {
+        if !self.contains(id) {
+            return None;
+        }
+        return self.index.bounds(id.index as usize);
+    }
+
+    /// Return shapes overlapping an area in world coordinates
+    pub fn query(&self, min: Vector2, max: Vector2) -> Vec<StaticShapeId> {
+        return self
+            .index
+            .query(min, max)
+            .into_iter()
+            .map(|index| StaticShapeId {
+                index: index as u32,
+                generation: self.generations[index],
+            })
+            .collect();
+    }
+
+    /// Return number of times shapes have been added or removed, to know when GPU buffers are out of date
+    pub(crate) fn version(&self) -> u64 {
+        return self.version;
+    }
+
+    /// Return shapes on a layer overlapping an area, or every shape on the layer if it repeats
+    pub(crate) fn shapes_in_view(
+        &self,
+        layer: RenderLayer,
+        min: Vector2,
+        max: Vector2,
+        is_repeating: bool,
+    ) -> Vec<&StaticShape> {
+        let shapes: Vec<&StaticShape> = if is_repeating {
+            self.shapes.iter().flatten().collect()
+        } else {
+            self.index
+                .query(min, max)
+                .into_iter()
+                .filter_map(|index| self.shapes[index].as_ref())
+                .collect()
+        };
+        return shapes
+            .into_iter()
+            .filter(|shape| shape.layer == layer)
+            .collect();
+    }
+
+    /// Return shapes grouped in one batch per layer, to upload to the GPU
+    pub(crate) fn batches(&self) -> Vec<StaticBatch> {
+        // Shapes are grouped by layer, then by the chunk their center is in
+        let mut groups: BTreeMap<(u32, i32, i32), Vec<&StaticShape>> = BTreeMap::new();
+        for shape in self.shapes.iter().flatten() {
+            let center = (shape.bounds.0 + shape.bounds.1) / 2.;
+            let chunk = (
+                shape.layer.index(),
+                (center.x / CHUNK_SIZE).floor() as i32,
+                (center.y / CHUNK_SIZE).floor() as i32,
+            );
+            groups.entry(chunk).or_default().push(shape);
+        }
+
+        let mut batches: Vec<StaticBatch> = Vec::new();
+        for ((layer, _, _), shapes) in groups {
+            if batches
+                .last()
+                .is_none_or(|batch| batch.layer.index() != layer)
+            {
+                batches.push(StaticBatch {
+                    layer: shapes[0].layer,
+                    vertices: Vec::new(),
+                    chunks: Vec::new(),
+                });
+            }
+            let batch = batches.last_mut().unwrap();
+
+            let start = batch.vertices.len() as u32;
+            let mut min = shapes[0].bounds.0;
+            let mut max = shapes[0].bounds.1;
+            for shape in shapes {
+                batch.vertices.extend_from_slice(&shape.vertices);
+                min.x = min.x.min(shape.bounds.0.x);
+                min.y = min.y.min(shape.bounds.0.y);
+                max.x = max.x.max(shape.bounds.1.x);
+                max.y = max.y.max(shape.bounds.1.y);
+            }
+            let end = batch.vertices.len() as u32;
+            batch.chunks.push((start..end, (min, max)));
+        }
+        return batches;
+    }
+}
@@ -0,0 +1,157 @@
+use crate::types::vector2::Vector2;
+
+use rgx::kit::shape2d::Vertex;
+
+/// Area of the screen a game view is drawn to, in fractions of the screen size from the top left corner
+pub struct Viewport {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Copy for Viewport {}
+
+impl Clone for Viewport {
+    fn clone(&self) -> Viewport {
+        *self
+    }
+}
+
+impl PartialEq for Viewport {
+    fn eq(&self, other: &Viewport) -> bool {
+        return self.x == other.x
+            && self.y == other.y
+            && self.width == other.width
+            && self.height == other.height;
+    }
+}
+
+impl Viewport {
+    /// Default constructor to initialize a viewport covering the whole screen
+    pub fn new() -> Self {
+        return Self::full();
+    }
+
+    /// Return a viewport covering the whole screen
+    pub fn full() -> Self {
+        return Self {
+            x: 0.,
+            y: 0.,
+            width: 1.,
+            height: 1.,
+        };
+    }
+    /// Return left half of the screen
+    pub fn left_half() -> Self {
+        return Self {
+            x: 0.,
+            y: 0.,
+            width: 0.5,
+            height: 1.,
+        };
+    }
+    /// Return right half of the screen
+    pub fn right_half() -> Self {
+        return Self {
+            x: 0.5,
+            y: 0.,
+            width: 0.5,
+            height: 1.,
+        };
+    }
+    /// Return top half of the screen
+    pub fn top_half() -> Self {
+        return Self {
+            x: 0.,
+            y: 0.,
+            width: 1.,
+            height: 0.5,
+        };
+    }
+    /// Return bottom half of the screen
+    pub fn bottom_half() -> Self {
+        return Self {
+            x: 0.,
+            y: 0.5,
+            width: 1.,
+            height: 0.5,
+        };
+    }
+
+    /// Return top left corner of viewport in pixels on a screen
+    pub fn origin(&self, screen_size: Vector2) -> Vector2 {
+        return Vector2 {
+            x: (self.x * screen_size.x).round(),
+            y: (self.y * screen_size.y).round(),
+        };
+    }
+    /// Return size of viewport in pixels on a screen
+    pub fn size(&self, screen_size: Vector2) -> Vector2 {
+        return Vector2 {
+            x: ((self.x + self.width) * screen_size.x).round(),
+            y: ((self.y + self.height) * screen_size.y).round(),
+        } - self.origin(screen_size);
+    }
+}
+
+/// Clip triangles to a rectangle in pixels, appending the parts inside the rectangle as triangles
+pub(crate) fn clip_triangles(
+    triangles: &[Vertex],
+    min: Vector2,
+    max: Vector2,
+    output: &mut Vec<Vertex>,
+) {
+    for triangle in triangles.chunks_exact(3) {
+        let is_inside = triangle.iter().all(|vertex| {
+            vertex.position.x >= min.x
+                && vertex.position.x <= max.x
+                && vertex.position.y >= min.y
+                && vertex.position.y <= max.y
+        });
+        if is_inside {
+            output.extend_from_slice(triangle);
+            continue;
+        }
+
+        // Clip polygon against each edge of the rectangle in turn
+        let mut polygon: Vec<Vertex> = triangle.to_vec();
+        polygon = clip_edge(&polygon, |v| v.position.x - min.x);
+        polygon = clip_edge(&polygon, |v| max.x - v.position.x);
+        polygon = clip_edge(&polygon, |v| v.position.y - min.y);
+        polygon = clip_edge(&polygon, |v| max.y - v.position.y);
+
+        // Convex polygon is split back into a triangle fan
+        for i in 1..polygon.len().saturating_sub(1) {
+            output.push(polygon[0]);
+            output.push(polygon[i]);
+            output.push(polygon[i + 1]);
+        }
+    }
+}
+
+/// Keep the part of a convex polygon where distance is not negative
+fn clip_edge<F>(polygon: &[Vertex], distance: F) -> Vec<Vertex>
+where
+    F: Fn(&Vertex) -> f32,
+{
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (i, current) in polygon.iter().enumerate() {
+        let next = &polygon[(i + 1) % polygon.len()];
+        let current_distance = distance(current);
+        let next_distance = distance(next);
+
+        if current_distance >= 0. {
+            clipped.push(*current);
+        }
+        // Add the point where the edge crosses the clip line
+        if (current_distance >= 0.) != (next_distance >= 0.) {
+            let t = current_distance / (current_distance - next_distance);
+            let mut vertex = *current;
+            vertex.position.x += (next.position.x - current.position.x) * t;
+            vertex.position.y += (next.position.y - current.position.y) * t;
+            clipped.push(vertex);
+        }
+    }
+    return clipped;
+}
@@ -0,0 +1,434 @@
+use crate::app::App;
+
+use rgx::core::*;
+use rgx::kit::shape2d::{Fill, Shape};
+use rgx::kit::ZDepth;
+
+// Z-depth of the fade overlay, in front of every render layer
+const FADE_ZDEPTH: f32 = 0.95;
+
+/// A scene is a self-contained part of a game such as a menu, a level or a pause screen.
+/// Scenes are managed by the App scene stack, only the scene at the top of the stack is updated
+pub trait Scene {
+    /// Called when scene is pushed onto the stack or replaces another scene
+    fn enter(&mut self, _app: &mut App) {}
+    /// Called at fixed frame time while scene is at the top of the stack
+    fn fixed_update(&mut self, _app: &mut App) {}
+    /// Called every frame while scene is at the top of the stack
+    fn update(&mut self, _app: &mut App) {}
+    /// Called every frame after update to submit shapes, while scene is visible
+    fn draw(&mut self, _app: &mut App) {}
+    /// Called when scene is popped or replaced
+    fn exit(&mut self, _app: &mut App) {}
+    /// Called when another scene is pushed on top of this scene
+    fn pause(&mut self, _app: &mut App) {}
+    /// Called when the scene on top of this scene is popped
+    fn resume(&mut self, _app: &mut App) {}
+
+    /// Return whether scenes below this scene are still drawn, e.g. for pause screens
+    fn is_overlay(&self) -> bool {
+        return false;
+    }
+}
+
+/// How the screen changes from one scene to the next
+pub enum Transition {
+    /// Change scene immediately
+    None,
+    /// Fade out to game view color then fade in, each half taking the given duration in seconds
+    Fade(f32),
+}
+
+impl Copy for Transition {}
+
+impl Clone for Transition {
+    fn clone(&self) -> Transition {
+        *self
+    }
+}
+
+/// A change to the scene stack requested during a frame
+enum SceneCommand {
+    Push(Box<dyn Scene>),
+    Pop,
+    Replace(Box<dyn Scene>),
+}
+
+/// State of a fade transition in progress
+struct Fade {
+    command: Option<SceneCommand>,
+    duration: f32,
+    elapsed: f32,
+    is_fading_in: bool,
+}
+
+impl Fade {
+    /// Return opacity of the fade overlay between 0 and 1
+    fn opacity(&self) -> f32 {
+        let progress = if self.duration > 0. {
+            (self.elapsed / self.duration).min(1.)
+        } else {
+            1.
+        };
+        return if self.is_fading_in {
+            1. - progress
+        } else {
+            progress
+        };
+    }
+}
+
+/// Stack of scenes, the scene at the top of the stack is the active scene.
+/// Changes to the stack are applied between frames so a scene can safely push, pop or replace itself
+pub struct SceneStack {
+    scenes: Vec<Box<dyn Scene>>,
+    commands: Vec<(SceneCommand, Transition)>,
+    fade: Option<Fade>,
+}
+
+impl SceneStack {
+    /// Default constructor to initialize an empty scene stack
+    pub fn new() -> Self {
+        return Self {
+            scenes: Vec::new(),
+            commands: Vec::new(),
+            fade: None,
+        };
+    }
+
+    /// Push a scene on top of the stack, pausing the current scene
+    pub fn push<S: Scene + 'static>(&mut self, scene: S, transition: Transition) {
+        self.commands
+            .push((SceneCommand::Push(Box::new(scene)), transition));
+    }
+    /// Pop the scene at the top of the stack, resuming the scene below it
+    pub fn pop(&mut self, transition: Transition) {
+        self.commands.push((SceneCommand::Pop, transition));
+    }
+    /// Replace the scene at the top of the stack with another scene
+    pub fn replace<S: Scene + 'static>(&mut self, scene: S, transition: Transition) {
+        self.commands
+            .push((SceneCommand::Replace(Box::new(scene)), transition));
+    }
+
+    /// Return number of scenes on the stack
+    pub fn len(&self) -> usize {
+        return self.scenes.len();
+    }
+    /// Return whether there is no scene on the stack
+    pub fn is_empty(&self) -> bool {
+        return self.scenes.is_empty();
+    }
+    /// Return whether a transition is in progress
+    pub fn is_transitioning(&self) -> bool {
+        return self.fade.is_some();
+    }
+
+    /// Apply pending changes to the stack and advance transitions.
+    /// Only meant to be called internally within sidekick
+    pub fn apply_commands(&mut self, app: &mut App) {
+        // Advance current fade, changing scenes once the screen is fully covered
+        if let Some(fade) = self.fade.as_mut() {
+            fade.elapsed += app.time.frame_time();
+            if fade.elapsed < fade.duration {
+                return;
+            }
+
+            if fade.is_fading_in {
+                self.fade = None;
+            } else {
+                let command = fade.command.take();
+                fade.is_fading_in = true;
+                fade.elapsed = 0.;
+                if let Some(command) = command {
+                    self.execute(command, app);
+                }
+                return;
+            }
+        }
+
+        while !self.commands.is_empty() {
+            let (command, transition) = self.commands.remove(0);
+            match transition {
+                Transition::None => self.execute(command, app),
+                Transition::Fade(duration) => {
+                    self.fade = Some(Fade {
+                        command: Some(command),
+                        duration,
+                        elapsed: 0.,
+                        is_fading_in: false,
+                    });
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Call fixed update on the active scene. Only meant to be called internally within sidekick
+    pub fn fixed_update(&mut self, app: &mut App) {
+        if self.is_fading_out() {
+            return;
+        }
+        if let Some(scene) = self.scenes.last_mut() {
+            scene.fixed_update(app);
+        }
+    }
+
+    /// Update the active scene and draw every visible scene from the bottom up.
+    /// Only meant to be called internally within sidekick
+    pub fn update(&mut self, app: &mut App) {
+        // Scenes being faded out are frozen
+        if !self.is_fading_out() {
+            if let Some(scene) = self.scenes.last_mut() {
+                scene.update(app);
+            }
+        }
+
+        // Draw scenes from the first scene that is not an overlay up to the top
+        let first_visible = self
+            .scenes
+            .iter()
+            .rposition(|scene| !scene.is_overlay())
+            .unwrap_or(0);
+        for scene in self.scenes.iter_mut().skip(first_visible) {
+            scene.draw(app);
+        }
+
+        // Cover screen with game view color while fading
+        if let Some(fade) = self.fade.as_ref() {
+            let color = app.game_view.color;
+            let size = app.screen_size();
+            app.draw_list.submit_screen(
+                Shape::rect([0., 0.], [size.x, size.y])
+                    .fill(Fill::solid(Rgba::new(
+                        color.r,
+                        color.g,
+                        color.b,
+                        color.a * fade.opacity(),
+                    )))
+                    .zdepth(ZDepth::from(FADE_ZDEPTH)),
+            );
+        }
+    }
+
+    /// Take changes requested while this stack was moved out of the App
+    pub(crate) fn append_commands(&mut self, other: &mut SceneStack) {
+        self.commands.append(&mut other.commands);
+    }
+
+    /// Return whether the active scene is being faded out
+    fn is_fading_out(&self) -> bool {
+        return match self.fade.as_ref() {
+            Some(fade) => !fade.is_fading_in,
+            None => false,
+        };
+    }
+
+    /// Change the stack and call scene callbacks
+    fn execute(&mut self, command: SceneCommand, app: &mut App) {
+        match command {
+            SceneCommand::Push(mut scene) => {
+                if let Some(current) = self.scenes.last_mut() {
+                    current.pause(app);
+                }
+                scene.enter(app);
+                self.scenes.push(scene);
+            }
+            SceneCommand::Pop => {
+                if let Some(mut current) = self.scenes.pop() {
+                    current.exit(app);
+                }
+                if let Some(next) = self.scenes.last_mut() {
+                    next.resume(app);
+                }
+            }
+            SceneCommand::Replace(mut scene) => {
+                if let Some(mut current) = self.scenes.pop() {
+                    current.exit(app);
+                }
+                scene.enter(app);
+                self.scenes.push(scene);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type Log = Rc<RefCell<Vec<String>>>;
+    type Callback = Box<dyn FnOnce(&mut App)>;
+
+    /// Scene that logs its callbacks and runs a function on its first update
+    struct LoggedScene {
+        name: &'static str,
+        is_overlay: bool,
+        log: Log,
+        on_first_update: Option<Callback>,
+    }
+
+    impl LoggedScene {
+        fn new(name: &'static str, log: &Log) -> Self {
+            return Self {
+                name,
+                is_overlay: false,
+                log: log.clone(),
+                on_first_update: None,
+            };
+        }
+
+        fn overlay(mut self) -> Self {
+            self.is_overlay = true;
+            return self;
+        }
+
+        fn on_first_update<F: FnOnce(&mut App) + 'static>(mut self, function: F) -> Self {
+            self.on_first_update = Some(Box::new(function));
+            return self;
+        }
+
+        fn record(&self, callback: &str) {
+            self.log
+                .borrow_mut()
+                .push(format!("{}.{}", self.name, callback));
+        }
+    }
+
+    impl Scene for LoggedScene {
+        fn enter(&mut self, _app: &mut App) {
+            self.record("enter");
+        }
+        fn update(&mut self, app: &mut App) {
+            self.record("update");
+            if let Some(function) = self.on_first_update.take() {
+                function(app);
+            }
+        }
+        fn draw(&mut self, _app: &mut App) {
+            self.record("draw");
+        }
+        fn exit(&mut self, _app: &mut App) {
+            self.record("exit");
+        }
+        fn pause(&mut self, _app: &mut App) {
+            self.record("pause");
+        }
+        fn resume(&mut self, _app: &mut App) {
+            self.record("resume");
+        }
+        fn is_overlay(&self) -> bool {
+            return self.is_overlay;
+        }
+    }
+
+    #[test]
+    fn changes_are_applied_on_next_frame() {
+        let log: Log = Rc::new(RefCell::new(Vec::new()));
+        let (scene_log, c_log, frame_log) = (log.clone(), log.clone(), log.clone());
+        let mut frame = 0;
+        let mut lengths = Vec::new();
+
+        App::new().run_headless(
+            move |app: &mut App| {
+                // Scenes push and pop themselves while the stack is moved out of the App
+                let b = LoggedScene::new("b", &scene_log)
+                    .on_first_update(|app: &mut App| app.scenes.pop(Transition::None));
+                let a = LoggedScene::new("a", &scene_log)
+                    .on_first_update(move |app: &mut App| app.scenes.push(b, Transition::None));
+                app.scenes.push(a, Transition::None);
+            },
+            |app: &mut App| {
+                frame_log.borrow_mut().push(format!("frame {}", frame));
+                lengths.push(app.scenes.len());
+                if frame == 2 {
+                    let c = LoggedScene::new("c", &c_log);
+                    app.scenes.replace(c, Transition::None);
+                }
+                frame += 1;
+            },
+            4,
+        );
+
+        assert_eq!(
+            *log.borrow(),
+            vec![
+                "a.enter", "frame 0", "a.update", "a.draw", // a requests b
+                "a.pause", "b.enter", "frame 1", "b.update", "b.draw", // b requests pop
+                "b.exit", "a.resume", "frame 2", "a.update", "a.draw", // c replaces a
+                "a.exit", "c.enter", "frame 3", "c.update", "c.draw",
+            ]
+        );
+        assert_eq!(lengths, vec![1, 2, 1, 1]);
+    }
+
+    #[test]
+    fn overlays_draw_scenes_below_them() {
+        let log: Log = Rc::new(RefCell::new(Vec::new()));
+        let init_log = log.clone();
+        App::new().run_headless(
+            move |app: &mut App| {
+                app.scenes
+                    .push(LoggedScene::new("a", &init_log), Transition::None);
+                app.scenes
+                    .push(LoggedScene::new("b", &init_log), Transition::None);
+                app.scenes
+                    .push(LoggedScene::new("c", &init_log).overlay(), Transition::None);
+                app.scenes
+                    .push(LoggedScene::new("d", &init_log).overlay(), Transition::None);
+            },
+            |_app: &mut App| {},
+            1,
+        );
+
+        // Only the top scene is updated, drawing starts at the first scene that is not an overlay
+        assert_eq!(
+            *log.borrow(),
+            vec![
+                "a.enter", "a.pause", "b.enter", "b.pause", "c.enter", "c.pause", "d.enter",
+                "d.update", "b.draw", "c.draw", "d.draw",
+            ]
+        );
+    }
+
+    #[test]
+    fn fade_changes_scene_once_faded_out() {
+        let log: Log = Rc::new(RefCell::new(Vec::new()));
+        let (init_log, update_log) = (log.clone(), log.clone());
+        let mut frame = 0;
+        let mut transitions = Vec::new();
+
+        App::new().run_headless(
+            move |app: &mut App| {
+                app.scenes
+                    .push(LoggedScene::new("a", &init_log), Transition::None);
+            },
+            |app: &mut App| {
+                update_log.borrow_mut().push(format!("frame {}", frame));
+                transitions.push(app.scenes.is_transitioning());
+                if frame == 0 {
+                    // Each half of the fade lasts a frame and a half at 60 frames per second
+                    let b = LoggedScene::new("b", &update_log);
+                    app.scenes.push(b, Transition::Fade(0.025));
+                }
+                frame += 1;
+            },
+            6,
+        );
+
+        assert_eq!(
+            *log.borrow(),
+            vec![
+                "a.enter", "frame 0", "a.update", "a.draw",
+                // a is frozen while fading out
+                "frame 1", "a.draw", "frame 2", "a.draw",
+                // b is pushed once the screen is covered and runs while fading in
+                "a.pause", "b.enter", "frame 3", "b.update", "b.draw", "frame 4", "b.update",
+                "b.draw", "frame 5", "b.update", "b.draw",
+            ]
+        );
+        assert_eq!(transitions, vec![false, true, true, true, true, false]);
+    }
+}
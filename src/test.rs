@@ -1 +1,288 @@
+use crate::app::App;
+use crate::types::color32::Color32;
+use crate::types::image::Image;
 
+use std::env;
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+/// Environment variable that, when set to anything other than "0", overwrites stored snapshots with new renders
+pub const BLESS_ENV_VAR: &str = "SIDEKICK_BLESS";
+
+// Default values for snapshot comparison
+const DEFAULT_DIRECTORY: &str = "tests/snapshots";
+const DEFAULT_TOLERANCE: u8 = 0;
+const DEFAULT_MAX_MISMATCHED_PIXELS: usize = 0;
+
+/// Outcome of comparing an image against a stored snapshot
+pub enum SnapshotResult {
+    /// Image matched the stored snapshot within tolerance
+    Matched,
+    /// Stored snapshot was written or overwritten because blessing is enabled
+    Blessed,
+    /// No snapshot is stored under this name and blessing is disabled
+    Missing { path: PathBuf },
+    /// Image and stored snapshot have different sizes
+    SizeMismatch {
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+    /// Too many pixels differed, a diff image and the actual render were written next to the snapshot
+    Mismatched {
+        mismatched_pixels: usize,
+        diff_path: PathBuf,
+        actual_path: PathBuf,
+    },
+}
+
+impl fmt::Display for SnapshotResult {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotResult::Matched => write!(formatter, "Snapshot matched"),
+            SnapshotResult::Blessed => write!(formatter, "Snapshot blessed"),
+            SnapshotResult::Missing { path } => write!(
+                formatter,
+                "Snapshot {} does not exist, run with {}=1 to create it",
+                path.display(),
+                BLESS_ENV_VAR
+            ),
+            SnapshotResult::SizeMismatch { expected, actual } => write!(
+                formatter,
+                "Snapshot size mismatch: expected {}x{}, got {}x{}",
+                expected.0, expected.1, actual.0, actual.1
+            ),
+            SnapshotResult::Mismatched {
+                mismatched_pixels,
+                diff_path,
+                actual_path,
+            } => write!(
+                formatter,
+                "Snapshot mismatch: {} pixels differ, see {} and {}",
+                mismatched_pixels,
+                diff_path.display(),
+                actual_path.display()
+            ),
+        }
+    }
+}
+
+impl SnapshotResult {
+    /// Return whether the comparison should be considered a success
+    pub fn is_ok(&self) -> bool {
+        return matches!(self, SnapshotResult::Matched | SnapshotResult::Blessed);
+    }
+}
+
+/// Golden-image snapshot tester that compares renders against PNG files stored on disk
+pub struct Snapshot {
+    /// Directory where snapshots are stored
+    pub directory: PathBuf,
+    /// Maximum difference allowed on any channel of a pixel before it counts as mismatched
+    pub tolerance: u8,
+    /// Maximum number of mismatched pixels allowed before the comparison fails
+    pub max_mismatched_pixels: usize,
+}
+
+impl Snapshot {
+    /// Default constructor to initialize Snapshot, storing snapshots in "tests/snapshots"
+    pub fn new() -> Self {
+        return Self {
+            directory: PathBuf::from(DEFAULT_DIRECTORY),
+            tolerance: DEFAULT_TOLERANCE,
+            max_mismatched_pixels: DEFAULT_MAX_MISMATCHED_PIXELS,
+        };
+    }
+
+    /// Return path of a named snapshot
+    pub fn path(&self, name: &str) -> PathBuf {
+        return self.directory.join(format!("{}.png", name));
+    }
+
+    /// Compare an image against a named snapshot, writing diff images on failure
+    pub fn compare(&self, name: &str, image: &Image) -> io::Result<SnapshotResult> {
+        let path = self.path(name);
+        let diff_path = self.directory.join(format!("{}.diff.png", name));
+        let actual_path = self.directory.join(format!("{}.actual.png", name));
+
+        if is_blessing() {
+            save_png(&path, image)?;
+            remove_if_exists(&diff_path)?;
+            remove_if_exists(&actual_path)?;
+            return Ok(SnapshotResult::Blessed);
+        }
+
+        if !path.exists() {
+            return Ok(SnapshotResult::Missing { path });
+        }
+
+        let expected = load_png(&path)?;
+        if expected.width() != image.width() || expected.height() != image.height() {
+            save_png(&actual_path, image)?;
+            return Ok(SnapshotResult::SizeMismatch {
+                expected: (expected.width(), expected.height()),
+                actual: (image.width(), image.height()),
+            });
+        }
+
+        let (mismatched_pixels, diff) = diff_images(&expected, image, self.tolerance);
+        if mismatched_pixels > self.max_mismatched_pixels {
+            save_png(&diff_path, &diff)?;
+            save_png(&actual_path, image)?;
+            return Ok(SnapshotResult::Mismatched {
+                mismatched_pixels,
+                diff_path,
+                actual_path,
+            });
+        }
+
+        remove_if_exists(&diff_path)?;
+        remove_if_exists(&actual_path)?;
+        return Ok(SnapshotResult::Matched);
+    }
+
+    /// Compare an image against a named snapshot and panic if they do not match
+    pub fn assert_matches(&self, name: &str, image: &Image) {
+        match self.compare(name, image) {
+            Ok(result) => {
+                if !result.is_ok() {
+                    panic!("{}: {}", name, result);
+                }
+            }
+            Err(err) => panic!("{}: error when comparing snapshot: {}", name, err),
+        }
+    }
+}
+
+/// Return whether snapshots should be overwritten instead of compared
+pub fn is_blessing() -> bool {
+    return match env::var(BLESS_ENV_VAR) {
+        Ok(value) => !value.is_empty() && value != "0",
+        Err(_) => false,
+    };
+}
+
+/// Run an App headless for a number of frames and render its last frame to an image,
+/// after the frame is fully updated like a frame shown in the window
+pub fn render_scene<I, U>(app: App, init: I, update: U, frames: usize) -> Image
+where
+    I: FnMut(&mut App) -> (),
+    U: FnMut(&mut App) -> (),
+{
+    return match app.render_headless(init, update, frames) {
+        Some(image) => image,
+        None => panic!("Scene quit before frame {} could be rendered", frames),
+    };
+}
+
+/// Compare two images of the same size, return the number of mismatched pixels and a diff image.
+/// Matching pixels are drawn as faded grayscale, mismatched pixels are drawn red
+pub fn diff_images(expected: &Image, actual: &Image, tolerance: u8) -> (usize, Image) {
+    let mut mismatched_pixels: usize = 0;
+    let mut diff = Image::new(expected.width(), expected.height());
+
+    for y in 0..expected.height() {
+        for x in 0..expected.width() {
+            let a = expected.pixel(x, y).unwrap_or_else(Color32::transparent);
+            let b = actual.pixel(x, y).unwrap_or_else(Color32::transparent);
+
+            let delta =
+                a.r.abs_diff(b.r)
+                    .max(a.g.abs_diff(b.g))
+                    .max(a.b.abs_diff(b.b))
+                    .max(a.a.abs_diff(b.a));
+
+            if delta > tolerance as u32 {
+                mismatched_pixels += 1;
+                diff.set_pixel(x, y, Color32::red());
+            } else {
+                let gray = (a.r + a.g + a.b) / 3 / 4 + 32;
+                diff.set_pixel(
+                    x,
+                    y,
+                    Color32 {
+                        r: gray,
+                        g: gray,
+                        b: gray,
+                        a: 255,
+                    },
+                );
+            }
+        }
+    }
+
+    return (mismatched_pixels, diff);
+}
+
+/// Load a PNG file into an RGBA image
+pub fn load_png<P: AsRef<Path>>(path: P) -> io::Result<Image> {
+    let mut decoder = png::Decoder::new(File::open(path)?);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info().map_err(to_io_error)?;
+
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).map_err(to_io_error)?;
+    buffer.truncate(info.buffer_size());
+
+    // Convert every color type to RGBA
+    let pixels: Vec<u8> = match info.color_type {
+        png::ColorType::Rgba => buffer,
+        png::ColorType::Rgb => buffer
+            .chunks(3)
+            .flat_map(|p| vec![p[0], p[1], p[2], 255])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => buffer
+            .chunks(2)
+            .flat_map(|p| vec![p[0], p[0], p[0], p[1]])
+            .collect(),
+        png::ColorType::Grayscale => buffer.iter().flat_map(|p| vec![*p, *p, *p, 255]).collect(),
+        png::ColorType::Indexed => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Indexed PNG was not expanded",
+            ))
+        }
+    };
+
+    return match Image::from_raw(info.width, info.height, pixels) {
+        Some(image) => Ok(image),
+        None => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "PNG data does not match its size",
+        )),
+    };
+}
+
+/// Save an RGBA image to a PNG file, creating parent directories if needed
+pub fn save_png<P: AsRef<Path>>(path: P, image: &Image) -> io::Result<()> {
+    if let Some(parent) = path.as_ref().parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let writer = BufWriter::new(File::create(path)?);
+    let mut encoder = png::Encoder::new(writer, image.width(), image.height());
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header().map_err(to_io_error)?;
+    writer
+        .write_image_data(image.pixels())
+        .map_err(to_io_error)?;
+    return Ok(());
+}
+
+/// Remove a file if it exists
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    return Ok(());
+}
+
+/// Convert any displayable error to an IO error
+fn to_io_error<E: fmt::Display>(err: E) -> io::Error {
+    return io::Error::other(err.to_string());
+}
@@ -1,12 +1,44 @@
-use std::time::Instant;
+use std::collections::VecDeque;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Number of recent frame times kept to compute frame statistics
+const FRAME_TIME_SAMPLES: usize = 120;
+// Time left before a frame deadline under which pacing yields instead of sleeping
+const SPIN_THRESHOLD: Duration = Duration::from_micros(200);
+
+/// How frames are paced
+pub enum FramePacing {
+    /// Update and render as fast as possible
+    Unlimited,
+    /// Let the display vertical sync pace frames
+    VSync,
+    /// Sleep between frames to hold target frame rate
+    TargetFrameRate,
+}
+
+impl Copy for FramePacing {}
+
+impl Clone for FramePacing {
+    fn clone(&self) -> FramePacing {
+        *self
+    }
+}
 
 /// Game time manager
 pub struct Time {
     pub time_scale: f32,
     pub target_frame_rate: u32,
+    pub frame_pacing: FramePacing,
+    pub max_fixed_steps: u32,
+    fixed_time_accumulator: f32,
+    interpolation_alpha: f32,
     frame_time: f32,
-    previous_time: f32,
+    frame_time_samples: VecDeque<f32>,
     current_time: f32,
+    previous_frame: Instant,
+    next_frame_deadline: Option<Instant>,
+    sleep_overshoot: Duration,
     start: Instant,
 }
 
@@ -16,9 +48,16 @@ impl Time {
         return Self {
             time_scale: 1.0,
             target_frame_rate: 60,
+            frame_pacing: FramePacing::TargetFrameRate,
+            max_fixed_steps: 5,
+            fixed_time_accumulator: 0.0,
+            interpolation_alpha: 0.0,
             frame_time: 0.0,
-            previous_time: 0.0,
+            frame_time_samples: VecDeque::with_capacity(FRAME_TIME_SAMPLES),
             current_time: 0.0,
+            previous_frame: Instant::now(),
+            next_frame_deadline: None,
+            sleep_overshoot: Duration::from_millis(1),
             start: Instant::now(),
         };
     }
@@ -34,20 +73,263 @@ impl Time {
     pub fn frame_rate(&self) -> u32 {
         return (1.0 / self.frame_time) as u32;
     }
+    /// Return time elapsed since App started
+    pub fn time(&self) -> f32 {
+        return self.current_time;
+    }
+
+    /// Return average frame time over recent frames
+    pub fn average_frame_time(&self) -> f32 {
+        if self.frame_time_samples.is_empty() {
+            return self.frame_time;
+        }
+        return self.frame_time_samples.iter().sum::<f32>() / self.frame_time_samples.len() as f32;
+    }
+    /// Return average frame rate over recent frames
+    pub fn average_frame_rate(&self) -> f32 {
+        let average_frame_time = self.average_frame_time();
+        if average_frame_time <= 0.0 {
+            return 0.0;
+        }
+        return 1.0 / average_frame_time;
+    }
+    /// Return variance of frame time over recent frames, lower means smoother frame pacing
+    pub fn frame_time_variance(&self) -> f32 {
+        if self.frame_time_samples.is_empty() {
+            return 0.0;
+        }
+        let average_frame_time = self.average_frame_time();
+        return self
+            .frame_time_samples
+            .iter()
+            .map(|frame_time| (frame_time - average_frame_time).powi(2))
+            .sum::<f32>()
+            / self.frame_time_samples.len() as f32;
+    }
+    /// Return how far current frame is between the last fixed step and the next one, from 0 to 1.
+    /// Is used to interpolate rendering between fixed update states
+    pub fn interpolation_alpha(&self) -> f32 {
+        return self.interpolation_alpha;
+    }
+
+    /// Add scaled frame time to fixed step accumulator, is called every update iteration.
+    /// Accumulated time is capped at max_fixed_steps so a slow frame cannot snowball into slower ones
+    pub fn accumulate_fixed_time(&mut self) {
+        let fixed_frame_time = self.fixed_frame_time();
+
+        self.fixed_time_accumulator += self.frame_time * self.time_scale;
+        let max_accumulated_time = fixed_frame_time * self.max_fixed_steps as f32;
+        if self.fixed_time_accumulator > max_accumulated_time {
+            self.fixed_time_accumulator = max_accumulated_time;
+        }
+
+        self.interpolation_alpha = self.fixed_time_accumulator / fixed_frame_time;
+    }
+
+    /// Return true and consume a fixed step if enough time has accumulated for one
+    pub fn consume_fixed_step(&mut self) -> bool {
+        let fixed_frame_time = self.fixed_frame_time();
+        if self.fixed_time_accumulator < fixed_frame_time {
+            return false;
+        }
+
+        self.fixed_time_accumulator -= fixed_frame_time;
+        self.interpolation_alpha = self.fixed_time_accumulator / fixed_frame_time;
+        return true;
+    }
 
-    /// Update frame time, is called every update iteration
+    /// Wait for next frame according to frame pacing, then update frame time. Is called every update iteration
     pub fn update(&mut self) {
-        // Get current time
-        self.current_time = self.start.elapsed().as_secs_f32();
-        // Get frame time by substracting previous time from current frame time
-        self.frame_time = self.current_time - self.previous_time;
-        // Set previous time for next frame update
-        self.previous_time = self.current_time;
-
-        // Busy wait for frame to pass before next update
-        while self.start.elapsed().as_secs_f32()
-            < self.current_time + (1. / self.target_frame_rate as f32)
-        {}
-        self.current_time += 1. / self.target_frame_rate as f32;
+        if let FramePacing::TargetFrameRate = self.frame_pacing {
+            self.wait_for_next_frame();
+        } else {
+            self.next_frame_deadline = None;
+        }
+
+        // Get frame time as time passed since previous frame
+        let now = Instant::now();
+        self.frame_time = now.duration_since(self.previous_frame).as_secs_f32();
+        self.previous_frame = now;
+        self.current_time = now.duration_since(self.start).as_secs_f32();
+
+        self.record_frame_time();
+    }
+
+    /// Advance time manually by a given frame time without waiting, is used when running headless
+    pub fn step(&mut self, frame_time: f32) {
+        self.frame_time = frame_time;
+        self.current_time += frame_time;
+
+        self.record_frame_time();
+    }
+
+    /// Sleep until next frame deadline at target frame rate.
+    /// Most of the wait is slept, the last part is yielded to correct for sleep inaccuracy
+    fn wait_for_next_frame(&mut self) {
+        let frame_duration = Duration::from_secs_f64(1.0 / self.target_frame_rate.max(1) as f64);
+        let deadline = match self.next_frame_deadline {
+            Some(deadline) => deadline,
+            None => self.previous_frame + frame_duration,
+        };
+
+        let mut now = Instant::now();
+        while now < deadline {
+            let remaining = deadline - now;
+            if remaining > self.sleep_overshoot + SPIN_THRESHOLD {
+                // Sleep less than remaining time by how much sleeps usually overshoot
+                let sleep_duration = remaining - self.sleep_overshoot - SPIN_THRESHOLD;
+                thread::sleep(sleep_duration);
+
+                // Keep a running estimate of sleep overshoot
+                let overshoot = now.elapsed().saturating_sub(sleep_duration);
+                self.sleep_overshoot = estimate_sleep_overshoot(self.sleep_overshoot, overshoot);
+            } else {
+                thread::yield_now();
+            }
+            now = Instant::now();
+        }
+
+        self.next_frame_deadline = Some(next_frame_deadline(deadline, now, frame_duration));
+    }
+
+    /// Add current frame time to recent frame time samples
+    fn record_frame_time(&mut self) {
+        if self.frame_time_samples.len() == FRAME_TIME_SAMPLES {
+            self.frame_time_samples.pop_front();
+        }
+        self.frame_time_samples.push_back(self.frame_time);
+    }
+}
+
+/// Return estimate of how much sleeps overshoot, moved an eighth of the way towards the last overshoot
+fn estimate_sleep_overshoot(estimate: Duration, overshoot: Duration) -> Duration {
+    return (estimate * 7 + overshoot) / 8;
+}
+
+/// Return deadline of the frame after the one with a deadline, reached at a time.
+/// Deadlines follow each other to avoid drift, unless a whole frame was missed
+fn next_frame_deadline(deadline: Instant, now: Instant, frame_duration: Duration) -> Instant {
+    if now.duration_since(deadline) > frame_duration {
+        return now + frame_duration;
+    }
+    return deadline + frame_duration;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Return whether two values are equal within rounding errors
+    fn is_close(a: f32, b: f32) -> bool {
+        return (a - b).abs() < 1e-5;
+    }
+
+    #[test]
+    fn step_advances_time() {
+        let mut time = Time::new();
+        time.step(0.5);
+        time.step(0.25);
+        assert_eq!(time.frame_time(), 0.25);
+        assert_eq!(time.frame_rate(), 4);
+        assert!(is_close(time.time(), 0.75));
+    }
+
+    #[test]
+    fn average_and_variance_of_frame_times() {
+        let mut time = Time::new();
+        assert_eq!(time.frame_time_variance(), 0.);
+        for frame_time in [0.01, 0.03, 0.01, 0.03].iter() {
+            time.step(*frame_time);
+        }
+        assert!(is_close(time.average_frame_time(), 0.02));
+        assert!(is_close(time.average_frame_rate(), 50.));
+        assert!(is_close(time.frame_time_variance(), 0.0001));
+    }
+
+    #[test]
+    fn frame_statistics_only_use_recent_frames() {
+        let mut time = Time::new();
+        for _ in 0..FRAME_TIME_SAMPLES {
+            time.step(1.);
+        }
+        for _ in 0..FRAME_TIME_SAMPLES {
+            time.step(0.02);
+        }
+        assert!(is_close(time.average_frame_time(), 0.02));
+        assert!(is_close(time.frame_time_variance(), 0.));
+
+        // Half the window at a different frame time
+        for _ in 0..FRAME_TIME_SAMPLES / 2 {
+            time.step(0.04);
+        }
+        assert!(is_close(time.average_frame_time(), 0.03));
+        assert!(is_close(time.frame_time_variance(), 0.0001));
+    }
+
+    #[test]
+    fn fixed_steps_and_interpolation() {
+        let mut time = Time::new();
+        time.target_frame_rate = 50;
+
+        // 2.5 fixed steps of time
+        time.step(0.05);
+        time.accumulate_fixed_time();
+        assert!(is_close(time.interpolation_alpha(), 2.5));
+        assert!(time.consume_fixed_step());
+        assert!(time.consume_fixed_step());
+        assert!(!time.consume_fixed_step());
+        assert!(is_close(time.interpolation_alpha(), 0.5));
+
+        // Time scale slows down fixed steps
+        time.time_scale = 0.5;
+        time.step(0.02);
+        time.accumulate_fixed_time();
+        assert!(is_close(time.interpolation_alpha(), 1.));
+        assert!(time.consume_fixed_step());
+        assert!(is_close(time.interpolation_alpha(), 0.));
+    }
+
+    #[test]
+    fn fixed_steps_are_capped() {
+        let mut time = Time::new();
+        time.target_frame_rate = 10;
+        time.max_fixed_steps = 3;
+        time.step(10.);
+        time.accumulate_fixed_time();
+        assert!(is_close(time.interpolation_alpha(), 3.));
+
+        let mut steps = 0;
+        while time.consume_fixed_step() {
+            steps += 1;
+        }
+        assert_eq!(steps, 3);
+    }
+
+    #[test]
+    fn sleep_overshoot_estimate_follows_overshoots() {
+        let estimate = estimate_sleep_overshoot(Duration::from_millis(1), Duration::from_millis(9));
+        assert_eq!(estimate, Duration::from_millis(2));
+
+        let mut estimate = Duration::from_millis(1);
+        for _ in 0..100 {
+            estimate = estimate_sleep_overshoot(estimate, Duration::from_micros(100));
+        }
+        assert!(estimate < Duration::from_micros(101));
+        assert!(estimate >= Duration::from_micros(100));
+    }
+
+    #[test]
+    fn frame_deadlines_do_not_drift() {
+        let start = Instant::now();
+        let frame = Duration::from_millis(16);
+
+        // Deadlines reached late still follow each other
+        let deadline = start + frame;
+        let next = next_frame_deadline(deadline, deadline + Duration::from_millis(3), frame);
+        assert_eq!(next, deadline + frame);
+
+        // A whole missed frame schedules from now instead of catching up
+        let now = deadline + Duration::from_millis(40);
+        assert_eq!(next_frame_deadline(deadline, now, frame), now + frame);
     }
 }
@@ -0,0 +1,104 @@
+use crate::types::color32::Color32;
+
+/// A type that holds an RGBA image in memory, 8 bits per channel
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Clone for Image {
+    fn clone(&self) -> Image {
+        return Image {
+            width: self.width,
+            height: self.height,
+            pixels: self.pixels.clone(),
+        };
+    }
+}
+
+impl Image {
+    /// Default constructor to initialize a transparent Image of a given size
+    pub fn new(width: u32, height: u32) -> Self {
+        return Self {
+            width,
+            height,
+            pixels: vec![0; (width * height * 4) as usize],
+        };
+    }
+
+    /// Initialize an Image of a given size filled with a color
+    pub fn filled(width: u32, height: u32, color: Color32) -> Self {
+        let mut image = Image::new(width, height);
+        image.fill(color);
+        return image;
+    }
+
+    /// Initialize an Image from raw RGBA bytes, return None if the byte count does not match the size
+    pub fn from_raw(width: u32, height: u32, pixels: Vec<u8>) -> Option<Self> {
+        if pixels.len() != (width * height * 4) as usize {
+            return None;
+        }
+        return Some(Self {
+            width,
+            height,
+            pixels,
+        });
+    }
+
+    /// Return Image width in pixels
+    pub fn width(&self) -> u32 {
+        return self.width;
+    }
+    /// Return Image height in pixels
+    pub fn height(&self) -> u32 {
+        return self.height;
+    }
+
+    /// Return raw RGBA bytes of Image, row by row from the top left corner
+    pub fn pixels(&self) -> &[u8] {
+        return &self.pixels;
+    }
+    /// Return mutable raw RGBA bytes of Image, row by row from the top left corner
+    pub fn pixels_mut(&mut self) -> &mut [u8] {
+        return &mut self.pixels;
+    }
+
+    /// Return color of a pixel, or None if it is out of bounds
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Color32> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = ((y * self.width + x) * 4) as usize;
+        return Some(Color32 {
+            r: self.pixels[i] as u32,
+            g: self.pixels[i + 1] as u32,
+            b: self.pixels[i + 2] as u32,
+            a: self.pixels[i + 3] as u32,
+        });
+    }
+
+    /// Set color of a pixel, pixels out of bounds are ignored
+    pub fn set_pixel(&mut self, x: u32, y: u32, mut color: Color32) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let color = color.normalized();
+        let i = ((y * self.width + x) * 4) as usize;
+        self.pixels[i] = color.r as u8;
+        self.pixels[i + 1] = color.g as u8;
+        self.pixels[i + 2] = color.b as u8;
+        self.pixels[i + 3] = color.a as u8;
+    }
+
+    /// Fill the whole Image with a color
+    pub fn fill(&mut self, mut color: Color32) {
+        let color = color.normalized();
+        for pixel in self.pixels.chunks_mut(4) {
+            pixel[0] = color.r as u8;
+            pixel[1] = color.g as u8;
+            pixel[2] = color.b as u8;
+            pixel[3] = color.a as u8;
+        }
+    }
+}
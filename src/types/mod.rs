@@ -1,4 +1,5 @@
 pub mod color;
 pub mod color32;
 pub mod font;
+pub mod image;
 pub mod vector2;
@@ -0,0 +1,64 @@
+use sidekick::app::App;
+use sidekick::entities::circle::Circle;
+use sidekick::entities::line::Line;
+use sidekick::entities::rectangle::Rectangle;
+use sidekick::test::{render_scene, Snapshot};
+use sidekick::types::color::Color;
+use sidekick::types::vector2::Vector2;
+
+// Snapshots are rendered small to keep the stored images small
+const WIDTH: u32 = 64;
+const HEIGHT: u32 = 48;
+
+/// Return App with the size of snapshots
+fn snapshot_app() -> App {
+    let mut app = App::new();
+    app.set_size(WIDTH, HEIGHT);
+    return app;
+}
+
+#[test]
+fn rectangle_draw() {
+    let mut rectangle = Rectangle::new();
+    rectangle.game_object.transform.size = Vector2 { x: 30., y: 20. };
+    rectangle.fill_color = Color::red();
+
+    let image = render_scene(
+        snapshot_app(),
+        |_app: &mut App| {},
+        |app: &mut App| rectangle.draw(app),
+        1,
+    );
+    Snapshot::new().assert_matches("rectangle", &image);
+}
+
+#[test]
+fn circle_draw() {
+    let mut circle = Circle::new();
+    circle.game_object.transform.radius = 15.;
+    circle.fill_color = Color::green();
+
+    let image = render_scene(
+        snapshot_app(),
+        |_app: &mut App| {},
+        |app: &mut App| circle.draw(app),
+        1,
+    );
+    Snapshot::new().assert_matches("circle", &image);
+}
+
+#[test]
+fn line_draw() {
+    let mut line = Line::new();
+    line.game_object.transform.radius = 20.;
+    line.stroke_size = 3.;
+    line.stroke_color = Color::blue();
+
+    let image = render_scene(
+        snapshot_app(),
+        |_app: &mut App| {},
+        |app: &mut App| line.draw(app),
+        1,
+    );
+    Snapshot::new().assert_matches("line", &image);
+}